@@ -0,0 +1,611 @@
+//! A command-line client of Mastodon built on mastors.
+//!
+//! The connection settings are loaded from ".env" in the current working directory in the same way as `Connection::new()`,
+//! or from the file given with `--env`.
+mod output;
+
+use std::{
+    error::Error,
+    io::Read,
+    process,
+};
+use clap::{ Args, Parser, Subcommand, ValueEnum };
+use mastors::{
+    Connection,
+    DateTime,
+    Method,
+    MethodWithRespHeader,
+    Utc,
+    api::v1::{
+        accounts,
+        instance,
+        lists,
+        media,
+        notifications,
+        streaming,
+        timelines,
+    },
+    entities::{
+        AccountId,
+        ListId,
+        MediaId,
+        NotificationId,
+        Status,
+        StatusId,
+        Visibility,
+    },
+    helpers::{
+        self,
+        DraftPoll,
+        PostedDraft,
+        Scheduler,
+        ServerConfiguration,
+        StatusDraft,
+    },
+    streaming::StreamType,
+};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// A command-line client of Mastodon.
+#[derive(Debug, Parser)]
+#[command(name = "mastors", version)]
+struct Cli {
+    /// Print the responses as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Load the connection settings from this file instead of ".env".
+    #[arg(long, global = true, value_name = "FILE", env = "MASTORS_ENV")]
+    env: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Post or schedule a status.
+    Toot(TootArgs),
+
+    /// Show statuses of a timeline.
+    Timeline {
+        #[command(subcommand)]
+        timeline: TimelineCommand,
+
+        #[command(flatten)]
+        page: PageArgs,
+    },
+
+    /// Show events of a stream until interrupted.
+    Stream {
+        #[command(subcommand)]
+        stream: StreamCommand,
+    },
+
+    /// Show, dismiss or clear your notifications.
+    Notifications {
+        #[command(subcommand)]
+        command: Option<NotificationsCommand>,
+    },
+
+    /// Follow an account specified by ID or handle.
+    Follow { account: String },
+
+    /// Unfollow an account specified by ID or handle.
+    Unfollow { account: String },
+
+    /// Block an account specified by ID or handle.
+    Block { account: String },
+
+    /// Mute an account specified by ID or handle.
+    Mute {
+        account: String,
+
+        /// Mute only statuses and keep notifications from the account.
+        #[arg(long)]
+        keep_notifications: bool,
+    },
+
+    /// Show and manage your lists.
+    Lists {
+        #[command(subcommand)]
+        command: Option<ListsCommand>,
+    },
+
+    /// Show information about the server.
+    Instance,
+}
+
+#[derive(Debug, Args)]
+struct TootArgs {
+    /// Status content text. It is read from the standard input if omitted and no media is attached.
+    text: Option<String>,
+
+    /// Content warning shown instead of the text.
+    #[arg(long, value_name = "TEXT")]
+    cw: Option<String>,
+
+    /// Visibility of the status.
+    #[arg(long, value_enum)]
+    visibility: Option<VisibilityArg>,
+
+    /// ISO 639-1 language code of the status.
+    #[arg(long)]
+    language: Option<String>,
+
+    /// ID of the status to reply to.
+    #[arg(long, value_name = "STATUS_ID")]
+    reply_to: Option<String>,
+
+    /// File to upload and attach to the status. Can be given multiple times.
+    #[arg(long, value_name = "FILE", conflicts_with = "poll")]
+    media: Vec<String>,
+
+    /// Mark the attached media as sensitive.
+    #[arg(long)]
+    sensitive: bool,
+
+    /// Option of a poll. Can be given multiple times.
+    #[arg(long, value_name = "OPTION")]
+    poll: Vec<String>,
+
+    /// Seconds until the poll expires.
+    #[arg(long, value_name = "SECONDS", default_value_t = 86400)]
+    poll_expires_in: u64,
+
+    /// Allow multiple choices in the poll.
+    #[arg(long)]
+    poll_multiple: bool,
+
+    /// Hide vote counts until the poll ends.
+    #[arg(long)]
+    poll_hide_totals: bool,
+
+    /// Schedule the status at this RFC 3339 datetime instead of posting it now.
+    #[arg(long, value_name = "DATETIME", value_parser = parse_datetime)]
+    schedule: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum VisibilityArg {
+    Public,
+    Unlisted,
+    Private,
+    Direct,
+}
+
+impl From<VisibilityArg> for Visibility {
+    fn from(visibility: VisibilityArg) -> Self {
+        match visibility {
+            VisibilityArg::Public => Visibility::Public,
+            VisibilityArg::Unlisted => Visibility::Unlisted,
+            VisibilityArg::Private => Visibility::Private,
+            VisibilityArg::Direct => Visibility::Direct,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum TimelineCommand {
+    /// Statuses of the accounts you follow.
+    Home,
+
+    /// Public statuses of this server.
+    Local,
+
+    /// Public statuses of all known servers.
+    Public,
+
+    /// Public statuses with a hashtag.
+    Tag { hashtag: String },
+
+    /// Statuses of the members of a list.
+    List { id: String },
+}
+
+#[derive(Debug, Args)]
+struct PageArgs {
+    /// Max number of statuses per page.
+    #[arg(long, global = true)]
+    limit: Option<usize>,
+
+    /// Show statuses older than this ID.
+    #[arg(long, global = true, value_name = "STATUS_ID")]
+    max_id: Option<String>,
+
+    /// Show the latest statuses newer than this ID.
+    #[arg(long, global = true, value_name = "STATUS_ID")]
+    since_id: Option<String>,
+
+    /// Show statuses immediately newer than this ID.
+    #[arg(long, global = true, value_name = "STATUS_ID")]
+    min_id: Option<String>,
+
+    /// Number of pages to follow toward older statuses.
+    #[arg(long, global = true, default_value_t = 1)]
+    pages: usize,
+}
+
+#[derive(Debug, Subcommand)]
+enum StreamCommand {
+    /// Your home timeline and notifications.
+    User,
+
+    /// Public statuses of all known servers.
+    Public,
+
+    /// Public statuses of this server.
+    Local,
+
+    /// Public statuses of other servers.
+    Remote,
+
+    /// Public statuses with a hashtag.
+    Hashtag {
+        hashtag: String,
+
+        /// Only statuses of this server.
+        #[arg(long)]
+        local: bool,
+    },
+
+    /// Statuses of the members of a list.
+    List { id: String },
+
+    /// Your direct messages.
+    Direct,
+}
+
+impl From<StreamCommand> for StreamType {
+    fn from(stream: StreamCommand) -> Self {
+        match stream {
+            StreamCommand::User => StreamType::User,
+            StreamCommand::Public => StreamType::Public,
+            StreamCommand::Local => StreamType::PublicLocal,
+            StreamCommand::Remote => StreamType::PublicRemote,
+            StreamCommand::Hashtag { hashtag, local: false } => StreamType::Hashtag(hashtag),
+            StreamCommand::Hashtag { hashtag, local: true } => StreamType::HashtagLocal(hashtag),
+            StreamCommand::List { id } => StreamType::List(id),
+            StreamCommand::Direct => StreamType::Direct,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum NotificationsCommand {
+    /// Show your notifications. This is the default.
+    List {
+        /// Max number of notifications.
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Show notifications older than this ID.
+        #[arg(long, value_name = "NOTIFICATION_ID")]
+        max_id: Option<String>,
+    },
+
+    /// Dismiss a notification.
+    Dismiss { id: String },
+
+    /// Clear all of your notifications.
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+enum ListsCommand {
+    /// Show your lists. This is the default.
+    Show,
+
+    /// Create a list.
+    Create { title: String },
+
+    /// Rename a list.
+    Rename { id: String, title: String },
+
+    /// Delete a list.
+    Delete { id: String },
+
+    /// Show member accounts of a list.
+    Members { id: String },
+
+    /// Add accounts specified by ID or handle to a list.
+    Add {
+        id: String,
+        #[arg(required = true)]
+        accounts: Vec<String>,
+    },
+
+    /// Remove accounts specified by ID or handle from a list.
+    Remove {
+        id: String,
+        #[arg(required = true)]
+        accounts: Vec<String>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(cli) {
+        eprintln!("mastors: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    let conn = match cli.env.as_ref() {
+        Some(path) => Connection::from_file(path)?,
+        None => Connection::new()?,
+    };
+    let json = cli.json;
+
+    match cli.command {
+        Command::Toot(args) => toot(&conn, json, args),
+        Command::Timeline { timeline, page } => {
+            let statuses = timeline_statuses(&conn, timeline, &page)?;
+            output::emit(json, &statuses, |s| output::statuses(s))
+        },
+        Command::Stream { stream } => {
+            for event in streaming::get(&conn, stream.into()).send()? {
+                output::event(json, &event?)?;
+            }
+            Ok(())
+        },
+        Command::Notifications { command } => notifications(&conn, json, command),
+        Command::Follow { account } => {
+            let relationship = accounts::id::follow::post(&conn, account_id(&conn, &account)?).send()?;
+            output::emit(json, &relationship, output::relationship)
+        },
+        Command::Unfollow { account } => {
+            let relationship = accounts::id::unfollow::post(&conn, account_id(&conn, &account)?).send()?;
+            output::emit(json, &relationship, output::relationship)
+        },
+        Command::Block { account } => {
+            let relationship = accounts::id::block::post(&conn, account_id(&conn, &account)?).send()?;
+            output::emit(json, &relationship, output::relationship)
+        },
+        Command::Mute { account, keep_notifications } => {
+            let req = accounts::id::mute::post(&conn, account_id(&conn, &account)?);
+            let req = if keep_notifications { req.without_notifications() } else { req };
+            let relationship = req.send()?;
+            output::emit(json, &relationship, output::relationship)
+        },
+        Command::Lists { command } => lists(&conn, json, command.unwrap_or(ListsCommand::Show)),
+        Command::Instance => {
+            let instance = instance::get(&conn).send()?;
+            output::emit(json, &instance, output::instance)
+        },
+    }
+}
+
+fn toot(conn: &Connection, json: bool, args: TootArgs) -> CliResult<()> {
+    let config = ServerConfiguration::from(conn);
+    if args.media.len() > config.max_media_attachments() {
+        return Err(mastors::Error::TooManyAttachmentMediasError(args.media.len(), config.max_media_attachments()).into());
+    }
+
+    let text = match args.text {
+        Some(text) => Some(text),
+        None if args.media.is_empty() => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            Some(text)
+        },
+        None => None,
+    };
+
+    let mut draft = StatusDraft::default();
+    if let Some(text) = text {
+        draft.set_text(text);
+    }
+    draft.set_spoiler_text::<String>(args.cw)
+        .set_visibility(args.visibility.map(Visibility::from))
+        .set_language::<String>(args.language)
+        .set_in_reply_to_id::<String>(args.reply_to)
+        .set_sensitive(args.sensitive);
+
+    if !args.poll.is_empty() {
+        let mut poll = DraftPoll::new(args.poll, args.poll_expires_in);
+        poll.set_multiple(args.poll_multiple)
+            .set_hide_totals(args.poll_hide_totals);
+        draft.set_poll(poll);
+    }
+
+    // Check the draft before uploading media not to leave unused attachments on the server.
+    validate_before_upload(&draft, args.media.len(), &config)?;
+    let media_ids = args.media.iter()
+        .map(|path| media::post(conn, path.as_str()).send().map(|attachment| attachment.id_to_owned()))
+        .collect::<mastors::Result<Vec<MediaId>>>()?;
+    draft.set_media_ids(media_ids);
+
+    match args.schedule {
+        Some(scheduled_at) => {
+            let scheduled = Scheduler::new(conn).schedule(&draft, scheduled_at)?;
+            output::emit(json, &scheduled, output::scheduled_status)
+        },
+        None => match draft.post(conn)? {
+            PostedDraft::Status(status) => output::emit(json, &*status, output::status),
+            PostedDraft::Scheduled(scheduled) => output::emit(json, &*scheduled, output::scheduled_status),
+        },
+    }
+}
+
+// Validate the draft as if `media` files were attached, with distinct placeholder IDs.
+fn validate_before_upload(draft: &StatusDraft, media: usize, config: &ServerConfiguration) -> mastors::Result<()> {
+    let placeholders = (0..media).map(|i| i.to_string()).collect::<Vec<String>>();
+    draft.clone()
+        .set_media_ids(placeholders)
+        .validate(config)
+}
+
+// Each timeline has its own request type, so pagination parameters are set with a macro.
+macro_rules! get_page {
+    ($req:expr, $page:expr, $max_id:expr) => {{
+        let mut req = $req;
+        if let Some(limit) = $page.limit {
+            req = req.limit(limit);
+        }
+        if let Some(max_id) = $max_id {
+            req = req.max_id(max_id);
+        }
+        if let Some(since_id) = $page.since_id.as_ref() {
+            req = req.since_id(since_id.as_str());
+        }
+        if let Some(min_id) = $page.min_id.as_ref() {
+            req = req.min_id(min_id.as_str());
+        }
+        req.send()
+    }};
+}
+
+fn timeline_statuses(conn: &Connection, timeline: TimelineCommand, page: &PageArgs) -> CliResult<Vec<Status>> {
+    let mut statuses: Vec<Status> = Vec::new();
+    let mut max_id: Option<StatusId> = page.max_id.as_deref().map(StatusId::from);
+
+    for _ in 0..page.pages {
+        let max = max_id.clone();
+        let got = match &timeline {
+            TimelineCommand::Home => get_page!(timelines::home::get(conn), page, max),
+            TimelineCommand::Local => get_page!(timelines::public::get(conn).local(), page, max),
+            TimelineCommand::Public => get_page!(timelines::public::get(conn), page, max),
+            TimelineCommand::Tag { hashtag } => get_page!(timelines::tag::get(conn, hashtag.as_str()), page, max),
+            TimelineCommand::List { id } => get_page!(timelines::list::get(conn, id.as_str()), page, max),
+        }?;
+
+        max_id = got.iter().map(|status| status.id()).min().cloned();
+        statuses.extend(got);
+
+        if max_id.is_none() {
+            break;
+        }
+    }
+
+    Ok(statuses)
+}
+
+fn notifications(conn: &Connection, json: bool, command: Option<NotificationsCommand>) -> CliResult<()> {
+    let command = command.unwrap_or(NotificationsCommand::List { limit: None, max_id: None });
+
+    match command {
+        NotificationsCommand::List { limit, max_id } => {
+            let mut req = notifications::get(conn);
+            if let Some(limit) = limit {
+                req = req.limit(limit);
+            }
+            if let Some(max_id) = max_id {
+                req = req.max_id(max_id);
+            }
+            let notifications = req.send()?;
+            output::emit(json, &notifications, |n| output::notifications(n))
+        },
+        NotificationsCommand::Dismiss { id } => {
+            notifications::id::dismiss::post(conn, NotificationId::new(id)).send()?;
+            Ok(())
+        },
+        NotificationsCommand::Clear => {
+            notifications::clear::post(conn).send()?;
+            Ok(())
+        },
+    }
+}
+
+fn lists(conn: &Connection, json: bool, command: ListsCommand) -> CliResult<()> {
+    match command {
+        ListsCommand::Show => {
+            let lists = lists::get(conn).send()?;
+            output::emit(json, &lists, |l| output::lists(l))
+        },
+        ListsCommand::Create { title } => {
+            let list = lists::post(conn, title).send()?;
+            output::emit(json, &list, output::list)
+        },
+        ListsCommand::Rename { id, title } => {
+            let list = lists::id::put(conn, ListId::new(id), title).send()?;
+            output::emit(json, &list, output::list)
+        },
+        ListsCommand::Delete { id } => {
+            lists::id::delete(conn, ListId::new(id)).send()?;
+            Ok(())
+        },
+        ListsCommand::Members { id } => {
+            let (_, accounts) = lists::id::accounts::get(conn, ListId::new(id)).limit(0).send()?;
+            output::emit(json, &accounts, |a| output::accounts(a))
+        },
+        ListsCommand::Add { id, accounts } => {
+            let account_ids = account_ids(conn, &accounts)?;
+            lists::id::accounts::post(conn, ListId::new(id), account_ids).send()?;
+            Ok(())
+        },
+        ListsCommand::Remove { id, accounts } => {
+            let account_ids = account_ids(conn, &accounts)?;
+            lists::id::accounts::delete(conn, ListId::new(id), account_ids).send()?;
+            Ok(())
+        },
+    }
+}
+
+// Numeric arguments are taken as account IDs, and others are resolved as handles.
+fn account_id(conn: &Connection, account: &str) -> CliResult<AccountId> {
+    if !account.is_empty() && account.bytes().all(|c| c.is_ascii_digit()) {
+        return Ok(AccountId::new(account));
+    }
+    Ok(helpers::resolve_account(conn, account)?.id().clone())
+}
+
+fn account_ids(conn: &Connection, accounts: &[String]) -> CliResult<Vec<AccountId>> {
+    accounts.iter()
+        .map(|account| account_id(conn, account))
+        .collect()
+}
+
+fn parse_datetime(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::parse_from(["mastors", "timeline", "tag", "rust", "--limit", "5", "--pages", "3", "--json"]);
+        assert!(cli.json);
+        match cli.command {
+            Command::Timeline { timeline: TimelineCommand::Tag { hashtag }, page } => {
+                assert_eq!(hashtag, "rust");
+                assert_eq!(page.limit, Some(5));
+                assert_eq!(page.pages, 3);
+            },
+            command => panic!("unexpected command: {:?}", command),
+        }
+
+        assert!(Cli::try_parse_from(["mastors", "toot", "hi", "--media", "a.png", "--poll", "yes"]).is_err());
+        assert!(Cli::try_parse_from(["mastors", "toot", "hi", "--schedule", "tomorrow"]).is_err());
+        assert!(Cli::try_parse_from(["mastors", "toot", "hi", "--schedule", "2020-01-01T09:00:00+09:00"]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_before_upload() {
+        let config = ServerConfiguration::default();
+
+        let mut draft = StatusDraft::default();
+        draft.set_text("a".repeat(config.max_characters() + 1));
+        assert!(validate_before_upload(&draft, 2, &config).is_err());
+
+        let mut draft = StatusDraft::default();
+        draft.set_text("hi").set_language("xx");
+        assert!(validate_before_upload(&draft, 1, &config).is_err());
+
+        // A draft without text needs media, but no more than the limit.
+        let draft = StatusDraft::default();
+        assert!(validate_before_upload(&draft, 0, &config).is_err());
+        assert!(validate_before_upload(&draft, config.max_media_attachments(), &config).is_ok());
+        assert!(validate_before_upload(&draft, config.max_media_attachments() + 1, &config).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Human-readable and JSON output of entities.
+use std::error::Error;
+use serde::Serialize;
+use serde_json::{ json, Value };
+use mastors::{
+    entities::{
+        Account,
+        Instance,
+        List,
+        Notification,
+        Relationship,
+        ScheduledStatus,
+        Status,
+    },
+    render::Renderer,
+    streaming::EventType,
+};
+
+/// Print `value` as pretty JSON if `json` is true, otherwise print it with `human`.
+pub fn emit<T>(json: bool, value: &T, human: impl FnOnce(&T)) -> Result<(), Box<dyn Error>>
+where
+    T: Serialize + ?Sized,
+{
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        human(value);
+    }
+    Ok(())
+}
+
+pub fn statuses(statuses: &[Status]) {
+    for s in statuses {
+        status(s);
+    }
+}
+
+pub fn status(status: &Status) {
+    if let Some(reblog) = status.reblog() {
+        println!("{} boosted by @{}", reblog.id(), status.account().acct());
+        return body(reblog);
+    }
+    body(status);
+}
+
+fn body(status: &Status) {
+    println!(
+        "{} @{} {} [{}]",
+        status.id(),
+        status.account().acct(),
+        status.created_at().to_rfc3339(),
+        status.visibility(),
+    );
+    if !status.spoiler_text().is_empty() {
+        println!("CW: {}", status.spoiler_text());
+    }
+
+    let text = Renderer::from_status(status).plain_text(status.content().unwrap_or_default());
+    for line in text.lines() {
+        println!("  {}", line);
+    }
+    for attachment in status.media_attachments() {
+        println!("  [{}] {}", attachment.r#type(), attachment.url());
+    }
+    if let Some(poll) = status.poll() {
+        for option in poll.options() {
+            println!("  ( ) {}", option.title());
+        }
+    }
+    println!();
+}
+
+pub fn scheduled_status(scheduled: &ScheduledStatus) {
+    println!("{} scheduled at {}", scheduled.id(), scheduled.scheduled_at().to_rfc3339());
+    if let Some(text) = scheduled.params().text() {
+        for line in text.lines() {
+            println!("  {}", line);
+        }
+    }
+}
+
+pub fn notifications(notifications: &[Notification]) {
+    for n in notifications {
+        notification(n);
+    }
+}
+
+pub fn notification(notification: &Notification) {
+    println!(
+        "{} {} {} from @{}",
+        notification.id(),
+        notification.created_at().to_rfc3339(),
+        notification.r#type(),
+        notification.account().acct(),
+    );
+    if let Some(s) = notification.status() {
+        status(s);
+    }
+}
+
+pub fn accounts(accounts: &[Account]) {
+    for a in accounts {
+        account(a);
+    }
+}
+
+pub fn account(account: &Account) {
+    println!("{} @{} {}", account.id(), account.acct(), account.display_name());
+}
+
+pub fn relationship(relationship: &Relationship) {
+    let flags = [
+        ("following", relationship.following()),
+        ("requested", relationship.requested()),
+        ("followed by", relationship.followed_by()),
+        ("blocking", relationship.blocking()),
+        ("muting", relationship.muting()),
+    ];
+    let flags = flags.iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>();
+
+    if flags.is_empty() {
+        println!("{}: none", relationship.id());
+    } else {
+        println!("{}: {}", relationship.id(), flags.join(", "));
+    }
+}
+
+pub fn lists(lists: &[List]) {
+    for l in lists {
+        list(l);
+    }
+}
+
+pub fn list(list: &List) {
+    println!("{} {}", list.id(), list.title());
+}
+
+pub fn instance(instance: &Instance) {
+    println!("{} ({})", instance.title(), instance.uri());
+    println!("version: {}", instance.version());
+    println!("users: {}", instance.stats().user_count());
+    println!("statuses: {}", instance.stats().status_count());
+    println!("domains: {}", instance.stats().domain_count());
+    println!("registrations: {}", if instance.registrations() { "open" } else { "closed" });
+    if !instance.short_description().is_empty() {
+        println!();
+        println!("{}", Renderer::new().plain_text(instance.short_description()));
+    }
+}
+
+/// Print an event of streaming, as a single line of JSON if `json` is true.
+pub fn event(json: bool, event: &EventType) -> Result<(), Box<dyn Error>> {
+    if json {
+        println!("{}", serde_json::to_string(&event_json(event)?)?);
+        return Ok(());
+    }
+
+    match event {
+        EventType::Update(s) => status(s),
+        EventType::StatusUpdate(s) => {
+            println!("(edited)");
+            status(s);
+        },
+        EventType::Notification(n) => notification(n),
+        EventType::Delete(id) => println!("{} deleted", id),
+        EventType::Conversation(c) => println!("conversation {} updated", c.id()),
+        EventType::Announcement(a) => println!("announcement {} published", a.id()),
+        EventType::AnnouncementReaction(r) => println!("announcement {} reacted with {}", r.announcement_id(), r.name()),
+        EventType::AnnouncementDelete(id) => println!("announcement {} deleted", id),
+        EventType::EncryptedMessage(_) => println!("encrypted message received"),
+        EventType::FiltersChanged => println!("filters changed"),
+        EventType::Unknown(data) => println!("unknown event: {}", data),
+    }
+    Ok(())
+}
+
+// Events are named as the streaming API names them.
+fn event_json(event: &EventType) -> serde_json::Result<Value> {
+    let (name, payload) = match event {
+        EventType::Update(s) => ("update", serde_json::to_value(s)?),
+        EventType::StatusUpdate(s) => ("status.update", serde_json::to_value(s)?),
+        EventType::Notification(n) => ("notification", serde_json::to_value(n)?),
+        EventType::Delete(id) => ("delete", serde_json::to_value(id)?),
+        EventType::Conversation(c) => ("conversation", serde_json::to_value(c)?),
+        EventType::Announcement(a) => ("announcement", serde_json::to_value(a)?),
+        EventType::AnnouncementReaction(r) => ("announcement.reaction", serde_json::to_value(r)?),
+        EventType::AnnouncementDelete(id) => ("announcement.delete", serde_json::to_value(id)?),
+        EventType::EncryptedMessage(m) => ("encrypted_message", serde_json::to_value(m)?),
+        EventType::FiltersChanged => ("filters_changed", Value::Null),
+        EventType::Unknown(data) => ("unknown", Value::String(data.to_owned())),
+    };
+    Ok(json!({ "event": name, "payload": payload }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_ascii_status() {
+        let mut json = serde_json::from_str::<Value>(include_str!("../../../test-resources/entities/status.json")).unwrap();
+        json["content"] = "<p>こんにちは</p><p>😀 été<br />二行目</p>".into();
+        status(&serde_json::from_value(json).unwrap());
+
+        let mut json = serde_json::from_str::<Value>(include_str!("../../../test-resources/entities/instance.json")).unwrap();
+        json["short_description"] = "<p>日本語のサーバーです</p>".into();
+        instance(&serde_json::from_value(json).unwrap());
+    }
+}
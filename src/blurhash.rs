@@ -0,0 +1,298 @@
+//! This module provides a decoder of [BlurHash](https://blurha.sh/) that is used for placeholders of media attachments.
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::blurhash::Blurhash;
+//!
+//! let blurhash: Blurhash = "LEHV6nWB2yk8pyo0adR*.7kCMdnj".parse()?;
+//! assert_eq!(blurhash.components(), (4, 3));
+//!
+//! let image = blurhash.decode(32, 24, 1.0);
+//! assert_eq!(image.pixels().len(), 32 * 24 * 3);
+//! assert_eq!(image.to_rgba().len(), 32 * 24 * 4);
+//! # Ok(())
+//! # }
+//! ```
+use std::{ f64::consts::PI, str::FromStr };
+use crate::{
+    Error,
+    Result,
+};
+
+const BASE83_CHARACTERS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// A parsed BlurHash that can be decoded to images of any size.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Blurhash {
+    components_x: usize,
+    components_y: usize,
+    // The first is the average color, and the rest are AC components before applying the punch.
+    colors: Vec<[f64; 3]>,
+}
+
+impl Blurhash {
+    /// Parse `hash` as a BlurHash.
+    pub fn parse(hash: &str) -> Result<Self> {
+        let invalid = || Error::InvalidBlurhashError(hash.to_owned());
+
+        if !hash.is_ascii() || hash.len() < 6 {
+            return Err(invalid());
+        }
+
+        let size_flag = decode83(&hash[0..1]).ok_or_else(invalid)?;
+        let components_x = (size_flag % 9) as usize + 1;
+        let components_y = (size_flag / 9) as usize + 1;
+        if hash.len() != 4 + 2 * components_x * components_y {
+            return Err(invalid());
+        }
+
+        let quantised_max_value = decode83(&hash[1..2]).ok_or_else(invalid)?;
+        let max_value = (quantised_max_value + 1) as f64 / 166.0;
+
+        let mut colors = Vec::with_capacity(components_x * components_y);
+        colors.push(decode_dc(decode83(&hash[2..6]).ok_or_else(invalid)?));
+        for i in 1..components_x * components_y {
+            let value = decode83(&hash[4 + i * 2..6 + i * 2]).ok_or_else(invalid)?;
+            colors.push(decode_ac(value, max_value));
+        }
+
+        Ok(Blurhash {
+            components_x,
+            components_y,
+            colors,
+        })
+    }
+
+    /// Get the number of horizontal and vertical components.
+    pub fn components(&self) -> (usize, usize) {
+        (self.components_x, self.components_y)
+    }
+
+    /// Get the average color of the image as RGB.
+    pub fn average_color(&self) -> [u8; 3] {
+        let [r, g, b] = self.colors[0];
+        [linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b)]
+    }
+
+    /// Decode to an RGB image of `width` x `height`.
+    ///
+    /// `punch` adjusts the contrast, `1.0` is the original and a larger value makes colors more vivid.
+    pub fn decode(&self, width: u32, height: u32, punch: f64) -> BlurhashImage {
+        let (width_usize, height_usize) = (width as usize, height as usize);
+
+        // Cosines are the same for every row or column, so calculate them in advance.
+        let cosines_x = cosines(width_usize, self.components_x);
+        let cosines_y = cosines(height_usize, self.components_y);
+
+        let mut pixels = Vec::with_capacity(width_usize * height_usize * 3);
+        for y in 0..height_usize {
+            for x in 0..width_usize {
+                let mut pixel = [0.0; 3];
+                for j in 0..self.components_y {
+                    for i in 0..self.components_x {
+                        let index = i + j * self.components_x;
+                        let factor = if index == 0 { 1.0 } else { punch };
+                        let basis = cosines_x[x * self.components_x + i] * cosines_y[y * self.components_y + j] * factor;
+                        let color = self.colors[index];
+                        pixel[0] += color[0] * basis;
+                        pixel[1] += color[1] * basis;
+                        pixel[2] += color[2] * basis;
+                    }
+                }
+                pixels.extend(pixel.iter().map(|value| linear_to_srgb(*value)));
+            }
+        }
+
+        BlurhashImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+impl FromStr for Blurhash {
+    type Err = Error;
+
+    fn from_str(hash: &str) -> Result<Self> {
+        Blurhash::parse(hash)
+    }
+}
+
+/// An RGB image decoded from a BlurHash.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BlurhashImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl BlurhashImage {
+    /// Get the width of this image.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of this image.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get pixels as a row-major RGB buffer.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Get pixels as a row-major RGBA buffer, every alpha value is `255`.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels.chunks(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect()
+    }
+
+    /// Get pixels as a row-major RGB buffer.
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+}
+
+/// Decode `hash` to an RGB buffer of `width` x `height`.
+pub fn decode(hash: &str, width: u32, height: u32, punch: f64) -> Result<Vec<u8>> {
+    Ok(Blurhash::parse(hash)?.decode(width, height, punch).into_pixels())
+}
+
+/// Decode `hash` to an RGBA buffer of `width` x `height`.
+pub fn decode_rgba(hash: &str, width: u32, height: u32, punch: f64) -> Result<Vec<u8>> {
+    Ok(Blurhash::parse(hash)?.decode(width, height, punch).to_rgba())
+}
+
+/// Get the size of an image that has `aspect` ratio and whose longer side is `max_size`.
+pub fn dimensions(aspect: f64, max_size: u32) -> (u32, u32) {
+    if !aspect.is_finite() || aspect <= 0.0 {
+        return (max_size, max_size);
+    }
+
+    if aspect >= 1.0 {
+        (max_size, ((max_size as f64 / aspect).round() as u32).max(1))
+    } else {
+        (((max_size as f64 * aspect).round() as u32).max(1), max_size)
+    }
+}
+
+fn decode83(text: &str) -> Option<u32> {
+    text.bytes().try_fold(0u32, |value, c| {
+        let digit = BASE83_CHARACTERS.iter().position(|d| *d == c)?;
+        Some(value * 83 + digit as u32)
+    })
+}
+
+fn decode_dc(value: u32) -> [f64; 3] {
+    [
+        srgb_to_linear((value >> 16) as u8),
+        srgb_to_linear((value >> 8) as u8),
+        srgb_to_linear(value as u8),
+    ]
+}
+
+fn decode_ac(value: u32, max_value: f64) -> [f64; 3] {
+    let quantised = [value / (19 * 19), (value / 19) % 19, value % 19];
+    let mut color = [0.0; 3];
+    for (channel, q) in color.iter_mut().zip(quantised.iter()) {
+        let normalized = (*q as f64 - 9.0) / 9.0;
+        *channel = normalized.signum() * normalized.powi(2) * max_value;
+    }
+    color
+}
+
+fn cosines(size: usize, components: usize) -> Vec<f64> {
+    let mut cosines = Vec::with_capacity(size * components);
+    for position in 0..size {
+        for component in 0..components {
+            cosines.push((PI * position as f64 * component as f64 / size as f64).cos());
+        }
+    }
+    cosines
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (srgb * 255.0 + 0.5) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode83(mut value: u32, length: usize) -> String {
+        let mut text = vec![b'0'; length];
+        for c in text.iter_mut().rev() {
+            *c = BASE83_CHARACTERS[(value % 83) as usize];
+            value /= 83;
+        }
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let blurhash = Blurhash::parse("LEHV6nWB2yk8pyo0adR*.7kCMdnj").unwrap();
+        assert_eq!(blurhash.components(), (4, 3));
+        assert_eq!(blurhash.colors.len(), 12);
+
+        assert!(Blurhash::parse("").is_err());
+        assert!(Blurhash::parse("LEHV6nWB2yk8pyo0adR*.7kCMdn").is_err());
+        assert!(Blurhash::parse("LEHV6nWB2yk8pyo0adR*.7kCMdn\"").is_err());
+        assert!(Blurhash::parse("LEHV6nWB2yk8pyo0adR*.7kCMdnあ").is_err());
+    }
+
+    #[test]
+    fn test_decode_solid_color() {
+        let hash = format!("00{}", encode83(0x33_99_cc, 4));
+        let blurhash = Blurhash::parse(&hash).unwrap();
+        assert_eq!(blurhash.average_color(), [0x33, 0x99, 0xcc]);
+
+        let pixels = decode(&hash, 3, 2, 1.0).unwrap();
+        assert_eq!(pixels, [0x33, 0x99, 0xcc].repeat(6));
+
+        let pixels = decode_rgba(&hash, 1, 1, 1.0).unwrap();
+        assert_eq!(pixels, vec![0x33, 0x99, 0xcc, 0xff]);
+    }
+
+    #[test]
+    fn test_decode_punch() {
+        // 2x1 components, dark to light from left to right.
+        let hash = format!("1L{}{}", encode83(0x80_80_80, 4), encode83(0, 2));
+        let image = Blurhash::parse(&hash).unwrap().decode(4, 1, 1.0);
+        let left = image.pixels()[0];
+        let right = image.pixels()[9];
+        assert!(left < right);
+
+        let punched = Blurhash::parse(&hash).unwrap().decode(4, 1, 2.0);
+        assert!(punched.pixels()[0] < left);
+        assert!(punched.pixels()[9] > right);
+        assert_eq!(Blurhash::parse(&hash).unwrap().decode(4, 1, 0.0).pixels(), [0x80; 12]);
+    }
+
+    #[test]
+    fn test_dimensions() {
+        assert_eq!(dimensions(16.0 / 9.0, 32), (32, 18));
+        assert_eq!(dimensions(0.75, 32), (24, 32));
+        assert_eq!(dimensions(1000.0, 32), (32, 1));
+        assert_eq!(dimensions(f64::NAN, 32), (32, 32));
+    }
+}
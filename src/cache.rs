@@ -0,0 +1,503 @@
+//! This module provides a cache of responses of GET requests with conditional requests.
+//!
+//! The cache is an in-memory LRU with an optional on-disk backend, and it is keyed by the HTTP method and URL.
+//! It honours `Cache-Control`, and revalidates stale responses with `If-None-Match` and `If-Modified-Since`
+//! if the server sent `ETag` or `Last-Modified`.
+//! Cache hits, revalidations and misses are reported through `log` at the debug level.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use std::time::Duration;
+//! use mastors::prelude::*;
+//! use mastors::cache::ResponseCache;
+//!
+//! let mut conn = Connection::from_file(".env.test")?;
+//! conn.set_cache(
+//!     ResponseCache::new(256)
+//!         .disk("./cache")
+//!         .ttl("/api/v1/custom_emojis", Duration::from_secs(60 * 60))
+//!         .ttl("/api/v1/accounts/:id", Duration::from_secs(5 * 60))
+//! );
+//!
+//! // The second call is served from the cache.
+//! let emojis = mastors::api::v1::custom_emojis::get(&conn).send()?;
+//! let emojis = mastors::api::v1::custom_emojis::get(&conn).send()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{ BTreeMap, HashMap },
+    fs,
+    path::{ Path, PathBuf },
+    sync::Mutex,
+    time::{ Duration, SystemTime, UNIX_EPOCH },
+};
+use log::{ debug, trace, warn };
+use reqwest::{
+    header,
+    blocking::Request,
+};
+use serde::{ Deserialize, Serialize };
+use crate::{
+    Connection,
+    Result,
+    utils,
+};
+
+/// The default number of responses stored on disk.
+pub const DEFAULT_DISK_CAPACITY: usize = 1024;
+
+const HTTP_NOT_MODIFIED: u16 = 304;
+const PATH_PARAM_PLACEHOLDER: &str = "_PATH_PARAM_";
+const PATH_PARAM_NAME: &str = ":id";
+
+/// A cache of responses of GET requests that can be set to [`Connection`](../struct.Connection.html).
+///
+/// The cache is shared with clones of the `Connection` that it is set to.
+/// The on-disk backend stores responses for the access token as they are, so do not share the directory between accounts.
+#[derive(Debug)]
+pub struct ResponseCache {
+    capacity: usize,
+    dir: Option<PathBuf>,
+    disk_capacity: usize,
+    ttls: HashMap<String, Duration>,
+    lru: Mutex<Lru>,
+}
+
+impl ResponseCache {
+    /// Create a new cache that holds `capacity` responses in memory.
+    pub fn new(capacity: usize) -> Self {
+        ResponseCache {
+            capacity,
+            dir: None,
+            disk_capacity: DEFAULT_DISK_CAPACITY,
+            ttls: HashMap::new(),
+            lru: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Store responses in `dir` too, they are kept after being evicted from memory and across processes up to the disk capacity.
+    pub fn disk(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Set the number of responses stored on disk, the least recently stored responses are removed beyond it.
+    ///
+    /// Defaults to [`DEFAULT_DISK_CAPACITY`](./constant.DEFAULT_DISK_CAPACITY.html).
+    pub fn disk_capacity(mut self, capacity: usize) -> Self {
+        self.disk_capacity = capacity;
+        self
+    }
+
+    /// Consider responses of `endpoint` such as `/api/v1/instance` fresh for `ttl` regardless of `Cache-Control`.
+    ///
+    /// The path parameter of the endpoint is written as `:id` such as `/api/v1/accounts/:id`.
+    pub fn ttl(mut self, endpoint: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint.into(), ttl);
+        self
+    }
+
+    /// Get the number of responses in memory.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Get whether no response is in memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all responses from memory and disk.
+    pub fn clear(&self) -> Result<()> {
+        *self.lock() = Lru::default();
+
+        if let Some(dir) = &self.dir {
+            for path in disk_entries(dir)? {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Send a GET request of `endpoint` through this cache.
+    pub(crate) fn send(&self, conn: &Connection, endpoint: &str, mut req: Request) -> Result<CachedResponse> {
+        let key = format!("{} {}", req.method(), req.url());
+        let now = unix_time(SystemTime::now());
+        let ttl = self.ttls.get(&endpoint.replace(PATH_PARAM_PLACEHOLDER, PATH_PARAM_NAME)).copied();
+
+        let cached = self.load(&key);
+        if let Some(cached) = &cached {
+            if cached.is_fresh(now) {
+                debug!("Cache hit: {}", key);
+                return Ok(cached.clone());
+            }
+
+            if let Some(etag) = &cached.etag {
+                req.headers_mut().insert(header::IF_NONE_MATCH, header::HeaderValue::from_str(etag)?);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                req.headers_mut().insert(header::IF_MODIFIED_SINCE, header::HeaderValue::from_str(last_modified)?);
+            }
+        }
+
+        trace!("Send a {} request to {}", req.method(), req.url());
+        let res = conn.client().execute(req)?;
+        trace!("{:?}", res);
+
+        if let Some(cached) = cached.filter(|_| res.status().as_u16() == HTTP_NOT_MODIFIED) {
+            debug!("Cache revalidated: {}", key);
+            let revalidated = cached.revalidate(&res, now, ttl);
+            self.store(&key, &revalidated);
+            return Ok(revalidated);
+        }
+
+        debug!("Cache miss: {}", key);
+        let res = utils::check_response(res)?;
+        let policy = CachePolicy::from_headers(res.headers());
+        let headers = res.headers().iter()
+            .filter_map(|(name, value)| Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let response = CachedResponse {
+            key: key.clone(),
+            etag: policy.etag.clone(),
+            last_modified: policy.last_modified.clone(),
+            fresh_until: policy.fresh_until(now, ttl),
+            headers,
+            body: res.text()?,
+        };
+
+        if policy.is_storable(ttl) {
+            self.store(&key, &response);
+        }
+        Ok(response)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.lru.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn load(&self, key: &str) -> Option<CachedResponse> {
+        if let Some(response) = self.lock().get(key) {
+            return Some(response);
+        }
+
+        let path = self.path(key)?;
+        let response = fs::read_to_string(&path).ok()
+            .and_then(|json| serde_json::from_str::<CachedResponse>(&json).ok())
+            .filter(|response| response.key == key)?;
+        trace!("Load {} from {}", key, path.display());
+
+        self.lock().insert(key, response.clone(), self.capacity);
+        Some(response)
+    }
+
+    fn store(&self, key: &str, response: &CachedResponse) {
+        self.lock().insert(key, response.clone(), self.capacity);
+
+        if let Some(path) = self.path(key) {
+            let is_new = !path.exists();
+            if let Err(e) = write_file(&path, response) {
+                warn!("Failed to write the cache of {} to {}: {}", key, path.display(), e);
+            } else if is_new {
+                if let Err(e) = self.trim_disk(&path) {
+                    warn!("Failed to remove old caches from {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    // Remove the least recently stored files except `stored` beyond the disk capacity.
+    fn trim_disk(&self, stored: &Path) -> Result<()> {
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => return Ok(()),
+        };
+
+        let entries = disk_entries(dir)?;
+        if entries.len() <= self.disk_capacity {
+            return Ok(());
+        }
+
+        let mut modified = entries.into_iter()
+            .filter(|path| path != stored)
+            .map(|path| Ok((fs::metadata(&path)?.modified()?, path)))
+            .collect::<Result<Vec<(SystemTime, PathBuf)>>>()?;
+        modified.sort();
+
+        let excess = modified.len() + 1 - self.disk_capacity.max(1);
+        for (_, path) in modified.into_iter().take(excess) {
+            trace!("Evict {} from the disk cache", path.display());
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("{:016x}.json", fnv1a(key))))
+    }
+}
+
+// A response stored in the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fresh_until: u64,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl CachedResponse {
+    // Get the value of the response header `name`.
+    pub(crate) fn header(&self, name: &str) -> Option<String> {
+        self.headers.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.to_owned())
+    }
+
+    // Deserialize the body as JSON.
+    pub(crate) fn json<T: for<'de> Deserialize<'de>>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+
+    fn is_fresh(&self, now: u64) -> bool {
+        now < self.fresh_until
+    }
+
+    // Update the freshness and headers with a `304 Not Modified` response.
+    fn revalidate(mut self, res: &reqwest::blocking::Response, now: u64, ttl: Option<Duration>) -> Self {
+        let policy = CachePolicy::from_headers(res.headers());
+        self.fresh_until = policy.fresh_until(now, ttl);
+        if policy.etag.is_some() {
+            self.etag = policy.etag;
+        }
+        if policy.last_modified.is_some() {
+            self.last_modified = policy.last_modified;
+        }
+        self
+    }
+}
+
+// Caching directives of a response.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+struct CachePolicy {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+    age: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CachePolicy {
+    fn from_headers(headers: &header::HeaderMap) -> Self {
+        let value = |name: header::HeaderName| headers.get(name).and_then(|value| value.to_str().ok()).map(str::to_owned);
+
+        let mut policy = CachePolicy {
+            age: value(header::AGE).and_then(|age| age.trim().parse().ok()).unwrap_or(0),
+            etag: value(header::ETAG),
+            last_modified: value(header::LAST_MODIFIED),
+            ..CachePolicy::default()
+        };
+
+        for directive in headers.get_all(header::CACHE_CONTROL).iter().filter_map(|value| value.to_str().ok()).flat_map(|value| value.split(',')) {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.split_once('=') {
+                Some(("max-age", seconds)) => policy.max_age = seconds.trim_matches('"').parse().ok(),
+                None if directive == "no-store" => policy.no_store = true,
+                None if directive == "no-cache" => policy.no_cache = true,
+                _ => (),
+            }
+        }
+        policy
+    }
+
+    fn fresh_until(&self, now: u64, ttl: Option<Duration>) -> u64 {
+        if let Some(ttl) = ttl {
+            return now + ttl.as_secs();
+        }
+        if self.no_cache {
+            return now;
+        }
+        now + self.max_age.unwrap_or(0).saturating_sub(self.age)
+    }
+
+    // A response can be stored if it can be used without a request or revalidated.
+    fn is_storable(&self, ttl: Option<Duration>) -> bool {
+        if ttl.is_some() {
+            return true;
+        }
+        !self.no_store && (self.max_age.unwrap_or(0) > self.age || self.etag.is_some() || self.last_modified.is_some())
+    }
+}
+
+// Least recently used responses, `order` maps the last used tick to the key.
+#[derive(Debug, Default)]
+struct Lru {
+    tick: u64,
+    entries: HashMap<String, (u64, CachedResponse)>,
+    order: BTreeMap<u64, String>,
+}
+
+impl Lru {
+    fn get(&mut self, key: &str) -> Option<CachedResponse> {
+        self.tick += 1;
+        let (tick, response) = self.entries.get_mut(key)?;
+        self.order.remove(tick);
+        self.order.insert(self.tick, key.to_owned());
+        *tick = self.tick;
+        Some(response.clone())
+    }
+
+    fn insert(&mut self, key: &str, response: CachedResponse, capacity: usize) {
+        self.tick += 1;
+        if let Some((tick, _)) = self.entries.insert(key.to_owned(), (self.tick, response)) {
+            self.order.remove(&tick);
+        }
+        self.order.insert(self.tick, key.to_owned());
+
+        while self.entries.len() > capacity {
+            let oldest = match self.order.keys().next() {
+                Some(tick) => *tick,
+                None => break,
+            };
+            if let Some(key) = self.order.remove(&oldest) {
+                trace!("Evict {} from the cache", key);
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+fn disk_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+fn write_file(path: &Path, response: &CachedResponse) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string(response)?)?;
+    Ok(())
+}
+
+// File names must be stable across processes, so do not use the randomized hasher of std.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(key: &str, fresh_until: u64) -> CachedResponse {
+        CachedResponse {
+            key: key.to_owned(),
+            etag: Some("W/\"abc\"".to_owned()),
+            last_modified: None,
+            fresh_until,
+            headers: vec![("link".to_owned(), "<https://example.com/api/v1/blocks?max_id=1>; rel=\"next\"".to_owned())],
+            body: "[]".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_cache_policy() {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::CACHE_CONTROL, header::HeaderValue::from_static("public, max-age=180"));
+        headers.insert(header::AGE, header::HeaderValue::from_static("30"));
+        let policy = CachePolicy::from_headers(&headers);
+        assert_eq!(policy.max_age, Some(180));
+        assert_eq!(policy.fresh_until(1000, None), 1150);
+        assert_eq!(policy.fresh_until(1000, Some(Duration::from_secs(10))), 1010);
+        assert!(policy.is_storable(None));
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::CACHE_CONTROL, header::HeaderValue::from_static("private, no-store"));
+        let policy = CachePolicy::from_headers(&headers);
+        assert!(!policy.is_storable(None));
+        assert!(policy.is_storable(Some(Duration::from_secs(10))));
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::CACHE_CONTROL, header::HeaderValue::from_static("max-age=0, private, must-revalidate"));
+        headers.insert(header::ETAG, header::HeaderValue::from_static("W/\"abc\""));
+        let policy = CachePolicy::from_headers(&headers);
+        assert_eq!(policy.fresh_until(1000, None), 1000);
+        assert!(policy.is_storable(None));
+        assert_eq!(policy.etag.as_deref(), Some("W/\"abc\""));
+    }
+
+    #[test]
+    fn test_lru() {
+        let mut lru = Lru::default();
+        lru.insert("a", response("a", 0), 2);
+        lru.insert("b", response("b", 0), 2);
+        assert!(lru.get("a").is_some());
+
+        lru.insert("c", response("c", 0), 2);
+        assert!(lru.get("b").is_none());
+        assert!(lru.get("a").is_some());
+        assert!(lru.get("c").is_some());
+        assert_eq!(lru.entries.len(), lru.order.len());
+    }
+
+    #[test]
+    fn test_disk() {
+        let dir = std::env::temp_dir().join(format!("mastors-cache-test-{}", std::process::id()));
+        let key = "GET https://example.com/api/v1/blocks";
+
+        let cache = ResponseCache::new(1).disk(&dir);
+        cache.store(key, &response(key, 100));
+        assert_eq!(cache.len(), 1);
+
+        let cache = ResponseCache::new(1).disk(&dir);
+        let loaded = cache.load(key).unwrap();
+        assert!(loaded.is_fresh(99));
+        assert!(!loaded.is_fresh(100));
+        assert_eq!(loaded.header("Link").as_deref(), Some("<https://example.com/api/v1/blocks?max_id=1>; rel=\"next\""));
+        assert_eq!(loaded.json::<Vec<String>>().unwrap(), Vec::<String>::new());
+        assert!(cache.load("GET https://example.com/api/v1/mutes").is_none());
+
+        cache.clear().unwrap();
+        assert!(cache.is_empty());
+        assert!(ResponseCache::new(1).disk(&dir).load(key).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_capacity() {
+        let dir = std::env::temp_dir().join(format!("mastors-cache-capacity-test-{}", std::process::id()));
+        let cache = ResponseCache::new(1).disk(&dir).disk_capacity(3);
+
+        for i in 0..10 {
+            let key = format!("GET https://example.com/api/v1/timelines/home?max_id={}", i);
+            cache.store(&key, &response(&key, 100));
+            assert!(disk_entries(&dir).unwrap().len() <= 3);
+        }
+        assert_eq!(disk_entries(&dir).unwrap().len(), 3);
+
+        // The latest response is always kept.
+        let key = "GET https://example.com/api/v1/timelines/home?max_id=9";
+        assert!(ResponseCache::new(1).disk(&dir).load(key).is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
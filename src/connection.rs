@@ -1,16 +1,24 @@
+use std::sync::{ Arc, RwLock };
+use std::time::Duration;
 use log::{ debug, trace };
 use isolang::Language;
-use reqwest::blocking::Client;
+use reqwest::{
+    header,
+    blocking::Client,
+};
 use crate::{
     Error,
+    Method,
     Result,
     Url,
+    cache::ResponseCache,
 };
 
 
 const DEFAULT_ENV_PATH: &str = ".env";
 const DEFAULT_ENV_TEST_PATH: &str = ".env.test";
 const ENV_SERVER_URL: &str = "SERVER_URL";
+const ENV_STREAMING_URL: &str = "STREAMING_URL";
 const ENV_ACCESS_TOKEN: &str = "ACCESS_TOKEN";
 const ENV_USER_AGENT: &str = "USER_AGENT";
 const ENV_DEFAULT_LANGUAGE: &str = "DEFAULT_LANGUAGE";
@@ -27,11 +35,15 @@ const DEFAULT_STATUS_MAX_CHARACTERS: &str = "500";
 const DEFAULT_STATUS_MAX_MEDIAS: &str = "4";
 const DEFAULT_POLL_MAX_OPTIONS: &str = "4";
 
+// Reads of the streaming timeline time out at this interval to detect stalled streams and closing.
+const STREAMING_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A `Connection` contains HTTP client and some settings to use REST API of the Mastodon server.
 /// 
 /// The Connection loads following variables from `.env`:
 /// 
 /// - `SERVER_URL`: Mastodon server URL.
+/// - `STREAMING_URL`: Streaming server URL. This setting is optional and discovered from `/api/v1/instance` by default.
 /// - `ACCESS_TOKEN`: A OAuth access token string generated by Mastodon server.
 /// - `USER_AGENT`: A string that send to Mastodon server as User-Agent http header. This setting is optional.
 /// - `DEFAULT_LANGUAGE`: Language set when status is posted. This setting must to be ISO639-1 compliant and is optional.
@@ -43,6 +55,7 @@ const DEFAULT_POLL_MAX_OPTIONS: &str = "4";
 /// 
 /// ```bash
 /// SERVER_URL="https://mastodon.social"
+/// STREAMING_URL="https://streaming.mastodon.social"
 /// ACCESS_TOKEN="ABCabc_ABCDEFG012345678_HIJKLMNhijklmn00000"
 /// USER_AGENT="MyApp"
 /// DEFAULT_LANGUAGE="ja"
@@ -67,6 +80,9 @@ pub struct Connection {
     whitelist_mode: bool,
     public_timeline_preview_disabled: bool,
     client: Client,
+    streaming_url: Arc<RwLock<Option<Url>>>,
+    streaming_client: Client,
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl Connection {
@@ -151,12 +167,32 @@ impl Connection {
         let public_timeline_preview_disabled = env::var(ENV_PUBLIC_TIMELINE_PREVIEW_DISABLED).is_ok();
         trace!("PUBLIC_TIMELINE_PREVIEW_DISABLED: {}", public_timeline_preview_disabled);
 
+        let streaming_url = match env::var(ENV_STREAMING_URL).ok() {
+            Some(url) => Some(to_http_scheme(Url::parse(&url)?)),
+            None => None,
+        };
+        trace!("STREAMING_URL: {:?}", streaming_url);
+
         let client = Client::builder()
             .gzip(true)
             .user_agent(&user_agent)
             .build()
             .map_err(Error::HttpClientError)?;
 
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            header::HeaderValue::from_str(format!("Bearer {}", access_token).as_str())?
+        );
+
+        // The blocking client applies the timeout to each read, so it works as the read timeout of the streaming timeline.
+        let streaming_client = Client::builder()
+            .default_headers(headers)
+            .user_agent(&user_agent)
+            .timeout(STREAMING_READ_TIMEOUT)
+            .build()
+            .map_err(Error::HttpClientError)?;
+
         debug!("Completed to construct a Connection");
         Ok(Connection {
             server,
@@ -169,9 +205,32 @@ impl Connection {
             whitelist_mode,
             public_timeline_preview_disabled,
             client,
+            streaming_url: Arc::new(RwLock::new(streaming_url)),
+            streaming_client,
+            cache: None,
         })
     }
 
+    // A connection with default settings that is not configured by env files, for tests that do not send requests.
+    #[cfg(test)]
+    pub(crate) fn offline() -> Self {
+        Connection {
+            server: Url::parse("https://example.com").unwrap(),
+            access_token: String::new(),
+            user_agent: DEFAULT_USER_AGENT.to_owned(),
+            default_language: None,
+            status_max_characters: DEFAULT_STATUS_MAX_CHARACTERS.parse().unwrap(),
+            status_max_medias: DEFAULT_STATUS_MAX_MEDIAS.parse().unwrap(),
+            poll_max_options: DEFAULT_POLL_MAX_OPTIONS.parse().unwrap(),
+            whitelist_mode: false,
+            public_timeline_preview_disabled: false,
+            client: Client::new(),
+            streaming_url: Arc::new(RwLock::new(None)),
+            streaming_client: Client::new(),
+            cache: None,
+        }
+    }
+
     /// Get the server URL.
     pub fn server_url(&self) -> &Url {
         &self.server
@@ -222,10 +281,71 @@ impl Connection {
         self.public_timeline_preview_disabled
     }
 
+    /// Get the base URL of the streaming server.
+    ///
+    /// If `STREAMING_URL` is not set and [`set_streaming_url()`](#method.set_streaming_url) has not been called,
+    /// the URL is discovered from `urls.streaming_api` of `/api/v1/instance` on first call and cached.
+    /// The cache is shared with clones of this `Connection`.
+    pub fn streaming_url(&self) -> Result<Url> {
+        if let Some(url) = self.streaming_url.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Ok(url.clone());
+        }
+
+        debug!("Discover the streaming URL from the instance information");
+        let instance = crate::api::v1::instance::get(self).send()?;
+        let url = to_http_scheme(instance.urls().streaming_api().clone());
+        trace!("Discovered streaming URL: {}", url);
+
+        *self.streaming_url.write().unwrap_or_else(|e| e.into_inner()) = Some(url.clone());
+        Ok(url)
+    }
+
+    /// Set the base URL of the streaming server instead of discovering it.
+    ///
+    /// `ws` and `wss` schemes are replaced with `http` and `https`.
+    /// This setting is not shared with clones that have been made before.
+    pub fn set_streaming_url(&mut self, url: Url) {
+        self.streaming_url = Arc::new(RwLock::new(Some(to_http_scheme(url))));
+    }
+
+    /// Set `cache` to cache responses of GET requests.
+    ///
+    /// The cache is shared with clones of this `Connection` that are made after this call.
+    pub fn set_cache(&mut self, cache: ResponseCache) {
+        self.cache = Some(Arc::new(cache));
+    }
+
+    /// Stop caching responses.
+    pub fn remove_cache(&mut self) {
+        self.cache = None;
+    }
+
+    /// Get the response cache if set.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
     // Get the reqwest::Client.
     pub(crate) fn client(&self) -> &Client {
         &self.client
     }
+
+    // Get the reqwest::Client for the streaming timeline, it sends the access token with every request.
+    pub(crate) fn streaming_client(&self) -> &Client {
+        &self.streaming_client
+    }
+}
+
+// Server-sent events are served over HTTP on the same host as WebSocket, so convert the scheme.
+fn to_http_scheme(mut url: Url) -> Url {
+    let scheme = match url.scheme() {
+        "wss" => "https",
+        "ws" => "http",
+        _ => return url,
+    };
+    // Both schemes are special, so replacing each other never fails.
+    let _ = url.set_scheme(scheme);
+    url
 }
 
 trait FromEnv<T> {
@@ -259,3 +379,21 @@ impl FromEnv<Url> for Url {
         Ok(Url::parse(&url)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_http_scheme() {
+        let url = to_http_scheme(Url::parse("wss://streaming.example.com").unwrap());
+        assert_eq!(url.as_str(), "https://streaming.example.com/");
+        assert_eq!(url.join("/api/v1/streaming/user").unwrap().as_str(), "https://streaming.example.com/api/v1/streaming/user");
+
+        let url = to_http_scheme(Url::parse("ws://localhost:4000").unwrap());
+        assert_eq!(url.as_str(), "http://localhost:4000/");
+
+        let url = to_http_scheme(Url::parse("https://example.com").unwrap());
+        assert_eq!(url.as_str(), "https://example.com/");
+    }
+}
@@ -1,21 +1,26 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Utc,
     Url,
 };
+use crate::utils::{
+    transform_datetime_to_string,
+    transform_option_datetime_to_string,
+};
 use super::{
+    AccountId,
     Emoji,
     Entity,
     Privacy,
 };
 
 /// Represents a user of Mastodon and their associated profile.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Account {
     // Base attributes
     #[mastors(identifier)]
-    id: String, // cast from an integer, but not guaranteed to be a number
+    id: AccountId, // cast from an integer, but not guaranteed to be a number
 
     username: String,
     acct: String,
@@ -33,21 +38,24 @@ pub struct Account {
     discoverable: Option<bool>,
 
     // Statistical attributes
+    #[serde(serialize_with = "transform_datetime_to_string")]
     created_at: DateTime<Utc>,
     statuses_count: usize,
     followers_count: usize,
     following_count: usize,
 
     // Optional attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
     moved: Option<Box<Account>>,
     fields: Option<Vec<Field>>,
     bot: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     source: Option<Source>,
 }
 
 impl Account {
     /// Get an ID of this account.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &AccountId {
         &self.id
     }
 
@@ -167,10 +175,11 @@ impl Account {
 }
 
 /// Represents a profile field as a name-value pair with optional verification.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
 pub struct Field {
     name: String,
     value: String, // html
+    #[serde(serialize_with = "transform_option_datetime_to_string")]
     verified_at: Option<DateTime<Utc>>,
 }
 
@@ -192,7 +201,7 @@ impl Field {
 }
 
 /// Represents display or publishing preferences of user's own account. Returned as an additional entity when verifying and updated credentials, as an attribute of Account.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
 pub struct Source {
     // Base attributes
     note: String,
@@ -240,3 +249,21 @@ impl Source {
 /// Represents an array of [`Account`](./struct.Account.html)s.
 pub type Accounts = Vec<Account>;
 impl Entity for Accounts {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let account: Account = assert_round_trip(include_str!("../../test-resources/entities/account.json"));
+        assert_eq!(account.created_at().to_rfc3339(), "2019-11-26T00:00:00+00:00");
+        assert!(account.source().is_some());
+        assert!(account.fields().unwrap()[0].verified_at().is_some());
+
+        let account: Account = assert_round_trip(include_str!("../../test-resources/entities/account_moved.json"));
+        assert_eq!(account.moved().unwrap().acct(), "bob@new.example");
+        assert!(account.source().is_none());
+    }
+}
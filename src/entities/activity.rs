@@ -1,21 +1,23 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::utils::transform_string_to_usize;
 use crate::utils::transform_string_to_i64;
+use crate::utils::transform_usize_to_string;
+use crate::utils::transform_i64_to_string;
 use super::Entity;
 
 /// Represents a weekly bucket of instance activity.
-#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Copy, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Copy, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Activity {
-    #[serde(deserialize_with = "transform_string_to_i64")]
+    #[serde(deserialize_with = "transform_string_to_i64", serialize_with = "transform_i64_to_string")]
     week: i64,
 
-    #[serde(deserialize_with = "transform_string_to_usize")]
+    #[serde(deserialize_with = "transform_string_to_usize", serialize_with = "transform_usize_to_string")]
     statuses: usize,
 
-    #[serde(deserialize_with = "transform_string_to_usize")]
+    #[serde(deserialize_with = "transform_string_to_usize", serialize_with = "transform_usize_to_string")]
     logins: usize,
 
-    #[serde(deserialize_with = "transform_string_to_usize")]
+    #[serde(deserialize_with = "transform_string_to_usize", serialize_with = "transform_usize_to_string")]
     registrations: usize,
 }
 
@@ -44,3 +46,15 @@ impl Activity {
 /// Represents an array of [`Activity`](./struct.Activity.html)s.
 pub type Activities = Vec<Activity>;
 impl Entity for Activities {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let activity: Activity = assert_round_trip(include_str!("../../test-resources/entities/activity.json"));
+        assert_eq!(activity.week(), 1574640000);
+    }
+}
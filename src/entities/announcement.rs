@@ -0,0 +1,220 @@
+use serde::{ Deserialize, Serialize };
+use crate::{
+    DateTime,
+    Url,
+    Utc,
+};
+use crate::utils::{
+    transform_datetime_to_string,
+    transform_option_datetime_to_string,
+};
+use super::{
+    AnnouncementId,
+    Emoji,
+    Entity,
+    Mention,
+    StatusId,
+    Tag,
+};
+
+/// Represents an announcement set by an administrator.
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct Announcement {
+    // Required attributes
+    #[mastors(identifier)]
+    id: AnnouncementId,
+
+    content: String,
+    all_day: bool,
+    #[serde(serialize_with = "transform_datetime_to_string")]
+    published_at: DateTime<Utc>,
+    #[serde(serialize_with = "transform_datetime_to_string")]
+    updated_at: DateTime<Utc>,
+    mentions: Vec<Mention>,
+    statuses: Vec<AnnouncementStatus>,
+    tags: Vec<Tag>,
+    emojis: Vec<Emoji>,
+    reactions: Vec<Reaction>,
+
+    // Optional attributes
+    #[serde(serialize_with = "transform_option_datetime_to_string")]
+    starts_at: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "transform_option_datetime_to_string")]
+    ends_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read: Option<bool>,
+}
+
+impl Announcement {
+    /// Get the ID of this announcement in the database.
+    pub fn id(&self) -> &AnnouncementId {
+        &self.id
+    }
+
+    /// Get the text of this announcement as HTML.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Get whether this announcement should start and end on dates only instead of datetimes.
+    pub fn all_day(&self) -> bool {
+        self.all_day
+    }
+
+    /// Get the date time when this announcement was published.
+    pub fn published_at(&self) -> DateTime<Utc> {
+        self.published_at
+    }
+
+    /// Get the date time when this announcement was last updated.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Get accounts mentioned in this announcement text.
+    pub fn mentions(&self) -> &Vec<Mention> {
+        &self.mentions
+    }
+
+    /// Get statuses linked in this announcement text.
+    pub fn statuses(&self) -> &Vec<AnnouncementStatus> {
+        &self.statuses
+    }
+
+    /// Get tags linked in this announcement text.
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    /// Get custom emojis used in this announcement text.
+    pub fn emojis(&self) -> &Vec<Emoji> {
+        &self.emojis
+    }
+
+    /// Get emoji reactions attached to this announcement.
+    pub fn reactions(&self) -> &Vec<Reaction> {
+        &self.reactions
+    }
+
+    /// Get the date time when this announcement will start.
+    pub fn starts_at(&self) -> Option<DateTime<Utc>> {
+        self.starts_at
+    }
+
+    /// Get the date time when this announcement will end.
+    pub fn ends_at(&self) -> Option<DateTime<Utc>> {
+        self.ends_at
+    }
+
+    /// Get whether authorized user has read this announcement.
+    pub fn read(&self) -> bool {
+        self.read.unwrap_or(false)
+    }
+}
+
+/// Represents an array of [`Announcement`](./struct.Announcement.html)s.
+pub type Announcements = Vec<Announcement>;
+impl Entity for Announcements {}
+
+/// Represents a status linked in an announcement text.
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
+pub struct AnnouncementStatus {
+    id: StatusId,
+    url: Url,
+}
+
+impl AnnouncementStatus {
+    /// Get the ID of the linked status in the database.
+    pub fn id(&self) -> &StatusId {
+        &self.id
+    }
+
+    /// Get the URL of the linked status.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+/// Represents an emoji reaction to an announcement.
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
+pub struct Reaction {
+    // Required attributes
+    name: String,
+    count: usize,
+
+    // Optional attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<Url>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    static_url: Option<Url>,
+}
+
+impl Reaction {
+    /// Get the emoji used for the reaction. Either a unicode emoji, or a custom emoji's shortcode.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the total number of users who have added this reaction.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get whether authorized user has added this reaction.
+    pub fn me(&self) -> bool {
+        self.me.unwrap_or(false)
+    }
+
+    /// Get a link to the custom emoji if the reaction is a custom emoji.
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Get a link to a non-animated version of the custom emoji if the reaction is a custom emoji.
+    pub fn static_url(&self) -> Option<&Url> {
+        self.static_url.as_ref()
+    }
+}
+
+/// Represents a change of the emoji reaction to an announcement that is received from the streaming timeline.
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct AnnouncementReaction {
+    name: String,
+    count: usize,
+    announcement_id: AnnouncementId,
+}
+
+impl AnnouncementReaction {
+    /// Get the emoji used for the reaction. Either a unicode emoji, or a custom emoji's shortcode.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the total number of users who have added this reaction.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the ID of the announcement that is reacted.
+    pub fn announcement_id(&self) -> &AnnouncementId {
+        &self.announcement_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let announcement: Announcement = assert_round_trip(include_str!("../../test-resources/entities/announcement.json"));
+        assert_eq!(announcement.reactions().len(), 2);
+        assert_eq!(announcement.reactions()[1].name(), "👍");
+
+        let reaction: AnnouncementReaction = assert_round_trip(include_str!("../../test-resources/entities/announcement_reaction.json"));
+        assert_eq!(reaction.count(), 5);
+    }
+}
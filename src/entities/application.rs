@@ -1,17 +1,20 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 
 /// Represents an application that interfaces with the REST API to access accounts or post statuses.
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Application {
     // Required attributes
     name: String,
 
     // Optional attributes
     website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     vapid_key: Option<String>,
 
     // Client attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
     client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     client_secret: Option<String>,
 }
 
@@ -41,3 +44,16 @@ impl Application {
         self.client_secret.as_deref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let application: Application = assert_round_trip(include_str!("../../test-resources/entities/application.json"));
+        assert_eq!(application.name(), "mastors");
+        assert!(application.client_id().is_some());
+    }
+}
@@ -1,18 +1,23 @@
-use serde::Deserialize;
-use crate::Url;
+use serde::{ Deserialize, Serialize };
+use crate::{
+    Url,
+    blurhash::{ Blurhash, BlurhashImage },
+};
+use super::MediaId;
 
 /// Represents a file or media attachment that can be added to a status.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Attachment {
     // Required attributes
     #[mastors(identifier)]
-    id: String,
+    id: MediaId,
     r#type: AttachmentType,
     url: crate::Url,
     preview_url: crate::Url,
 
     // Optional attributes
     remote_url: Option<crate::Url>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     text_url: Option<crate::Url>,
     meta: Option<AttachmentMeta>,
     description: Option<String>,
@@ -21,12 +26,12 @@ pub struct Attachment {
 
 impl Attachment {
     /// Get the ID of this attachment in the database.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &MediaId {
         &self.id
     }
 
     /// Get the ID of this attachment in the database.
-    pub fn id_to_owned(&self) -> String {
+    pub fn id_to_owned(&self) -> MediaId {
         self.id.clone()
     }
 
@@ -70,6 +75,23 @@ impl Attachment {
         self.blurhash.as_deref()
     }
 
+    /// Get the size of a placeholder whose longer side is `max_size`, from the aspect ratio of this attachment.
+    ///
+    /// The size is a square if the aspect ratio is unknown.
+    pub fn blurhash_dimensions(&self, max_size: u32) -> (u32, u32) {
+        let aspect = self.meta.as_ref().and_then(AttachmentMeta::aspect_ratio).unwrap_or(1.0);
+        crate::blurhash::dimensions(aspect, max_size)
+    }
+
+    /// Decode the BlurHash of this attachment to an RGB placeholder whose longer side is `max_size`.
+    ///
+    /// Returns `None` if this attachment has no BlurHash.
+    pub fn decode_blurhash(&self, max_size: u32, punch: f64) -> Option<crate::Result<BlurhashImage>> {
+        let hash = self.blurhash.as_deref()?;
+        let (width, height) = self.blurhash_dimensions(max_size);
+        Some(Blurhash::parse(hash).map(|blurhash| blurhash.decode(width, height, punch)))
+    }
+
     /// Get the type of this attachment as `AttachmentType`.
     /// 
     /// This method is an alias of `r#type()`.
@@ -104,20 +126,33 @@ impl Attachment {
 }
 
 /// Metadata returned by Paperclip.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize)]
 pub struct AttachmentMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
     length: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     fps: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     aspect: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     audio_encode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     audio_bitrate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     audio_channels: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     original: Option<AttachmentMetaSub>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     small: Option<AttachmentMetaSub>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     focus: Option<Focus>,
 }
 
@@ -186,21 +221,35 @@ impl AttachmentMeta {
     pub fn focus(&self) -> Option<&Focus> {
         self.focus.as_ref()
     }
+
+    /// Get the aspect ratio from `small`, `original` or this metadata in that order.
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        self.small.as_ref().and_then(AttachmentMetaSub::aspect_ratio)
+            .or_else(|| self.original.as_ref().and_then(AttachmentMetaSub::aspect_ratio))
+            .or_else(|| aspect_ratio(self.aspect, self.width, self.height))
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize)]
 pub struct AttachmentMetaSub {
     // for Image, Gifv, Video
+    #[serde(skip_serializing_if = "Option::is_none")]
     width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     aspect: Option<f64>,
 
     // for Audio, Gifv, Video
+    #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     bitrate: Option<u32>,
 
     // for Gifv, Video
+    #[serde(skip_serializing_if = "Option::is_none")]
     frame_rate: Option<String>,
 }
 
@@ -239,6 +288,19 @@ impl AttachmentMetaSub {
     pub fn frame_rate(&self) -> Option<&str> {
         self.frame_rate.as_deref()
     }
+
+    /// Get the aspect ratio from `aspect`, or `width` and `height`.
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        aspect_ratio(self.aspect, self.width, self.height)
+    }
+}
+
+fn aspect_ratio(aspect: Option<f64>, width: Option<u32>, height: Option<u32>) -> Option<f64> {
+    match (aspect, width, height) {
+        (Some(aspect), _, _) if aspect > 0.0 => Some(aspect),
+        (_, Some(width), Some(height)) if width > 0 && height > 0 => Some(width as f64 / height as f64),
+        _ => None,
+    }
 }
 
 /// In summary, floating points range from -1.0 to 1.0, left-to-right or bottom-to-top.
@@ -247,7 +309,7 @@ impl AttachmentMetaSub {
 /// (0.5, 0.5) would be in the center of the upper-right quadrant.
 /// (-0.5, -0.5) would be in the center of the lower-left quadrant.
 /// For reference, thumbnails in the Mastodon frontend are most commonly 16:9.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Deserialize, Serialize)]
 pub struct Focus {
     x: f64,
     y: f64,
@@ -317,7 +379,16 @@ impl FromStr for AttachmentType {
     }
 }
 
-use serde::de;
+use serde::{ ser, de };
+
+impl ser::Serialize for AttachmentType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
 
 impl<'de> de::Deserialize<'de> for AttachmentType {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
@@ -331,3 +402,20 @@ impl<'de> de::Deserialize<'de> for AttachmentType {
 		}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let attachment: Attachment = assert_round_trip(include_str!("../../test-resources/entities/attachment.json"));
+        assert!(attachment.is_image());
+        assert!(attachment.meta().unwrap().focus().is_some());
+
+        let attachment: Attachment = assert_round_trip(include_str!("../../test-resources/entities/attachment_video.json"));
+        assert!(attachment.is_video());
+        assert_eq!(attachment.meta().unwrap().duration(), Some(88.65));
+    }
+}
@@ -1,12 +1,14 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::Url;
 use crate::utils::{
     transform_string_to_option_string,
     transform_string_to_option_url,
+    transform_option_string_to_string,
+    transform_option_url_to_string,
 };
 
 /// Represents a rich preview card that is generated using OpenGraph tags from a URL.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Card {
     // Required attributes
     url: Url,
@@ -19,19 +21,19 @@ pub struct Card {
      * These are optional, but they are never set to null or the key itself is omitted.
      * If nothing a value, an empty string is set.
      */
-    #[serde(deserialize_with = "transform_string_to_option_string")]
+    #[serde(deserialize_with = "transform_string_to_option_string", serialize_with = "transform_option_string_to_string")]
     author_name: Option<String>,
-    #[serde(deserialize_with = "transform_string_to_option_url")]
+    #[serde(deserialize_with = "transform_string_to_option_url", serialize_with = "transform_option_url_to_string")]
     author_url: Option<Url>,
-    #[serde(deserialize_with = "transform_string_to_option_string")]
+    #[serde(deserialize_with = "transform_string_to_option_string", serialize_with = "transform_option_string_to_string")]
     provider_name: Option<String>,
-    #[serde(deserialize_with = "transform_string_to_option_url")]
+    #[serde(deserialize_with = "transform_string_to_option_url", serialize_with = "transform_option_url_to_string")]
     provider_url: Option<Url>,
     html: Option<String>,
     width: Option<u32>,
     height: Option<u32>,
     image: Option<Url>,
-    #[serde(deserialize_with = "transform_string_to_option_url")]
+    #[serde(deserialize_with = "transform_string_to_option_url", serialize_with = "transform_option_url_to_string")]
     embed_url: Option<Url>,
 }
 
@@ -172,7 +174,16 @@ impl FromStr for CardType {
     }
 }
 
-use serde::de;
+use serde::{ ser, de };
+
+impl ser::Serialize for CardType {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
 
 impl<'de> de::Deserialize<'de> for CardType {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
@@ -186,3 +197,17 @@ impl<'de> de::Deserialize<'de> for CardType {
 		}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let card: Card = assert_round_trip(include_str!("../../test-resources/entities/card.json"));
+        assert!(card.is_video());
+        assert_eq!(card.author_name(), Some("YOGSCAST Lewis & Simon"));
+        assert_eq!(card.embed_url(), None);
+    }
+}
@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use super::{
     Status,
 };
 
 /// Represents the tree around the given status.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Context {
     // Required attributes
     ancestors: Vec<Status>,
@@ -22,3 +22,15 @@ impl Context {
         &self.descendants
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let context: Context = assert_round_trip(include_str!("../../test-resources/entities/context.json"));
+        assert_eq!(context.ancestors().len(), 1);
+    }
+}
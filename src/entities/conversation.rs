@@ -0,0 +1,60 @@
+use serde::{ Deserialize, Serialize };
+use super::{
+    Account,
+    ConversationId,
+    Entity,
+    Status,
+};
+
+/// Represents a conversation with "direct message" visibility.
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct Conversation {
+    // Required attributes
+    #[mastors(identifier)]
+    id: ConversationId,
+
+    accounts: Vec<Account>,
+    unread: bool,
+
+    // Optional attributes
+    last_status: Option<Box<Status>>,
+}
+
+impl Conversation {
+    /// Get the ID of this conversation in the database.
+    pub fn id(&self) -> &ConversationId {
+        &self.id
+    }
+
+    /// Get participants in this conversation.
+    pub fn accounts(&self) -> &Vec<Account> {
+        &self.accounts
+    }
+
+    /// Get whether this conversation is marked as unread.
+    pub fn unread(&self) -> bool {
+        self.unread
+    }
+
+    /// Get the last status in this conversation, to be used for optional display.
+    pub fn last_status(&self) -> Option<&Status> {
+        self.last_status.as_deref()
+    }
+}
+
+/// Represents an array of [`Conversation`](./struct.Conversation.html)s.
+pub type Conversations = Vec<Conversation>;
+impl Entity for Conversations {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let conversation: Conversation = assert_round_trip(include_str!("../../test-resources/entities/conversation.json"));
+        assert_eq!(conversation.accounts().len(), 1);
+        assert!(conversation.last_status().is_some());
+    }
+}
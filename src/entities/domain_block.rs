@@ -0,0 +1,4 @@
+/// Represents domains that you are blocking.
+///
+/// This is the same type as `Peers`, a list of domain names.
+pub type DomainBlocks = Vec<String>;
@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::Url;
 use super::Entity;
 
 
 /// Represents a custom emoji.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Emoji {
     #[mastors(identifier)]
     shortcode: String,
@@ -44,3 +44,15 @@ impl Emoji {
 /// Represents an array of [`Emoji`](./struct.Emoji.html)s.
 pub type Emojis = Vec<Emoji>;
 impl Entity for Emojis {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let emoji: Emoji = assert_round_trip(include_str!("../../test-resources/entities/emoji.json"));
+        assert_eq!(emoji.shortcode(), "blobaww");
+    }
+}
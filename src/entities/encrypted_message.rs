@@ -0,0 +1,81 @@
+use serde::{ Deserialize, Serialize };
+use crate::{
+    DateTime,
+    Utc,
+};
+use crate::utils::transform_datetime_to_string;
+
+/// Represents an end-to-end encrypted message that is received from the streaming timeline.
+///
+/// ## Note
+///
+/// End-to-end encryption is an experimental feature of the Mastodon.
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct EncryptedMessage {
+    // Required attributes
+    #[mastors(identifier)]
+    id: String,
+
+    account_id: String,
+    device_id: String,
+    r#type: u32,
+    body: String,
+    digest: String,
+    message_franking: String,
+    #[serde(serialize_with = "transform_datetime_to_string")]
+    created_at: DateTime<Utc>,
+}
+
+impl EncryptedMessage {
+    /// Get the ID of this message in the database.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the ID of the account that sent this message.
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// Get the ID of the device that sent this message.
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Get the type of this message, `0` for a pre-key message and `1` for a normal message.
+    pub fn r#type(&self) -> u32 {
+        self.r#type
+    }
+
+    /// Get the encrypted body of this message.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Get the digest of this message.
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    /// Get the message franking of this message used for reporting.
+    pub fn message_franking(&self) -> &str {
+        &self.message_franking
+    }
+
+    /// Get the date time when this message was created.
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let message: EncryptedMessage = assert_round_trip(include_str!("../../test-resources/entities/encrypted_message.json"));
+        assert_eq!(message.r#type(), 0);
+    }
+}
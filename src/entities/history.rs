@@ -1,19 +1,21 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::utils::{
     transform_string_to_u64,
     transform_string_to_usize,
+    transform_u64_to_string,
+    transform_usize_to_string,
 };
 
 /// Represents daily usage history of a hashtag.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Copy, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Copy, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct History {
-    #[serde(deserialize_with = "transform_string_to_u64")]
+    #[serde(deserialize_with = "transform_string_to_u64", serialize_with = "transform_u64_to_string")]
     day: u64,
 
-    #[serde(deserialize_with = "transform_string_to_usize")]
+    #[serde(deserialize_with = "transform_string_to_usize", serialize_with = "transform_usize_to_string")]
     uses: usize,
 
-    #[serde(deserialize_with = "transform_string_to_usize")]
+    #[serde(deserialize_with = "transform_string_to_usize", serialize_with = "transform_usize_to_string")]
     accounts: usize,
 }
 
@@ -33,3 +35,16 @@ impl History {
         self.accounts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let history: History = assert_round_trip(include_str!("../../test-resources/entities/history.json"));
+        assert_eq!(history.day(), 1574553600);
+        assert_eq!(history.accounts(), 31);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::Deref,
+};
+use chrono::{ DateTime, TimeZone, Utc };
+use serde::{ Deserialize, Serialize };
+
+// Lower 16 bits of snowflake IDs are the sequence data, and the rest is milliseconds since the epoch.
+// See lib/mastodon/snowflake.rb of Mastodon.
+const SNOWFLAKE_SEQUENCE_BITS: u32 = 16;
+
+// Mastodon IDs are cast from integers, so shorter IDs are smaller.
+// Non-numeric IDs are not guaranteed to be ordered, so they are just compared as strings after all numeric IDs to keep the order total.
+pub(crate) fn compare_ids(a: &str, b: &str) -> Ordering {
+    let is_numeric = |id: &str| !id.is_empty() && id.bytes().all(|c| c.is_ascii_digit());
+
+    match (is_numeric(a), is_numeric(b)) {
+        (true, true) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    }
+}
+
+macro_rules! define_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        ///
+        /// IDs are ordered as numbers if they are numeric strings.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Create an ID from `id`.
+            pub fn new(id: impl Into<String>) -> Self {
+                $name(id.into())
+            }
+
+            /// Get this ID as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Convert this ID into a string.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                compare_ids(&self.0, &other.0)
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(id: &String) -> Self {
+                $name(id.to_owned())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                $name(id.to_owned())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+define_id!(
+    /// An ID of [`Account`](./struct.Account.html).
+    AccountId
+);
+
+define_id!(
+    /// An ID of [`Announcement`](./struct.Announcement.html).
+    AnnouncementId
+);
+
+define_id!(
+    /// An ID of [`Conversation`](./struct.Conversation.html).
+    ConversationId
+);
+
+define_id!(
+    /// An ID of [`List`](./struct.List.html).
+    ListId
+);
+
+define_id!(
+    /// An ID of [`Attachment`](./struct.Attachment.html).
+    MediaId
+);
+
+define_id!(
+    /// An ID of [`Notification`](./struct.Notification.html).
+    NotificationId
+);
+
+define_id!(
+    /// An ID of [`Poll`](./struct.Poll.html).
+    PollId
+);
+
+define_id!(
+    /// An ID of [`ScheduledStatus`](./struct.ScheduledStatus.html).
+    ScheduledStatusId
+);
+
+define_id!(
+    /// An ID of [`Status`](./struct.Status.html).
+    StatusId
+);
+
+impl StatusId {
+    /// Create the smallest status ID that can be given to a status posted at `datetime`.
+    ///
+    /// Status IDs of Mastodon are snowflakes that encode the time of posting,
+    /// so this ID can be used as `max_id` or `min_id` to get statuses posted before or after `datetime`.
+    /// Datetimes before the Unix epoch are treated as the Unix epoch.
+    pub fn from_datetime(datetime: DateTime<Utc>) -> Self {
+        let millis = datetime.timestamp_millis().max(0) as u64;
+        StatusId((millis << SNOWFLAKE_SEQUENCE_BITS).to_string())
+    }
+
+    /// Get the datetime encoded in this ID, `None` if this ID is not numeric.
+    ///
+    /// Statuses posted before Mastodon v2.0.0 have sequential IDs that do not encode the datetime,
+    /// so the result for them is around the Unix epoch.
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        let id = self.0.parse::<u64>().ok()?;
+        Utc.timestamp_millis_opt((id >> SNOWFLAKE_SEQUENCE_BITS) as i64).single()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ord() {
+        let mut ids: Vec<StatusId> = vec!["100".into(), "99".into(), "103270115826048975".into(), "101".into()];
+        ids.sort();
+        assert_eq!(ids, ["99", "100", "101", "103270115826048975"]);
+
+        assert!(AccountId::new("abc") < AccountId::new("abd"));
+        assert!(AccountId::new("9") < AccountId::new("10"));
+
+        // Numeric IDs come before non-numeric IDs, so that mixed IDs are ordered consistently.
+        assert!(AccountId::new("10") < AccountId::new("1x"));
+        assert!(AccountId::new("9") < AccountId::new("1x"));
+        assert!(AccountId::new("") > AccountId::new("9"));
+        let mut ids: Vec<AccountId> = vec!["1x".into(), "10".into(), "abc".into(), "9".into(), "".into()];
+        ids.sort();
+        assert_eq!(ids, ["9", "10", "", "1x", "abc"]);
+    }
+
+    #[test]
+    fn test_serde() {
+        let id: NotificationId = serde_json::from_str("\"34975861\"").unwrap();
+        assert_eq!(id, "34975861");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"34975861\"");
+    }
+
+    #[test]
+    fn test_datetime() {
+        let id = StatusId::new("103270115826048975");
+        assert_eq!(id.datetime().unwrap().to_rfc3339(), "2019-12-08T03:48:33.849+00:00");
+
+        let id = StatusId::from_datetime(Utc.with_ymd_and_hms(2019, 12, 8, 3, 48, 33).unwrap());
+        assert_eq!(id, "103270115770368000");
+        assert!(id < StatusId::new("103270115826048975"));
+        assert_eq!(id.datetime(), Some(Utc.with_ymd_and_hms(2019, 12, 8, 3, 48, 33).unwrap()));
+
+        assert_eq!(StatusId::from_datetime(Utc.timestamp_opt(-1, 0).unwrap()), "0");
+        assert!(StatusId::new("abc").datetime().is_none());
+    }
+}
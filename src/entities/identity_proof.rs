@@ -1,18 +1,20 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Url,
     Utc,
 };
+use crate::utils::transform_datetime_to_string;
 use super::Entity;
 
 /// Represents a proof from an external identity provider.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct IdentityProof {
     provider: String,
     provider_username: String,
     profile_url: Url,
     proof_url: Url,
+    #[serde(serialize_with = "transform_datetime_to_string")]
     updated_at: DateTime<Utc>,
 }
 
@@ -46,3 +48,15 @@ impl IdentityProof {
 /// Represents an array of [`IdentityProof`](./struct.IdentityProof.html)s.
 pub type IdentityProofs = Vec<IdentityProof>;
 impl Entity for IdentityProofs {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let proof: IdentityProof = assert_round_trip(include_str!("../../test-resources/entities/identity_proof.json"));
+        assert_eq!(proof.provider(), "Keybase");
+    }
+}
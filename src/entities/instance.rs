@@ -1,12 +1,13 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::Url;
+use crate::utils::transform_url_to_string_without_trailing_slash;
 use super::{
     Account,
     Entity,
 };
 
 /// Represents the software instance of Mastodon running on this domain.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Instance {
     uri: String,
     title: String,
@@ -21,6 +22,7 @@ pub struct Instance {
     stats: Stats,
     thumbnail: Option<Url>,
     contact_account: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     invites_enabled: Option<bool>,
 }
 
@@ -97,8 +99,9 @@ impl Instance {
 }
 
 /// URLs of interest for clients apps.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
 pub struct Urls {
+    #[serde(serialize_with = "transform_url_to_string_without_trailing_slash")]
     streaming_api: Url,
 }
 
@@ -110,7 +113,7 @@ impl Urls {
 }
 
 /// Statistics about how much information the instance contains.
-#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Copy, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Copy, Deserialize, Serialize)]
 pub struct Stats {
     user_count: usize,
     status_count: usize,
@@ -136,3 +139,16 @@ impl Stats {
 
 pub type Peers = Vec<String>;
 impl Entity for Peers {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let instance: Instance = assert_round_trip(include_str!("../../test-resources/entities/instance.json"));
+        assert_eq!(instance.uri(), "example.social");
+        assert_eq!(instance.stats().user_count(), 812303);
+    }
+}
@@ -1,26 +1,120 @@
-use serde::Deserialize;
-use super::Entity;
+use serde::{ Deserialize, Serialize };
+use super::{
+    Entity,
+    ListId,
+};
 
 /// Represents a list of some users that the authenticated user follows.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct List {
     #[mastors(identifier)]
-    id: String,
+    id: ListId,
     title: String,
+
+    // Optional attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replies_policy: Option<RepliesPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclusive: Option<bool>,
 }
 
 impl List {
     /// Get the internal database ID of this list.
-    pub fn id(&self) -> &str {
-        self.id.as_str()
+    pub fn id(&self) -> &ListId {
+        &self.id
     }
 
     /// Get the user defined title of this list.
     pub fn title(&self) -> &str {
         self.title.as_str()
     }
+
+    /// Get which replies should be shown in this list.
+    pub fn replies_policy(&self) -> Option<RepliesPolicy> {
+        self.replies_policy
+    }
+
+    /// Get whether members of this list are removed from the home timeline.
+    pub fn exclusive(&self) -> bool {
+        self.exclusive.unwrap_or(false)
+    }
 }
 
 /// Represents an array of [`List`](./struct.List.html)s.
 pub type Lists = Vec<List>;
 impl Entity for Lists {}
+
+/// Represents which replies should be shown in the list.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+pub enum RepliesPolicy {
+    /// Show replies to any followed user.
+    Followed,
+
+    /// Show replies to members of the list.
+    List,
+
+    /// Show replies to no one.
+    None,
+}
+
+use std::fmt;
+use std::str::FromStr;
+
+impl fmt::Display for RepliesPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepliesPolicy::Followed => write!(f, "followed"),
+            RepliesPolicy::List => write!(f, "list"),
+            RepliesPolicy::None => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for RepliesPolicy {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "followed" => Ok(RepliesPolicy::Followed),
+            "list" => Ok(RepliesPolicy::List),
+            "none" => Ok(RepliesPolicy::None),
+            _ => Err(crate::Error::ParseRepliesPolicyError(s.to_owned())),
+        }
+    }
+}
+
+use serde::{ ser, de };
+
+impl ser::Serialize for RepliesPolicy {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RepliesPolicy {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match RepliesPolicy::from_str(s.as_str()) {
+            Ok(r) => Ok(r),
+            Err(e) => Err(de::Error::custom(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let list: List = assert_round_trip(include_str!("../../test-resources/entities/list.json"));
+        assert_eq!(list.replies_policy(), Some(RepliesPolicy::List));
+    }
+}
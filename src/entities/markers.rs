@@ -1,13 +1,16 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{ DateTime, Utc };
+use crate::utils::transform_datetime_to_string;
 
 /// Represents the last read ID of the status and notification.
 /// 
 /// Any element to be None if call for the api method without specified elements.
 /// See [`/api/v1/markers`](../syncronous/methods/api/v1/markers).
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Markers {
+	#[serde(skip_serializing_if = "Option::is_none")]
 	home: Option<Marker>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	notifications: Option<Marker>,
 }
 
@@ -24,9 +27,10 @@ impl Markers {
 }
 
 /// Represents the last read ID of the status or notification.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Marker {
 	last_read_id: String,
+	#[serde(serialize_with = "transform_datetime_to_string")]
 	updated_at: DateTime<Utc>,
 	version: u32,
 }
@@ -49,3 +53,18 @@ impl Marker {
 		self.version
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::entities::assert_round_trip;
+
+	#[test]
+	fn test_round_trip() {
+		let markers: Markers = assert_round_trip(include_str!("../../test-resources/entities/markers.json"));
+		assert_eq!(markers.home().unwrap().last_read_id(), "103194548672408537");
+
+		let markers: Markers = assert_round_trip(r#"{ "notifications": { "last_read_id": "1", "version": 1, "updated_at": "2019-11-26T22:37:25.000Z" } }"#);
+		assert!(markers.home().is_none());
+	}
+}
@@ -1,12 +1,13 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::Url;
+use super::AccountId;
 
 /// Represents a mention of a user within the content of a status.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Mention {
     // Required attributes
     #[mastors(identifier)]
-    id: String,
+    id: AccountId,
 
     username: String,
     url: Url,
@@ -15,7 +16,7 @@ pub struct Mention {
 
 impl Mention {
     /// Get the account id of the mentioned user.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &AccountId {
         &self.id
     }
 
@@ -34,3 +35,15 @@ impl Mention {
         &self.acct
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let mention: Mention = assert_round_trip(include_str!("../../test-resources/entities/mention.json"));
+        assert_eq!(mention.acct(), "alice");
+    }
+}
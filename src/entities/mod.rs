@@ -1,16 +1,21 @@
 //! This module provides entities that are the response of the API methods.
 //! 
 //! e.g. [`mastors::entities::Instance`](./struct.Instance.html) is entity returned by API method [`mastors::api::v1::instance::get()`](../api/v1/instance/fn.get.html).
-pub trait Entity: std::fmt::Debug + std::marker::Sized + for<'de> serde::Deserialize<'de> {}
+pub trait Entity: std::fmt::Debug + std::marker::Sized + serde::Serialize + for<'de> serde::Deserialize<'de> {}
 
 pub(crate) mod account;
 pub(crate) mod activity;
+pub(crate) mod announcement;
 pub(crate) mod application;
 pub(crate) mod attachment;
 pub(crate) mod card;
 pub(crate) mod context;
+pub(crate) mod conversation;
+pub(crate) mod domain_block;
 pub(crate) mod emoji;
+pub(crate) mod encrypted_message;
 pub(crate) mod history;
+pub(crate) mod id;
 pub(crate) mod identity_proof;
 pub(crate) mod instance;
 pub(crate) mod list;
@@ -21,21 +26,28 @@ pub(crate) mod page_navigation;
 pub(crate) mod privacy;
 pub(crate) mod poll;
 pub(crate) mod relationship;
+pub(crate) mod results;
 pub(crate) mod scheduled_status;
 pub(crate) mod status;
 pub(crate) mod tag;
+pub(crate) mod trends_link;
 
 pub use account::{ Account, Accounts };
 pub use activity::{ Activity, Activities };
+pub use announcement::{ Announcement, Announcements, AnnouncementReaction, AnnouncementStatus, Reaction };
 pub use application::Application;
 pub use attachment::Attachment;
 pub use card::Card;
 pub use context::Context;
+pub use conversation::{ Conversation, Conversations };
+pub use domain_block::DomainBlocks;
 pub use emoji::{ Emoji, Emojis };
+pub use encrypted_message::EncryptedMessage;
 pub use history::History;
+pub use id::{ AccountId, AnnouncementId, ConversationId, ListId, MediaId, NotificationId, PollId, ScheduledStatusId, StatusId };
 pub use identity_proof::{ IdentityProof, IdentityProofs };
 pub use instance::Instance;
-pub use list::{ List, Lists };
+pub use list::{ List, Lists, RepliesPolicy };
 pub use markers::{ Marker, Markers };
 pub use mention::Mention;
 pub use notification::{ Notification, Notifications, NotificationType };
@@ -43,10 +55,64 @@ pub use page_navigation::PageNavigation;
 pub use poll::Poll;
 pub use privacy::{ Privacy, Visibility };
 pub use relationship::{ Relationship, Relationships };
+pub use results::SearchResults;
 pub use scheduled_status::{ DeletedScheduledStatus, Params, ScheduledStatus, ScheduledStatuses, ScheduledPoll };
 pub use status::{ Status, Statuses };
 pub use tag::{ Tag, Trends };
+pub use trends_link::{ TrendsLink, TrendsLinks };
 
 /// Represents a no body response.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, serde::Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, serde::Deserialize, serde::Serialize, mastors_derive::Entity)]
 pub struct Nothing {}
+
+/// Assert that `json` is deserialized and serialized again to the same JSON, and return the deserialized entity.
+#[cfg(test)]
+pub(crate) fn assert_round_trip<E>(json: &str) -> E
+where
+    E: serde::Serialize + for<'de> serde::Deserialize<'de>,
+{
+    let expected: serde_json::Value = serde_json::from_str(json).unwrap();
+    let entity: E = serde_json::from_value(expected.clone()).unwrap();
+
+    let serialized = serde_json::to_value(&entity).unwrap();
+    assert_eq!(serialized, expected);
+
+    let deserialized: E = serde_json::from_value(serialized).unwrap();
+    assert_eq!(serde_json::to_value(&deserialized).unwrap(), expected);
+
+    entity
+}
+
+/// Create a plain public status from the status fixture with `id`, posted by the account of `account_id`.
+///
+/// Fields can be patched before the status is deserialized, for tests that need many different statuses.
+#[cfg(test)]
+pub(crate) fn status_fixture(id: &str, account_id: &str) -> serde_json::Value {
+    let mut status: serde_json::Value = serde_json::from_str(include_str!("../../test-resources/entities/status.json")).unwrap();
+    status["id"] = id.into();
+    status["uri"] = format!("https://example.com/statuses/{}", id).into();
+    status["url"] = format!("https://example.com/@user{}/{}", account_id, id).into();
+    status["visibility"] = "public".into();
+    status["spoiler_text"] = "".into();
+    status["media_attachments"] = serde_json::json!([]);
+    status["poll"] = serde_json::Value::Null;
+    status["in_reply_to_id"] = serde_json::Value::Null;
+    status["in_reply_to_account_id"] = serde_json::Value::Null;
+
+    let account = &mut status["account"];
+    account["id"] = account_id.into();
+    account["username"] = format!("user{}", account_id).into();
+    account["acct"] = format!("user{}", account_id).into();
+    account["url"] = format!("https://example.com/@user{}", account_id).into();
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_nothing() {
+        assert_round_trip::<Nothing>("{}");
+    }
+}
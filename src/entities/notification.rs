@@ -1,33 +1,37 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Error,
     Utc,
 };
+use crate::utils::transform_datetime_to_string;
 use super::{
     Account,
     Entity,
+    NotificationId,
     Status,
 };
 
 /// Represents a receive notification for activity on your account or statuses.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Notification {
     // Required attributes
     #[mastors(identifier)]
-    id: String,
+    id: NotificationId,
     r#type: NotificationType,
 
+    #[serde(serialize_with = "transform_datetime_to_string")]
     created_at: DateTime<Utc>,
     account: Box<Account>,
 
     // Optional attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<Box<Status>>,
 }
 
 impl Notification {
     /// Get the id of the notification in the database.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &NotificationId {
         &self.id
     }
 
@@ -203,3 +207,20 @@ impl<'de> de::Deserialize<'de> for NotificationType {
 		}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let notification: Notification = assert_round_trip(include_str!("../../test-resources/entities/notification.json"));
+        assert!(notification.is_favourite());
+        assert!(notification.status().is_some());
+
+        let notification: Notification = assert_round_trip(include_str!("../../test-resources/entities/notification_follow.json"));
+        assert!(notification.is_follow());
+        assert!(notification.status().is_none());
+    }
+}
@@ -1,22 +1,29 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Utc,
 };
-use super::Emoji;
+use crate::utils::transform_datetime_to_string;
+use super::{
+    Emoji,
+    PollId,
+};
 
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 /// Represents a poll attached to a status.
 pub struct Poll {
     #[mastors(identifier)]
-    id: String,
+    id: PollId,
 
+    #[serde(serialize_with = "transform_datetime_to_string")]
     expires_at: DateTime<Utc>,
     expired: bool,
     multiple: bool,
     votes_count: usize,
     voters_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     voted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     own_votes: Option<Vec<u8>>,
     options: Vec<PollOption>,
     emojis: Vec<Emoji>,
@@ -24,7 +31,7 @@ pub struct Poll {
 
 impl Poll {
     /// Get the ID of the poll in the database.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &PollId {
         &self.id
     }
 
@@ -75,7 +82,7 @@ impl Poll {
 }
 
 /// One of the answers for the poll.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
 pub struct PollOption {
     title: String,
     votes_count: Option<usize>,
@@ -92,3 +99,15 @@ impl PollOption {
         self.votes_count
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let poll: Poll = assert_round_trip(include_str!("../../test-resources/entities/poll.json"));
+        assert_eq!(poll.options()[0].title(), "accept");
+    }
+}
@@ -1,11 +1,14 @@
 //! Represents relationship between accounts.
-use serde::Deserialize;
-use super::Entity;
+use serde::{ Deserialize, Serialize };
+use super::{
+    AccountId,
+    Entity,
+};
 
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Relationship {
     #[mastors(identifier)]
-    id: String,
+    id: AccountId,
 
     following: bool,
     requested: bool,
@@ -17,12 +20,16 @@ pub struct Relationship {
     blocking: bool,
     domain_blocking: bool,
     blocked_by: bool,
+
+    // Optional attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notifying: Option<bool>,
 }
 
 impl Relationship {
     /// Get an ID of related account.
-    pub fn id(&self) -> &str {
-        self.id.as_str()
+    pub fn id(&self) -> &AccountId {
+        &self.id
     }
 
     /// Get whether you are following this account.
@@ -60,6 +67,11 @@ impl Relationship {
         self.showing_reblogs
     }
 
+    /// Get whether you get notifications when this account posts a status.
+    pub fn notifying(&self) -> bool {
+        self.notifying.unwrap_or(false)
+    }
+
     /// Get whether you are blocking this account.
     pub fn blocking(&self) -> bool {
         self.blocking
@@ -79,3 +91,15 @@ impl Relationship {
 /// Represents an array of [`Relationship`](./struct.Relationship.html)s.
 pub type Relationships = Vec<Relationship>;
 impl Entity for Relationships {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let relationship: Relationship = assert_round_trip(include_str!("../../test-resources/entities/relationship.json"));
+        assert!(relationship.following());
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{ Deserialize, Serialize };
+use super::{
+    Account,
+    Status,
+    Tag,
+};
+
+/// Represents the results of a search.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct SearchResults {
+    // Required attributes
+    accounts: Vec<Account>,
+    statuses: Vec<Status>,
+    hashtags: Vec<Tag>,
+}
+
+impl SearchResults {
+    /// Get accounts which match the given query.
+    pub fn accounts(&self) -> &Vec<Account> {
+        &self.accounts
+    }
+
+    /// Get statuses which match the given query.
+    pub fn statuses(&self) -> &Vec<Status> {
+        &self.statuses
+    }
+
+    /// Get hashtags which match the given query.
+    pub fn hashtags(&self) -> &Vec<Tag> {
+        &self.hashtags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let results: SearchResults = assert_round_trip(include_str!("../../test-resources/entities/search_results.json"));
+        assert_eq!(results.accounts().len(), 1);
+        assert_eq!(results.hashtags()[0].name(), "mastors");
+    }
+}
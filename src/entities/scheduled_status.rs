@@ -1,22 +1,30 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Utc,
 };
+use crate::utils::{
+    transform_datetime_to_string,
+    transform_option_datetime_to_string,
+};
 use super::{
     Attachment,
     Entity,
+    MediaId,
     Nothing,
+    ScheduledStatusId,
+    StatusId,
     Visibility,
 };
 
 /// Represents a status that will be published at a future scheduled date.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct ScheduledStatus {
     // Required attributes
     #[mastors(identifier)]
-    id: String,
+    id: ScheduledStatusId,
 
+    #[serde(serialize_with = "transform_datetime_to_string")]
     scheduled_at: DateTime<Utc>,
     params: Box<Params>,
     media_attachments: Vec<Attachment>,
@@ -24,7 +32,7 @@ pub struct ScheduledStatus {
 
 impl ScheduledStatus {
     /// Get an ID of this scheduled status in the database.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &ScheduledStatusId {
         &self.id
     }
 
@@ -45,15 +53,16 @@ impl ScheduledStatus {
 }
 
 /// Represents parameters of ScheduledStatus that will toot at scheduled date and time.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Params {
     text: Option<String>,
     application_id: u64,
     visibility: Option<Visibility>,
-    in_reply_to_id: Option<String>,
-    media_ids: Option<Vec<String>>,
+    in_reply_to_id: Option<StatusId>,
+    media_ids: Option<Vec<MediaId>>,
     sensitive: Option<bool>,
     spoiler_text: Option<String>,
+    #[serde(serialize_with = "transform_option_datetime_to_string")]
     scheduled_at: Option<DateTime<Utc>>,
     poll: Option<ScheduledPoll>,
 }
@@ -75,12 +84,12 @@ impl Params {
     }
 
     /// Get a status ID that is this scheduled status will reply to.
-    pub fn in_reply_to_id(&self) -> Option<&str> {
-        self.in_reply_to_id.as_deref()
+    pub fn in_reply_to_id(&self) -> Option<&StatusId> {
+        self.in_reply_to_id.as_ref()
     }
 
     /// Get attached media IDs of this scheduled status.
-    pub fn media_ids(&self) -> Option<&Vec<String>> {
+    pub fn media_ids(&self) -> Option<&Vec<MediaId>> {
         self.media_ids.as_ref()
     }
 
@@ -109,7 +118,7 @@ impl Params {
 }
 
 /// Represents a poll that is containd in ScheduledStatus and will toot at scheduled date and time.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ScheduledPoll {
     multiple: bool,
     hide_totals: bool,
@@ -147,3 +156,16 @@ impl Entity for ScheduledStatuses {}
 /// 
 /// API method `DELETE /api/v1/scheduled_statuses/:id` returns nothing.
 pub type DeletedScheduledStatus = Nothing;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let scheduled: ScheduledStatus = assert_round_trip(include_str!("../../test-resources/entities/scheduled_status.json"));
+        assert_eq!(scheduled.params().application_id(), 596551);
+        assert_eq!(scheduled.params().poll().unwrap().options().len(), 2);
+    }
+}
@@ -1,11 +1,13 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::{
     DateTime,
     Utc,
     Url,
 };
+use crate::utils::transform_datetime_to_string;
 use super::{
     Account,
+    AccountId,
     Application,
     Attachment,
     Card,
@@ -13,18 +15,20 @@ use super::{
     Entity,
     Mention,
     Poll,
+    StatusId,
     Tag,
     Visibility,
 };
 
 /// Represents a status posted by an account.
-#[derive(Debug, Clone, Deserialize, mastors_derive::Entity)]
+#[derive(Debug, Clone, Deserialize, Serialize, mastors_derive::Entity)]
 pub struct Status {
     // Base attributes
     #[mastors(identifier)]
-    id: String,
+    id: StatusId,
 
     uri: Url,
+    #[serde(serialize_with = "transform_datetime_to_string")]
     created_at: DateTime<Utc>,
     account: Box<Account>,
     content: Option<String>,
@@ -32,6 +36,7 @@ pub struct Status {
     sensitive: bool,
     spoiler_text: String,
     media_attachments: Vec<Attachment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     application: Option<Application>,
 
     // Rendering attributes
@@ -46,25 +51,31 @@ pub struct Status {
 
     // Nullable attributes
     url: Option<Url>,
-    in_reply_to_id: Option<String>,
-    in_reply_to_account_id: Option<String>,
+    in_reply_to_id: Option<StatusId>,
+    in_reply_to_account_id: Option<AccountId>,
     reblog: Option<Box<Status>>,
     poll: Option<Poll>,
     card: Option<Card>,
     language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     text: Option<String>,
 
     // Authorized user attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
     favourited: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     reblogged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     muted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     bookmarked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pinned: Option<bool>,
 }
 
 impl Status {
     /// Get the ID of this status in the database.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &StatusId {
         &self.id
     }
 
@@ -154,13 +165,13 @@ impl Status {
     }
 
     /// Get an ID of the status being replied.
-    pub fn in_reply_to_id(&self) -> Option<&str> {
-        self.in_reply_to_id.as_deref()
+    pub fn in_reply_to_id(&self) -> Option<&StatusId> {
+        self.in_reply_to_id.as_ref()
     }
 
     /// Get an ID of the account being replied to.
-    pub fn in_reply_to_account_id(&self) -> Option<&str> {
-        self.in_reply_to_account_id.as_deref()
+    pub fn in_reply_to_account_id(&self) -> Option<&AccountId> {
+        self.in_reply_to_account_id.as_ref()
     }
 
     /// Get the status being reblogged.
@@ -239,3 +250,22 @@ impl Status {
 /// Represents an array of [`Status`](./struct.Status.html)es.
 pub type Statuses = Vec<Status>;
 impl Entity for Statuses {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let status: Status = assert_round_trip(include_str!("../../test-resources/entities/status.json"));
+        assert!(status.is_unlisted());
+        assert!(status.poll().is_some());
+        assert!(status.card().is_some());
+        assert!(status.application().is_some());
+
+        let status: Status = assert_round_trip(include_str!("../../test-resources/entities/status_reblog.json"));
+        assert_eq!(status.reblog().unwrap().id(), "1");
+        assert!(status.text().is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{ Deserialize, Serialize };
 use crate::Url;
 use super::{
     Entity,
@@ -6,10 +6,11 @@ use super::{
 };
 
 /// Represents a hashtag used within the content of a status.
-#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize)]
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone, Deserialize, Serialize)]
 pub struct Tag {
     name: String,
     url: crate::Url,
+    #[serde(skip_serializing_if = "Option::is_none")]
     history: Option<Vec<History>>,
 }
 
@@ -35,3 +36,15 @@ impl Entity for Tag {}
 /// Represents an array of [`Tag`](./struct.Tag.html)s.
 pub type Trends = Vec<Tag>;
 impl Entity for Trends {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_round_trip() {
+        let tag: Tag = assert_round_trip(include_str!("../../test-resources/entities/tag.json"));
+        assert_eq!(tag.history().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,86 @@
+use std::ops::Deref;
+use serde::{ Deserialize, Serialize };
+use super::{
+    Card,
+    Entity,
+    History,
+};
+
+/// Represents a link that is frequently shared around the server.
+///
+/// This entity is a [`Card`](./struct.Card.html) with the daily usage statistics of the link.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Deserialize, Serialize, mastors_derive::Entity)]
+pub struct TrendsLink {
+    #[serde(flatten)]
+    card: Card,
+
+    history: Vec<History>,
+}
+
+impl TrendsLink {
+    /// Get the preview card of this link.
+    pub fn card(&self) -> &Card {
+        &self.card
+    }
+
+    /// Get usage statistics of this link for given days.
+    pub fn history(&self) -> &Vec<History> {
+        &self.history
+    }
+}
+
+impl Deref for TrendsLink {
+    type Target = Card;
+
+    fn deref(&self) -> &Self::Target {
+        &self.card
+    }
+}
+
+/// Represents an array of [`TrendsLink`](./struct.TrendsLink.html)s.
+pub type TrendsLinks = Vec<TrendsLink>;
+impl Entity for TrendsLinks {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::assert_round_trip;
+
+    #[test]
+    fn test_deserialize_trends_link() {
+        let json = r#"{
+            "url": "https://example.com/news/1",
+            "title": "News",
+            "description": "Something happened",
+            "type": "link",
+            "author_name": "",
+            "author_url": "",
+            "provider_name": "Example",
+            "provider_url": "",
+            "html": "",
+            "width": 400,
+            "height": 200,
+            "image": null,
+            "embed_url": "",
+            "history": [
+                { "day": "1574553600", "uses": "12", "accounts": "9" },
+                { "day": "1574467200", "uses": "0", "accounts": "0" }
+            ]
+        }"#;
+
+        let link = serde_json::from_str::<TrendsLink>(json).unwrap();
+        assert_eq!(link.title(), "News");
+        assert!(link.is_link());
+        assert_eq!(link.provider_name(), Some("Example"));
+        assert_eq!(link.author_name(), None);
+        assert_eq!(link.history().len(), 2);
+        assert_eq!(link.history()[0].uses(), 12);
+        assert_eq!(link.history()[0].day(), 1574553600);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let link: TrendsLink = assert_round_trip(include_str!("../../test-resources/entities/trends_link.json"));
+        assert_eq!(link.history()[0].uses(), 12);
+    }
+}
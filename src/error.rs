@@ -1,4 +1,4 @@
-use chrono::{ DateTime, Utc };
+use chrono::{ DateTime, NaiveDate, Utc };
 use err_derive::Error;
 use serde::Deserialize;
 
@@ -43,6 +43,9 @@ pub enum Error {
     #[error(display = "Size of '{}' is zero", _0)]
     BlankFileError(String),
 
+    #[error(display = "'{}' is not a known export file name", _0)]
+    UnknownExportFileError(String),
+
     #[error(display = "Parse URL error: {}", source)]
     ParseUrlError{
         #[error(source, from)]
@@ -61,6 +64,9 @@ pub enum Error {
     #[error(display = "'{}' is not a valid CardType string", _0)]
     ParseCardTypeError(String),
 
+    #[error(display = "'{}' is not a valid RepliesPolicy string", _0)]
+    ParseRepliesPolicyError(String),
+
     #[error(display = "'{}' is not a valid Scope string", _0)]
     ParseScopeError(String),
 
@@ -82,6 +88,9 @@ pub enum Error {
     #[error(display = "HTTP client error: {} ({}) {}", _0, _1, _2)]
     HttpClientStatusError(Url, u16, Box<ReceivedMessage>),
 
+    #[error(display = "HTTP rate limited: {} (reset at {:?})", _0, _1)]
+    HttpTooManyRequestsError(Url, Option<DateTime<Utc>>),
+
     #[error(display = "HTTP server error: {} ({})", _0, _1)]
     HttpServerStatusError(Url, u16),
 
@@ -114,12 +123,42 @@ pub enum Error {
     ),
     */
 
+    #[error(display = "Streaming timeline has been stalled for {:?}", _0)]
+    StreamStalled(std::time::Duration),
+
+    #[error(display = "Downloaded file from {} is incomplete: expected {} bytes, got {} bytes", _0, _1, _2)]
+    IncompleteDownloadError(Url, u64, u64),
+
+    #[error(display = "Downloaded image from {} has unexpected dimensions: expected {:?}, got {:?}", _0, _1, _2)]
+    MediaDimensionsMismatchError(Url, (u32, u32), (u32, u32)),
+
+    #[error(display = "'{}' is not a valid BlurHash", _0)]
+    InvalidBlurhashError(String),
+
     #[error(display = "Failed to deserialize entity, perhaps, this is a bug of mastors: {}", _0)]
     DeserializeJsonError(
         #[error(source, from)]
         serde_json::error::Error,
     ),
 
+    #[error(display = "Failed to serialize to JSON: {}", _0)]
+    SerializeJsonError(
+        #[error(source, no_from)]
+        serde_json::error::Error,
+    ),
+
+    #[error(display = "Failed to serialize to TOML: {}", _0)]
+    SerializeTomlError(
+        #[error(source, from)]
+        toml::ser::Error,
+    ),
+
+    #[error(display = "Failed to deserialize TOML: {}", _0)]
+    DeserializeTomlError(
+        #[error(source, from)]
+        toml::de::Error,
+    ),
+
     #[error(display = "Received Unknown event type '{}'", _0)]
     UnknownEventTypeError(String),
 
@@ -150,12 +189,27 @@ pub enum Error {
     #[error(display = "Poll option is duplicate")]
     DuplicatePollOptionError,
 
+    #[error(display = "Too many characters in a poll option (max: {}, got: {})", _1, _0)]
+    TooManyPollOptionCharactersError(usize, usize),
+
+    #[error(display = "Poll expiration allows seconds between {} and {} but got {}", _1, _2, _0)]
+    InvalidPollExpirationError(u64, u64, u64),
+
+    #[error(display = "A status cannot have both a poll and media attachments")]
+    PollWithMediaAttachmentsError,
+
     #[error(display = "{} is a past date time", _0)]
     PastDateTimeError(DateTime<Utc>),
 
     #[error(display = "Schedule is too close: now: {}, scheduled: {}", _0, _1)]
     ScheduleTooCloseError(DateTime<Utc>, DateTime<Utc>),
 
+    #[error(display = "Too many scheduled statuses on {}: max: {}", _0, _1)]
+    TooManyDailyScheduledStatusesError(NaiveDate, usize),
+
+    #[error(display = "Too many scheduled statuses: max: {}", _0)]
+    TooManyScheduledStatusesError(usize),
+
     #[error(display = "Voted option is duplicate")]
     DuplicateVoteOptionError,
 
@@ -165,6 +219,15 @@ pub enum Error {
     #[error(display = "Account ID is nothing")]
     NoAccountIdError,
 
+    #[error(display = "Account '{}' is not found", _0)]
+    AccountNotFoundError(String),
+
+    #[error(display = "Status '{}' is not found", _0)]
+    StatusNotFoundError(String),
+
+    #[error(display = "Line {} of CSV is invalid: {}", _0, _1)]
+    InvalidCsvRowError(usize, String),
+
     #[error(display = "No timeline specified")]
     NoTimelineError,
 }
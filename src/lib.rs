@@ -131,7 +131,11 @@ mod error;
 mod synchronous;
 mod utils;
 
+pub mod blurhash;
+pub mod cache;
 pub mod entities;
+pub mod render;
+pub mod status_length;
 //pub mod scope;
 
 pub use connection::Connection;
@@ -150,6 +154,8 @@ pub use current_mode::{
         MethodWithRespHeader,
         api,
     },
+    bot,
+    helpers,
     streaming,
 };
 
@@ -0,0 +1,669 @@
+//! This module provides a renderer that converts HTML content of statuses and accounts into plain text or Markdown.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::render::Renderer;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let status = mastors::api::v1::statuses::id::get(&conn, "1").send()?;
+//!
+//! let renderer = Renderer::from_status(&status);
+//! println!("{}", renderer.plain_text(status.content().unwrap_or_default()));
+//! # Ok(())
+//! # }
+//! ```
+use crate::entities::{
+    Account,
+    Emoji,
+    Mention,
+    Status,
+    Tag,
+};
+
+/// Represents a piece of the rendered content.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Token {
+    /// Plain text that has been decoded HTML entities.
+    Text(String),
+
+    /// A line break by `<br>` or a list item.
+    LineBreak,
+
+    /// A break between paragraphs or other blocks.
+    ParagraphBreak,
+
+    /// A mention of the account, `acct` is always `username@domain`.
+    Mention {
+        /// The `username@domain` of the mentioned account.
+        acct: String,
+        /// The profile URL of the mentioned account.
+        url: String,
+    },
+
+    /// A hashtag, `name` does not have the leading `#`.
+    Hashtag {
+        /// The name of the hashtag.
+        name: String,
+        /// The URL of the hashtag.
+        url: String,
+    },
+
+    /// A link, `text` is the displayed text that may be shortened with `…`.
+    Link {
+        /// The destination of the link.
+        url: String,
+        /// The displayed text of the link.
+        text: String,
+    },
+
+    /// A custom emoji.
+    Emoji {
+        /// The shortcode of the custom emoji without colons.
+        shortcode: String,
+        /// The image URL of the custom emoji.
+        url: String,
+    },
+}
+
+/// A renderer that converts HTML content into tokens, plain text or Markdown.
+///
+/// Mentions, hashtags and custom emojis are resolved with the entities given to the renderer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Renderer<'a> {
+    mentions: &'a [Mention],
+    tags: &'a [Tag],
+    emojis: &'a [Emoji],
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a new `Renderer` that has no entities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new `Renderer` with mentions, tags and emojis of `status`.
+    pub fn from_status(status: &'a Status) -> Self {
+        Renderer {
+            mentions: status.mentions(),
+            tags: status.tags(),
+            emojis: status.emojis(),
+        }
+    }
+
+    /// Create a new `Renderer` with emojis of `account`.
+    pub fn from_account(account: &'a Account) -> Self {
+        Renderer {
+            emojis: account.emojis(),
+            ..Self::default()
+        }
+    }
+
+    /// Set mentions to resolve `acct` of mentioned accounts.
+    pub fn mentions(mut self, mentions: &'a [Mention]) -> Self {
+        self.mentions = mentions;
+        self
+    }
+
+    /// Set tags to resolve names of hashtags.
+    pub fn tags(mut self, tags: &'a [Tag]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Set custom emojis to emit `Token::Emoji`.
+    pub fn emojis(mut self, emojis: &'a [Emoji]) -> Self {
+        self.emojis = emojis;
+        self
+    }
+
+    /// Convert `html` into tokens for custom renderers.
+    pub fn tokens(&self, html: &str) -> Vec<Token> {
+        let mut tokenizer = Tokenizer::new(self);
+
+        for piece in Pieces::new(html) {
+            match piece {
+                Piece::Text(text) => tokenizer.text(&decode_entities(text)),
+                Piece::Tag(tag) => tokenizer.tag(HtmlTag::parse(tag)),
+            }
+        }
+        tokenizer.finish()
+    }
+
+    /// Convert `html` into plain text, links are rendered as the full URLs.
+    pub fn plain_text(&self, html: &str) -> String {
+        self.tokens(html)
+            .into_iter()
+            .map(|token| match token {
+                Token::Text(text) => text,
+                Token::LineBreak => "\n".to_owned(),
+                Token::ParagraphBreak => "\n\n".to_owned(),
+                Token::Mention { acct, .. } => format!("@{}", acct),
+                Token::Hashtag { name, .. } => format!("#{}", name),
+                Token::Link { url, .. } => url,
+                Token::Emoji { shortcode, .. } => format!(":{}:", shortcode),
+            })
+            .collect()
+    }
+
+    /// Convert `html` into Markdown.
+    pub fn markdown(&self, html: &str) -> String {
+        self.tokens(html)
+            .into_iter()
+            .map(|token| match token {
+                Token::Text(text) => escape_markdown(&text),
+                Token::LineBreak => "  \n".to_owned(),
+                Token::ParagraphBreak => "\n\n".to_owned(),
+                Token::Mention { acct, url } => format!("[@{}]({})", escape_markdown(&acct), url),
+                Token::Hashtag { name, url } => format!("[#{}]({})", escape_markdown(&name), url),
+                Token::Link { url, text } if url.contains(text.trim_end_matches('…')) => format!("<{}>", url),
+                Token::Link { url, text } => format!("[{}]({})", escape_markdown(&text), url),
+                Token::Emoji { shortcode, .. } => format!(":{}:", shortcode),
+            })
+            .collect()
+    }
+}
+
+// Builds tokens from pieces of HTML.
+struct Tokenizer<'r, 'a> {
+    renderer: &'r Renderer<'a>,
+    tokens: Vec<Token>,
+    anchor: Option<Anchor>,
+    spans: Vec<SpanKind>,
+    pre: usize,
+}
+
+#[derive(PartialEq)]
+enum SpanKind {
+    Normal,
+    // Hidden part of a shortened link.
+    Invisible,
+    // Displayed part of a shortened link, followed by `…`.
+    Ellipsis,
+}
+
+struct Anchor {
+    href: String,
+    class: String,
+    text: String,
+    visible_text: String,
+}
+
+impl<'r, 'a> Tokenizer<'r, 'a> {
+    fn new(renderer: &'r Renderer<'a>) -> Self {
+        Tokenizer {
+            renderer,
+            tokens: Vec::new(),
+            anchor: None,
+            spans: Vec::new(),
+            pre: 0,
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        // Newlines of the source are not significant except in preformatted blocks.
+        let text = if self.pre > 0 {
+            text.to_owned()
+        } else if text.trim().is_empty() && text.contains('\n') {
+            return;
+        } else {
+            text.replace(['\r', '\n'], " ")
+        };
+
+        if let Some(anchor) = self.anchor.as_mut() {
+            anchor.text.push_str(&text);
+            if !self.spans.contains(&SpanKind::Invisible) {
+                anchor.visible_text.push_str(&text);
+            }
+            return;
+        }
+
+        self.push_text(&text);
+    }
+
+    fn tag(&mut self, tag: HtmlTag) {
+        match (tag.name.as_str(), tag.closing) {
+            ("br", _) => self.push(Token::LineBreak),
+            ("p", false) | ("div", false) | ("blockquote", false) | ("ul", false) | ("ol", false)
+            | ("h1", false) | ("h2", false) | ("h3", false) | ("h4", false) | ("h5", false) | ("h6", false) => {
+                self.push(Token::ParagraphBreak);
+            },
+            ("pre", false) => {
+                self.push(Token::ParagraphBreak);
+                self.pre += 1;
+            },
+            ("pre", true) => self.pre = self.pre.saturating_sub(1),
+            ("li", false) => {
+                self.push(Token::LineBreak);
+                self.push_text("- ");
+            },
+            ("span", false) => {
+                let kind = if tag.has_class("invisible") {
+                    SpanKind::Invisible
+                } else if tag.has_class("ellipsis") {
+                    SpanKind::Ellipsis
+                } else {
+                    SpanKind::Normal
+                };
+                self.spans.push(kind);
+            },
+            ("span", true) => {
+                if let (Some(SpanKind::Ellipsis), Some(anchor)) = (self.spans.pop(), self.anchor.as_mut()) {
+                    anchor.visible_text.push('…');
+                }
+            },
+            ("a", false) => {
+                self.anchor = Some(Anchor {
+                    href: tag.attr("href").unwrap_or_default(),
+                    class: tag.attr("class").unwrap_or_default(),
+                    text: String::new(),
+                    visible_text: String::new(),
+                });
+            },
+            ("a", true) => {
+                if let Some(anchor) = self.anchor.take() {
+                    let token = self.anchor_token(anchor);
+                    self.push(token);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    fn anchor_token(&self, anchor: Anchor) -> Token {
+        let classes = anchor.class.split_whitespace().collect::<Vec<&str>>();
+        let text = anchor.text.trim();
+
+        if classes.contains(&"hashtag") || (classes.contains(&"mention") && text.starts_with('#')) {
+            let name = text.trim_start_matches('#');
+            let name = self.renderer.tags
+                .iter()
+                .find(|tag| tag.name().eq_ignore_ascii_case(name))
+                .map(|tag| tag.name())
+                .unwrap_or(name);
+
+            return Token::Hashtag {
+                name: name.to_owned(),
+                url: anchor.href,
+            };
+        }
+
+        if classes.contains(&"mention") || (text.starts_with('@') && !text.contains(' ')) {
+            let username = text.trim_start_matches('@');
+            let mention = self.renderer.mentions
+                .iter()
+                .find(|mention| mention.url().as_str() == anchor.href)
+                .or_else(|| self.renderer.mentions.iter().find(|mention| mention.acct() == username))
+                .or_else(|| self.renderer.mentions.iter().find(|mention| mention.username() == username));
+
+            let acct = match mention {
+                Some(mention) => full_acct(mention.acct(), mention.url().host_str()),
+                None => full_acct(username, url::Url::parse(&anchor.href).ok().as_ref().and_then(|u| u.host_str())),
+            };
+
+            return Token::Mention {
+                acct,
+                url: anchor.href,
+            };
+        }
+
+        let text = if anchor.visible_text.trim().is_empty() {
+            anchor.text
+        } else {
+            anchor.visible_text
+        };
+
+        Token::Link {
+            url: anchor.href,
+            text: text.trim().to_owned(),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let emojis = self.renderer.emojis;
+        let mut rest = text;
+
+        // Split custom emojis out of the text.
+        while let Some(start) = rest.find(':') {
+            let found = rest[start + 1..].find(':').and_then(|len| {
+                let shortcode = &rest[start + 1..start + 1 + len];
+                emojis.iter()
+                    .find(|emoji| emoji.shortcode() == shortcode)
+                    .map(|emoji| (len, emoji))
+            });
+
+            match found {
+                Some((len, emoji)) => {
+                    self.push(Token::Text(rest[..start].to_owned()));
+                    self.push(Token::Emoji {
+                        shortcode: emoji.shortcode().to_owned(),
+                        url: emoji.url().to_string(),
+                    });
+                    rest = &rest[start + len + 2..];
+                },
+                None => {
+                    self.push(Token::Text(rest[..start + 1].to_owned()));
+                    rest = &rest[start + 1..];
+                },
+            };
+        }
+        self.push(Token::Text(rest.to_owned()));
+    }
+
+    fn push(&mut self, token: Token) {
+        match (self.tokens.last_mut(), token) {
+            (_, Token::Text(text)) if text.is_empty() => (),
+            (Some(Token::Text(last)), Token::Text(text)) => last.push_str(&text),
+            // Breaks at the beginning are not significant.
+            (None, Token::ParagraphBreak) | (None, Token::LineBreak) => (),
+            (Some(Token::ParagraphBreak), Token::ParagraphBreak) => (),
+            (Some(Token::LineBreak), Token::ParagraphBreak) => {
+                self.tokens.pop();
+                self.tokens.push(Token::ParagraphBreak);
+            },
+            (Some(Token::ParagraphBreak), Token::LineBreak) => (),
+            (_, token) => self.tokens.push(token),
+        }
+    }
+
+    fn finish(mut self) -> Vec<Token> {
+        if let Some(anchor) = self.anchor.take() {
+            let token = self.anchor_token(anchor);
+            self.push(token);
+        }
+        while matches!(self.tokens.last(), Some(Token::ParagraphBreak) | Some(Token::LineBreak)) {
+            self.tokens.pop();
+        }
+        self.tokens
+    }
+}
+
+// Local accounts have only the username as acct, so complete it with the domain.
+fn full_acct(acct: &str, domain: Option<&str>) -> String {
+    match domain {
+        Some(domain) if !acct.contains('@') => format!("{}@{}", acct, domain),
+        _ => acct.to_owned(),
+    }
+}
+
+enum Piece<'h> {
+    Text(&'h str),
+    Tag(&'h str),
+}
+
+// Splits HTML into texts and tags.
+struct Pieces<'h> {
+    rest: &'h str,
+}
+
+impl<'h> Pieces<'h> {
+    fn new(html: &'h str) -> Self {
+        Pieces {
+            rest: html,
+        }
+    }
+}
+
+impl<'h> Iterator for Pieces<'h> {
+    type Item = Piece<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        if let Some(tag) = self.rest.strip_prefix('<') {
+            if let Some(end) = tag.find('>') {
+                self.rest = &tag[end + 1..];
+                return Some(Piece::Tag(&tag[..end]));
+            }
+        }
+
+        // The first char is a part of the text even if it is an unclosed `<`.
+        let start = self.rest.chars().next().map_or(0, char::len_utf8);
+        let end = self.rest[start..].find('<').map(|i| i + start).unwrap_or(self.rest.len());
+        let text = &self.rest[..end];
+        self.rest = &self.rest[end..];
+        Some(Piece::Text(text))
+    }
+}
+
+struct HtmlTag {
+    name: String,
+    closing: bool,
+    attrs: Vec<(String, String)>,
+}
+
+impl HtmlTag {
+    fn parse(tag: &str) -> Self {
+        let tag = tag.trim().trim_end_matches('/');
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+
+        let mut attrs = Vec::new();
+        let mut rest = tag[name_end..].trim_start();
+        while !rest.is_empty() {
+            let key_end = rest.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(rest.len());
+            let key = rest[..key_end].to_ascii_lowercase();
+            rest = rest[key_end..].trim_start();
+
+            let value = match rest.strip_prefix('=') {
+                Some(value) => {
+                    let value = value.trim_start();
+                    let (value, next) = match value.chars().next() {
+                        Some(quote) if quote == '"' || quote == '\'' => {
+                            let end = value[1..].find(quote).map(|i| i + 1).unwrap_or(value.len());
+                            (&value[1..end], value.get(end + 1..).unwrap_or(""))
+                        },
+                        _ => {
+                            let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                            (&value[..end], &value[end..])
+                        },
+                    };
+                    rest = next.trim_start();
+                    decode_entities(value)
+                },
+                None => String::new(),
+            };
+
+            if !key.is_empty() {
+                attrs.push((key, value));
+            }
+        }
+
+        HtmlTag {
+            name,
+            closing,
+            attrs,
+        }
+    }
+
+    fn attr(&self, key: &str) -> Option<String> {
+        self.attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.to_owned())
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .map(|classes| classes.split_whitespace().any(|c| c == class))
+            .unwrap_or(false)
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..].find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (end, c)));
+
+        match entity {
+            Some((end, c)) => {
+                decoded.push(c);
+                rest = &rest[end + 2..];
+            },
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            },
+        };
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let code = match code.strip_prefix('x').or_else(|| code.strip_prefix('X')) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse::<u32>().ok()?,
+            };
+            std::char::from_u32(code)
+        },
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = concat!(
+        r#"<p>Hello <span class="h-card"><a href="https://example.com/@alice" class="u-url mention">@<span>alice</span></a></span> "#,
+        r#"and <span class="h-card"><a href="https://remote.example/@bob" class="u-url mention">@<span>bob</span></a></span>, "#,
+        r#"check <a href="https://example.com/tags/rust" class="mention hashtag" rel="tag">#<span>RUST</span></a> :blobcat:<br />"#,
+        r#"see <a href="https://www.example.org/very/long/path" rel="nofollow noopener noreferrer" target="_blank">"#,
+        r#"<span class="invisible">https://www.</span><span class="ellipsis">example.org/very/lo</span><span class="invisible">ng/path</span></a></p>"#,
+        "\n",
+        r#"<p>2 &lt; 3 &amp;&amp; 4 &gt; 1 &#39;ok&#x27; *</p>"#,
+    );
+
+    fn renderer_entities() -> (Vec<Mention>, Vec<Tag>, Vec<Emoji>) {
+        let mentions = serde_json::from_str(r#"[
+            { "id": "1", "username": "alice", "url": "https://example.com/@alice", "acct": "alice" },
+            { "id": "2", "username": "bob", "url": "https://remote.example/@bob", "acct": "bob@remote.example" }
+        ]"#).unwrap();
+        let tags = serde_json::from_str(r#"[
+            { "name": "rust", "url": "https://example.com/tags/rust" }
+        ]"#).unwrap();
+        let emojis = serde_json::from_str(r#"[
+            {
+                "shortcode": "blobcat",
+                "url": "https://example.com/emojis/blobcat.png",
+                "static_url": "https://example.com/emojis/blobcat.png",
+                "visible_in_picker": true
+            }
+        ]"#).unwrap();
+
+        (mentions, tags, emojis)
+    }
+
+    #[test]
+    fn test_tokens() {
+        let (mentions, tags, emojis) = renderer_entities();
+        let renderer = Renderer::new().mentions(&mentions).tags(&tags).emojis(&emojis);
+
+        let tokens = renderer.tokens(CONTENT);
+        assert_eq!(tokens[0], Token::Text("Hello ".to_owned()));
+        assert_eq!(tokens[1], Token::Mention {
+            acct: "alice@example.com".to_owned(),
+            url: "https://example.com/@alice".to_owned(),
+        });
+        assert_eq!(tokens[3], Token::Mention {
+            acct: "bob@remote.example".to_owned(),
+            url: "https://remote.example/@bob".to_owned(),
+        });
+        assert_eq!(tokens[5], Token::Hashtag {
+            name: "rust".to_owned(),
+            url: "https://example.com/tags/rust".to_owned(),
+        });
+        assert_eq!(tokens[7], Token::Emoji {
+            shortcode: "blobcat".to_owned(),
+            url: "https://example.com/emojis/blobcat.png".to_owned(),
+        });
+        assert_eq!(tokens[8], Token::LineBreak);
+        assert!(tokens.contains(&Token::ParagraphBreak));
+    }
+
+    #[test]
+    fn test_plain_text() {
+        let (mentions, tags, emojis) = renderer_entities();
+        let renderer = Renderer::new().mentions(&mentions).tags(&tags).emojis(&emojis);
+
+        assert_eq!(
+            renderer.plain_text(CONTENT),
+            "Hello @alice@example.com and @bob@remote.example, check #rust :blobcat:\n\
+             see https://www.example.org/very/long/path\n\n\
+             2 < 3 && 4 > 1 'ok' *"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let (mentions, tags, emojis) = renderer_entities();
+        let renderer = Renderer::new().mentions(&mentions).tags(&tags).emojis(&emojis);
+
+        assert_eq!(
+            renderer.markdown(CONTENT),
+            "Hello [@alice@example.com](https://example.com/@alice) and [@bob@remote.example](https://remote.example/@bob), \
+             check [#rust](https://example.com/tags/rust) :blobcat:  \n\
+             see <https://www.example.org/very/long/path>\n\n\
+             2 \\< 3 && 4 \\> 1 'ok' \\*"
+        );
+    }
+
+    #[test]
+    fn test_without_entities() {
+        let renderer = Renderer::new();
+
+        assert_eq!(
+            renderer.plain_text(r#"<p>:unknown: <a href="https://example.com/@carol" class="u-url mention">@<span>carol</span></a> <a href="https://example.com/about">about us</a></p>"#),
+            ":unknown: @carol@example.com https://example.com/about"
+        );
+        assert_eq!(
+            renderer.markdown(r#"<p><a href="https://example.com/about">about us</a></p>"#),
+            "[about us](https://example.com/about)"
+        );
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let renderer = Renderer::new();
+
+        assert_eq!(renderer.plain_text("<p>こんにちは</p>"), "こんにちは");
+        assert_eq!(renderer.plain_text("<p>été</p>"), "été");
+        assert_eq!(renderer.plain_text("<p>hi</p><p>😀</p>"), "hi\n\n😀");
+        assert_eq!(renderer.plain_text("<p>一行目<br />二行目</p>"), "一行目\n二行目");
+        assert_eq!(renderer.markdown("<p>été<br>été</p>"), "été  \nété");
+        assert_eq!(renderer.tokens("<p>€ &lt; 5</p>"), vec![Token::Text("€ < 5".to_owned())]);
+    }
+}
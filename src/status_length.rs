@@ -0,0 +1,158 @@
+//! This module provides a character counter that follows the rules of Mastodon's `StatusLengthValidator`.
+//!
+//! - Every URL is counted as 23 characters regardless of its length.
+//! - Mentions of remote accounts are counted by `@username` without the domain.
+//! - Characters are counted as extended grapheme clusters, so an emoji sequence such as 👨‍👩‍👧 is a character.
+//!
+//! ```
+//! use mastors::status_length;
+//!
+//! let text = "@alice@example.social see https://example.com/a/very/long/path/to/some/article";
+//! assert_eq!(status_length::count(text), 6 + 5 + 23);
+//! assert_eq!(status_length::remaining(500, text, ""), 500 - 34);
+//! ```
+use lazy_static::lazy_static;
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The number of characters that every URL is counted as.
+pub const URL_PLACEHOLDER_LENGTH: usize = 23;
+
+lazy_static! {
+    // Simplified `valid_url` of twitter-text, only URLs with the protocol are counted as URLs on Mastodon.
+    static ref URL_RE: Regex = Regex::new(
+        r"(?i)https?://(?:(?:[\p{L}\p{N}](?:[\p{L}\p{N}_-]*[\p{L}\p{N}])?\.)+[\p{L}\p{N}-]{2,}|localhost)(?::\d+)?(?:[/?#][^\s<>]*)?"
+    ).unwrap();
+
+    // `Account::MENTION_RE` without the lookbehind, that is checked separately.
+    static ref MENTION_RE: Regex = Regex::new(
+        r"(?i)@([a-z0-9_]+(?:[a-z0-9_.-]+[a-z0-9_]+)?)(?:@[\w.-]+\w+)?"
+    ).unwrap();
+}
+
+/// Count characters of `text` in the same way as Mastodon.
+pub fn count(text: &str) -> usize {
+    countable_text(text).graphemes(true).count()
+}
+
+/// Count characters of the status that has `status` and `spoiler_text` in the same way as Mastodon.
+pub fn count_status(status: &str, spoiler_text: &str) -> usize {
+    count(status) + count(spoiler_text)
+}
+
+/// Get the number of remaining characters of the status, a negative number means the status is too long.
+///
+/// `max_characters` is usually [`Connection::status_max_characters()`](../struct.Connection.html#method.status_max_characters).
+pub fn remaining(max_characters: usize, status: &str, spoiler_text: &str) -> isize {
+    max_characters as isize - count_status(status, spoiler_text) as isize
+}
+
+// Replace URLs with the placeholder and remote mentions with the local part.
+fn countable_text(text: &str) -> String {
+    let mut entities: Vec<(usize, usize, String)> = Vec::new();
+
+    for m in URL_RE.find_iter(text) {
+        if !is_url_boundary(text, m.start()) {
+            continue;
+        }
+        let end = m.start() + trim_url_end(m.as_str());
+        entities.push((m.start(), end, "x".repeat(URL_PLACEHOLDER_LENGTH)));
+    }
+
+    for caps in MENTION_RE.captures_iter(text) {
+        let m = caps.get(0).unwrap();
+        if !is_mention_boundary(text, m.start()) {
+            continue;
+        }
+        // Overlapping entities are removed, URLs are preferred like twitter-text.
+        if entities.iter().any(|(start, end, _)| m.start() < *end && *start < m.end()) {
+            continue;
+        }
+        entities.push((m.start(), m.end(), format!("@{}", &caps[1])));
+    }
+    entities.sort_by_key(|(start, _, _)| *start);
+
+    let mut countable = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, end, replacement) in entities {
+        countable.push_str(&text[last..start]);
+        countable.push_str(&replacement);
+        last = end;
+    }
+    countable.push_str(&text[last..]);
+    countable
+}
+
+// URLs must not follow a word character or some symbols.
+fn is_url_boundary(text: &str, start: usize) -> bool {
+    match text[..start].chars().next_back() {
+        Some(c) => !(c.is_alphanumeric() || matches!(c, '@' | '#' | '$' | '/' | '＠' | '＃')),
+        None => true,
+    }
+}
+
+// Same as the lookbehind `(?<![=/[:word:]])` of `Account::MENTION_RE`.
+fn is_mention_boundary(text: &str, start: usize) -> bool {
+    match text[..start].chars().next_back() {
+        Some(c) => !(c.is_alphanumeric() || c == '_' || c == '=' || c == '/'),
+        None => true,
+    }
+}
+
+// Trailing punctuations are not a part of the URL, but closing parentheses are if balanced.
+fn trim_url_end(url: &str) -> usize {
+    let mut end = url.len();
+
+    while let Some(c) = url[..end].chars().next_back() {
+        let trim = match c {
+            '.' | ',' | ':' | ';' | '!' | '?' | '\'' | '"' | '*' | '…' => true,
+            ')' => url[..end].matches('(').count() < url[..end].matches(')').count(),
+            ']' => url[..end].matches('[').count() < url[..end].matches(']').count(),
+            _ => false,
+        };
+        if !trim {
+            break;
+        }
+        end -= c.len_utf8();
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_urls() {
+        assert_eq!(count("https://example.com"), 23);
+        assert_eq!(count("see https://example.com/a/very/long/path/to/some/article?q=1#top."), 4 + 23 + 1);
+        assert_eq!(count("(https://ja.wikipedia.org/wiki/Rust_(programming_language))"), 1 + 23 + 1);
+        assert_eq!(count("https://localhost:3000/about"), 23);
+        // URLs without the protocol are counted as is.
+        assert_eq!(count("example.com"), 11);
+        assert_eq!(count("foohttps://example.com"), 22);
+    }
+
+    #[test]
+    fn test_count_mentions() {
+        assert_eq!(count("@alice"), 6);
+        assert_eq!(count("@alice@example.social hello"), 6 + 6);
+        assert_eq!(count("mail@alice@example.social"), 25);
+        assert_eq!(count("https://example.com/@alice@example.social"), 23);
+    }
+
+    #[test]
+    fn test_count_graphemes() {
+        assert_eq!(count("👨‍👩‍👧"), 1);
+        assert_eq!(count("🇯🇵"), 1);
+        assert_eq!(count("がぎ"), 2);
+        assert_eq!(count("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn test_remaining() {
+        assert_eq!(count_status("@alice@example.social", "cw"), 8);
+        assert_eq!(remaining(10, "@alice@example.social", "cw"), 2);
+        assert_eq!(remaining(5, "https://example.com", ""), -18);
+    }
+}
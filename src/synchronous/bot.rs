@@ -0,0 +1,530 @@
+//! This module provides a bot that reacts to commands in mentions.
+//!
+//! The bot consumes notifications of `StreamType::User`, and parses the plain text of mentions as `command args...`
+//! after leading mentions, then routes it to the handler registered for the command name.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use std::time::Duration;
+//! use mastors::prelude::*;
+//! use mastors::bot::Bot;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let mut bot = Bot::new(&conn)
+//!     .cooldown(Duration::from_secs(10))
+//!     .state_file("bot_state.txt");
+//!
+//! bot.add_command("ping", |ctx| {
+//!     ctx.reply("pong")?;
+//!     Ok(())
+//! });
+//! bot.add_command("echo", |ctx| {
+//!     ctx.reply(ctx.command().rest())?;
+//!     Ok(())
+//! });
+//!
+//! bot.run()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::result::Result as StdResult;
+use std::time::{ Duration, Instant };
+use log::{ debug, error, info, trace };
+use crate::{
+    Connection,
+    Method,
+    Result,
+    api::v1::{
+        accounts,
+        notifications,
+        streaming,
+    },
+    entities::{
+        Account,
+        AccountId,
+        Notification,
+        NotificationId,
+        Status,
+    },
+    helpers::{
+        NOTIFICATIONS_PAGE_LIMIT,
+        PostedDraft,
+        StatusDraft,
+    },
+    render::Renderer,
+    streaming::{
+        EventType,
+        StreamType,
+    },
+};
+
+/// The result type of command handlers.
+pub type HandlerResult = StdResult<(), Box<dyn StdError>>;
+
+type Handler<'a> = Box<dyn FnMut(&Context) -> HandlerResult + 'a>;
+
+/// Represents a command parsed from the plain text of a mention.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Command {
+    name: String,
+    args: Vec<String>,
+    rest: String,
+}
+
+impl Command {
+    /// Parse a command from plain `text`.
+    ///
+    /// Leading mentions are skipped, the next word is the command name in lowercase and the following words are arguments.
+    /// Returns `None` if `text` has no words other than mentions.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut rest = text.trim_start();
+        loop {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (word, remaining) = rest.split_at(end);
+            if word.is_empty() {
+                return None;
+            }
+
+            if word.starts_with('@') {
+                rest = remaining.trim_start();
+                continue;
+            }
+
+            let rest = remaining.trim();
+            return Some(Command {
+                name: word.to_lowercase(),
+                args: rest.split_whitespace().map(str::to_owned).collect(),
+                rest: rest.to_owned(),
+            });
+        }
+    }
+
+    /// Get the command name in lowercase.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the whitespace-separated arguments.
+    pub fn args(&self) -> &Vec<String> {
+        &self.args
+    }
+
+    /// Get the text after the command name as it is, except for surrounding whitespace.
+    pub fn rest(&self) -> &str {
+        &self.rest
+    }
+}
+
+/// Represents a mention that is routed to a command handler.
+pub struct Context<'a> {
+    conn: &'a Connection,
+    me: &'a Account,
+    notification: &'a Notification,
+    status: &'a Status,
+    command: &'a Command,
+}
+
+impl<'a> Context<'a> {
+    /// Get the connection of the bot.
+    pub fn conn(&self) -> &Connection {
+        self.conn
+    }
+
+    /// Get the account of the bot.
+    pub fn me(&self) -> &Account {
+        self.me
+    }
+
+    /// Get the notification of the mention.
+    pub fn notification(&self) -> &Notification {
+        self.notification
+    }
+
+    /// Get the status that mentioned the bot.
+    pub fn status(&self) -> &Status {
+        self.status
+    }
+
+    /// Get the account that mentioned the bot.
+    pub fn account(&self) -> &Account {
+        self.notification.account()
+    }
+
+    /// Get the parsed command.
+    pub fn command(&self) -> &Command {
+        self.command
+    }
+
+    /// Get a draft of the reply to the status that mentioned the bot.
+    ///
+    /// See [`reply_draft()`](./fn.reply_draft.html).
+    pub fn reply_draft(&self, text: impl AsRef<str>) -> StatusDraft {
+        reply_draft(self.status, self.me.id(), text)
+    }
+
+    /// Reply to the status that mentioned the bot with `text`.
+    pub fn reply(&self, text: impl AsRef<str>) -> Result<Status> {
+        match self.reply_draft(text).post(self.conn)? {
+            PostedDraft::Status(status) => Ok(*status),
+            PostedDraft::Scheduled(_) => unreachable!("a reply draft does not have the datetime to publish"),
+        }
+    }
+}
+
+/// Create a draft of the reply to `status` with `text` as the account of `me`.
+///
+/// The reply has the same visibility and CW as `status`, and mentions the author and the accounts mentioned in `status` except for `me`.
+pub fn reply_draft(status: &Status, me: &AccountId, text: impl AsRef<str>) -> StatusDraft {
+    let mut accts: Vec<&str> = Vec::new();
+    if status.account().id() != me {
+        accts.push(status.account().acct());
+    }
+    for mention in status.mentions() {
+        if mention.id() != me && mention.id() != status.account().id() && !accts.contains(&mention.acct()) {
+            accts.push(mention.acct());
+        }
+    }
+
+    let mut content = accts.iter()
+        .map(|acct| format!("@{} ", acct))
+        .collect::<String>();
+    content.push_str(text.as_ref());
+
+    let mut draft = StatusDraft::new(content);
+    draft.set_visibility(status.visibility())
+        .set_in_reply_to_id(status.id());
+    if !status.spoiler_text().is_empty() {
+        draft.set_spoiler_text(status.spoiler_text());
+    }
+    draft
+}
+
+/// A bot that routes commands in mentions to registered handlers.
+///
+/// Mentions from the bot itself are always ignored, and mentions from other bots are ignored unless [`allow_bots()`](#method.allow_bots) is set.
+/// Errors returned from handlers are logged with `log::error!` and do not stop the bot.
+pub struct Bot<'a> {
+    conn: &'a Connection,
+    handlers: HashMap<String, Handler<'a>>,
+    fallback: Option<Handler<'a>>,
+    cooldown: Cooldown,
+    allow_bots: bool,
+    state_file: Option<PathBuf>,
+    last_notification_id: Option<NotificationId>,
+    me: Option<Account>,
+}
+
+impl<'a> Bot<'a> {
+    /// Create a new `Bot` that has no command handlers.
+    pub fn new(conn: &'a Connection) -> Self {
+        Bot {
+            conn,
+            handlers: HashMap::new(),
+            fallback: None,
+            cooldown: Cooldown::new(Duration::from_secs(0)),
+            allow_bots: false,
+            state_file: None,
+            last_notification_id: None,
+            me: None,
+        }
+    }
+
+    /// Set the period in which commands from the same account are ignored after a handled command. Defaults to zero.
+    pub fn cooldown(mut self, period: Duration) -> Self {
+        self.cooldown = Cooldown::new(period);
+        self
+    }
+
+    /// Set to handle mentions from accounts that are marked as bots.
+    pub fn allow_bots(mut self) -> Self {
+        self.allow_bots = true;
+        self
+    }
+
+    /// Set the file to persist the ID of the last handled notification.
+    ///
+    /// If the file exists when the bot starts, mentions received after that notification are handled first.
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
+    /// Register `handler` for the command `name`, case-insensitive.
+    pub fn add_command<F>(&mut self, name: impl AsRef<str>, handler: F) -> &mut Self
+    where
+        F: FnMut(&Context) -> HandlerResult + 'a,
+    {
+        self.handlers.insert(name.as_ref().to_lowercase(), Box::new(handler));
+        self
+    }
+
+    /// Register `handler` for commands that have no handler.
+    pub fn set_fallback<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: FnMut(&Context) -> HandlerResult + 'a,
+    {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    /// Get the ID of the last handled notification.
+    pub fn last_notification_id(&self) -> Option<&NotificationId> {
+        self.last_notification_id.as_ref()
+    }
+
+    /// Handle mentions missed since the last handled notification, then handle mentions of the user stream until it ends.
+    pub fn run(&mut self) -> Result<()> {
+        self.load_state()?;
+        self.catch_up()?;
+
+        info!("Start streaming notifications as @{}", self.me()?.acct());
+        for event in streaming::get(self.conn, StreamType::User).send()? {
+            if let EventType::Notification(notification) = event? {
+                self.handle(&notification)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle mentions received after the last handled notification, in order of receipt.
+    pub fn catch_up(&mut self) -> Result<()> {
+        while let Some(min_id) = self.last_notification_id.clone() {
+            let mut page = notifications::get(self.conn)
+                .min_id(min_id)
+                .limit(NOTIFICATIONS_PAGE_LIMIT)
+                .send()?;
+            if page.is_empty() {
+                break;
+            }
+
+            debug!("Catch up {} notifications", page.len());
+            page.sort_by(|a, b| a.id().cmp(b.id()));
+            for notification in page.iter() {
+                self.handle(notification)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a notification, and get whether a command handler has been called.
+    ///
+    /// Notifications that are not newer than the last handled notification are skipped.
+    pub fn handle(&mut self, notification: &Notification) -> Result<bool> {
+        if let Some(last) = self.last_notification_id.as_ref() {
+            if notification.id() <= last {
+                trace!("Skip handled notification {}", notification.id());
+                return Ok(false);
+            }
+        }
+
+        let handled = self.route(notification)?;
+        self.last_notification_id = Some(notification.id().clone());
+        self.save_state()?;
+        Ok(handled)
+    }
+
+    fn route(&mut self, notification: &Notification) -> Result<bool> {
+        let status = match notification.status() {
+            Some(status) if notification.is_mention() => status,
+            _ => return Ok(false),
+        };
+
+        self.me()?;
+        let me = self.me.as_ref().expect("the account of the bot is loaded");
+        if is_ignored(notification.account(), me.id(), self.allow_bots) {
+            debug!("Ignore mention from @{}", notification.account().acct());
+            return Ok(false);
+        }
+
+        let command = match parse_mention(status) {
+            Some(command) => command,
+            None => return Ok(false),
+        };
+
+        let handler = match self.handlers.get_mut(command.name()) {
+            Some(handler) => handler,
+            None => match self.fallback.as_mut() {
+                Some(fallback) => fallback,
+                None => {
+                    debug!("No handler for command {}", command.name());
+                    return Ok(false);
+                },
+            },
+        };
+
+        if !self.cooldown.try_acquire(notification.account().id(), Instant::now()) {
+            debug!("Ignore command {} from @{} in cooldown", command.name(), notification.account().acct());
+            return Ok(false);
+        }
+
+        let ctx = Context {
+            conn: self.conn,
+            me,
+            notification,
+            status,
+            command: &command,
+        };
+        debug!("Handle command {} from @{}", command.name(), notification.account().acct());
+        if let Err(e) = handler(&ctx) {
+            error!("Command {} from @{} failed: {}", command.name(), notification.account().acct(), e);
+        }
+        Ok(true)
+    }
+
+    fn me(&mut self) -> Result<&Account> {
+        if self.me.is_none() {
+            self.me = Some(accounts::verify_credentials::get(self.conn).send()?);
+        }
+        Ok(self.me.as_ref().expect("the account of the bot is loaded"))
+    }
+
+    fn load_state(&mut self) -> Result<()> {
+        if let Some(path) = self.state_file.as_ref() {
+            match fs::read_to_string(path) {
+                Ok(id) if !id.trim().is_empty() => {
+                    debug!("Resume from notification {}", id.trim());
+                    self.last_notification_id = Some(NotificationId::new(id.trim()));
+                },
+                Ok(_) => (),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn save_state(&self) -> Result<()> {
+        if let (Some(path), Some(id)) = (self.state_file.as_ref(), self.last_notification_id.as_ref()) {
+            fs::write(path, id.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+// Mentions are rendered with the entities of the status, so mentions of remote accounts are skipped as `@username@domain`.
+fn parse_mention(status: &Status) -> Option<Command> {
+    let text = Renderer::from_status(status).plain_text(status.content().unwrap_or_default());
+    Command::parse(&text)
+}
+
+// Mentions from the bot itself are never handled to avoid loops between replies.
+fn is_ignored(account: &Account, me: &AccountId, allow_bots: bool) -> bool {
+    account.id() == me || (account.bot() && !allow_bots)
+}
+
+// Remembers when each account has run a command last.
+#[derive(Debug, Clone)]
+struct Cooldown {
+    period: Duration,
+    last: HashMap<AccountId, Instant>,
+}
+
+impl Cooldown {
+    fn new(period: Duration) -> Self {
+        Cooldown {
+            period,
+            last: HashMap::new(),
+        }
+    }
+
+    fn try_acquire(&mut self, id: &AccountId, now: Instant) -> bool {
+        if let Some(last) = self.last.get(id) {
+            if now.duration_since(*last) < self.period {
+                return false;
+            }
+        }
+        self.last.insert(id.clone(), now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Visibility;
+
+    fn status() -> Status {
+        serde_json::from_str(include_str!("../../test-resources/entities/status.json")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let command = Command::parse("@bot@example.com @alice Echo  hello\nworld ").unwrap();
+        assert_eq!(command.name(), "echo");
+        assert_eq!(command.args(), &vec!["hello", "world"]);
+        assert_eq!(command.rest(), "hello\nworld");
+
+        let command = Command::parse("ping").unwrap();
+        assert_eq!(command.name(), "ping");
+        assert!(command.args().is_empty());
+        assert_eq!(command.rest(), "");
+
+        assert!(Command::parse("@bot @alice").is_none());
+        assert!(Command::parse("  ").is_none());
+    }
+
+    #[test]
+    fn test_parse_mention() {
+        let mut status = serde_json::from_str::<serde_json::Value>(include_str!("../../test-resources/entities/status.json")).unwrap();
+        status["content"] = concat!(
+            r#"<p><span class="h-card"><a href="https://example.com/@bob" class="u-url mention">@<span>bob</span></a></span></p>"#,
+            r#"<p>こんにちは 世界<br />été</p>"#,
+        ).into();
+        let status: Status = serde_json::from_value(status).unwrap();
+
+        let command = parse_mention(&status).unwrap();
+        assert_eq!(command.name(), "こんにちは");
+        assert_eq!(command.args(), &vec!["世界", "été"]);
+        assert_eq!(command.rest(), "世界\nété");
+    }
+
+    #[test]
+    fn test_reply_draft() {
+        let status = status();
+
+        let draft = reply_draft(&status, &"1".into(), "hi");
+        assert_eq!(draft.text(), Some("@alice hi"));
+        assert_eq!(draft.visibility(), Some(Visibility::Unlisted));
+        assert_eq!(draft.spoiler_text(), Some("greeting"));
+        assert_eq!(draft.in_reply_to_id(), Some(status.id()));
+
+        let draft = reply_draft(&status, &"2".into(), "hi");
+        assert_eq!(draft.text(), Some("@alice @bob@example.com hi"));
+    }
+
+    #[test]
+    fn test_is_ignored() {
+        let mut account = serde_json::from_str::<serde_json::Value>(include_str!("../../test-resources/entities/account.json")).unwrap();
+        let id = AccountId::new(account["id"].as_str().unwrap());
+
+        let human: Account = serde_json::from_value(account.clone()).unwrap();
+        assert!(is_ignored(&human, &id, false));
+        assert!(!is_ignored(&human, &"0".into(), false));
+
+        account["bot"] = true.into();
+        let bot: Account = serde_json::from_value(account).unwrap();
+        assert!(is_ignored(&bot, &"0".into(), false));
+        assert!(!is_ignored(&bot, &"0".into(), true));
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(10));
+        let now = Instant::now();
+        let alice = AccountId::new("1");
+        let bob = AccountId::new("2");
+
+        assert!(cooldown.try_acquire(&alice, now));
+        assert!(!cooldown.try_acquire(&alice, now + Duration::from_secs(9)));
+        assert!(cooldown.try_acquire(&bob, now + Duration::from_secs(9)));
+        assert!(cooldown.try_acquire(&alice, now + Duration::from_secs(10)));
+        assert!(!cooldown.try_acquire(&alice, now + Duration::from_secs(19)));
+    }
+}
@@ -0,0 +1,926 @@
+//! This module provides helpers to export and import account data in the CSV formats of Mastodon's settings/export.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::{ AccountExport, AccountImport, ExportKind };
+//!
+//! let old = Connection::from_file(".env.old")?;
+//! let new = Connection::from_file(".env.new")?;
+//!
+//! let following = AccountExport::new(&old).export(ExportKind::Following)?;
+//!
+//! let report = AccountImport::new(&new)
+//!     .dry_run()
+//!     .on_progress(|progress| println!("{}/{:?} {:?}", progress.done(), progress.total(), progress.item()))
+//!     .import(ExportKind::Following, &following)?;
+//!
+//! for failure in report.failures() {
+//!     println!("line {}: {}: {}", failure.line(), failure.item(), failure.error());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::HashMap,
+    fmt,
+    fs,
+    io::Write,
+    path::{ Path, PathBuf },
+    thread,
+    time::Duration,
+};
+use chrono::{ DateTime, Utc };
+use log::{ debug, trace, warn };
+use crate::{
+    Connection,
+    Error,
+    Method,
+    MethodWithRespHeader,
+    Result,
+    api::{
+        v1::{
+            accounts,
+            blocks,
+            bookmarks,
+            domain_blocks,
+            lists,
+            mutes,
+            statuses,
+        },
+        v2::search,
+    },
+    entities::{
+        Account,
+        AccountId,
+        ListId,
+        PageNavigation,
+    },
+};
+use super::{
+    ACCOUNTS_PAGE_LIMIT,
+    ListSync,
+    STATUSES_PAGE_LIMIT,
+    resolve_account,
+};
+
+/// The default time to wait before retrying a request that is rate limited, if the server does not tell when the rate limit is reset.
+pub const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// The default number of retries of a request that is rate limited.
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+const DOMAINS_PAGE_LIMIT: usize = 200;
+const RELATIONSHIPS_CHUNK: usize = 40;
+
+/// The kind of account data, each kind is exported to a CSV file.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum ExportKind {
+    /// Accounts you are following, `following_accounts.csv`.
+    Following,
+
+    /// Lists and member accounts of them, `lists.csv`.
+    Lists,
+
+    /// Accounts you are blocking, `blocked_accounts.csv`.
+    Blocks,
+
+    /// Accounts you are muting, `muted_accounts.csv`.
+    Mutes,
+
+    /// Domains you are blocking, `blocked_domains.csv`.
+    DomainBlocks,
+
+    /// URIs of statuses you have bookmarked, `bookmarks.csv`.
+    Bookmarks,
+}
+
+impl ExportKind {
+    /// All kinds of account data.
+    pub const ALL: [ExportKind; 6] = [
+        ExportKind::Following,
+        ExportKind::Lists,
+        ExportKind::Blocks,
+        ExportKind::Mutes,
+        ExportKind::DomainBlocks,
+        ExportKind::Bookmarks,
+    ];
+
+    /// Get the file name that Mastodon uses for this kind.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ExportKind::Following => "following_accounts.csv",
+            ExportKind::Lists => "lists.csv",
+            ExportKind::Blocks => "blocked_accounts.csv",
+            ExportKind::Mutes => "muted_accounts.csv",
+            ExportKind::DomainBlocks => "blocked_domains.csv",
+            ExportKind::Bookmarks => "bookmarks.csv",
+        }
+    }
+
+    /// Get the kind from the file name that Mastodon uses.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        ExportKind::ALL.iter().copied().find(|kind| kind.file_name() == file_name)
+    }
+
+    // Only following accounts and muted accounts have the header row.
+    fn header(self) -> Option<&'static [&'static str]> {
+        match self {
+            ExportKind::Following => Some(&["Account address", "Show boosts", "Notify on new posts", "Languages"]),
+            ExportKind::Mutes => Some(&["Account address", "Hide notifications"]),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExportKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportKind::Following => write!(f, "following"),
+            ExportKind::Lists => write!(f, "lists"),
+            ExportKind::Blocks => write!(f, "blocks"),
+            ExportKind::Mutes => write!(f, "mutes"),
+            ExportKind::DomainBlocks => write!(f, "domain_blocks"),
+            ExportKind::Bookmarks => write!(f, "bookmarks"),
+        }
+    }
+}
+
+/// Represents the progress of exporting or importing.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Progress {
+    kind: ExportKind,
+    done: usize,
+    total: Option<usize>,
+    item: Option<String>,
+}
+
+impl Progress {
+    /// Get the kind of account data.
+    pub fn kind(&self) -> ExportKind {
+        self.kind
+    }
+
+    /// Get the number of processed rows.
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    /// Get the number of all rows, `None` while exporting because it is unknown until the last page.
+    pub fn total(&self) -> Option<usize> {
+        self.total
+    }
+
+    /// Get the processed item such as an acct handle, `None` while exporting.
+    pub fn item(&self) -> Option<&str> {
+        self.item.as_deref()
+    }
+}
+
+type ProgressCallback<'a> = Box<dyn FnMut(&Progress) + 'a>;
+
+// Retries requests that are rate limited with HTTP 429, waiting until the reset time given by the server.
+#[derive(Debug, Clone, Copy)]
+struct RateLimiter {
+    wait: Duration,
+    max_retries: usize,
+}
+
+impl RateLimiter {
+    fn call<T, F>(&self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Result<T>,
+    {
+        let mut retries = 0;
+        loop {
+            match request() {
+                Err(e) if retries < self.max_retries => {
+                    let wait = match self.wait_for(&e, Utc::now()) {
+                        Some(wait) => wait,
+                        None => return Err(e),
+                    };
+                    retries += 1;
+                    warn!("Rate limited, retry {}/{} after {:?}", retries, self.max_retries, wait);
+                    thread::sleep(wait);
+                },
+                result => return result,
+            }
+        }
+    }
+
+    // Get the time to wait until the rate limit is reset if `e` is rate limited, or the fixed wait if the server did not tell the reset time.
+    fn wait_for(&self, e: &Error, now: DateTime<Utc>) -> Option<Duration> {
+        match e {
+            Error::HttpTooManyRequestsError(_, Some(reset)) => Some((*reset - now).to_std().unwrap_or_default()),
+            Error::HttpTooManyRequestsError(_, None) => Some(self.wait),
+            _ => None,
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter {
+            wait: DEFAULT_RATE_LIMIT_WAIT,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+fn file_kind(path: &Path) -> Result<ExportKind> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(ExportKind::from_file_name)
+        .ok_or_else(|| Error::UnknownExportFileError(path.display().to_string()))
+}
+
+/// A helper to export account data of the authenticated user in the CSV formats of Mastodon.
+pub struct AccountExport<'a> {
+    conn: &'a Connection,
+    limiter: RateLimiter,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a> AccountExport<'a> {
+    /// Create a new `AccountExport` for the authenticated user of `conn`.
+    pub fn new(conn: &'a Connection) -> Self {
+        AccountExport {
+            conn,
+            limiter: RateLimiter::default(),
+            on_progress: None,
+        }
+    }
+
+    /// Set the time to wait before retrying a request that is rate limited, used only when the server does not tell when the rate limit is reset.
+    ///
+    /// Defaults to [`DEFAULT_RATE_LIMIT_WAIT`](./constant.DEFAULT_RATE_LIMIT_WAIT.html).
+    pub fn rate_limit_wait(mut self, wait: Duration) -> Self {
+        self.limiter.wait = wait;
+        self
+    }
+
+    /// Set the number of retries of a request that is rate limited.
+    ///
+    /// Defaults to [`DEFAULT_MAX_RETRIES`](./constant.DEFAULT_MAX_RETRIES.html).
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.limiter.max_retries = max_retries;
+        self
+    }
+
+    /// Set a callback that is called every time a page is fetched.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: FnMut(&Progress) + 'a,
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Export `kind` of account data as CSV text.
+    pub fn export(&mut self, kind: ExportKind) -> Result<String> {
+        let mut csv = String::new();
+        if let Some(header) = kind.header() {
+            csv.push_str(&csv_row(header));
+        }
+
+        for row in self.rows(kind)? {
+            csv.push_str(&csv_row(&row));
+        }
+        Ok(csv)
+    }
+
+    /// Export `kind` of account data as CSV to `writer`.
+    pub fn export_to<W: Write>(&mut self, kind: ExportKind, mut writer: W) -> Result<()> {
+        writer.write_all(self.export(kind)?.as_bytes())?;
+        Ok(writer.flush()?)
+    }
+
+    /// Export all kinds of account data into `dir` with the file names of Mastodon, and get paths of written files.
+    pub fn export_all(&mut self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir.as_ref())?;
+
+        let mut paths = Vec::with_capacity(ExportKind::ALL.len());
+        for kind in ExportKind::ALL.iter().copied() {
+            let path = dir.as_ref().join(kind.file_name());
+            fs::write(&path, self.export(kind)?)?;
+            debug!("Exported {} to {}", kind, path.display());
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn rows(&mut self, kind: ExportKind) -> Result<Vec<Vec<String>>> {
+        let conn = self.conn;
+        let local_domain = conn.server_url().host_str().unwrap_or_default().to_owned();
+
+        let rows = match kind {
+            ExportKind::Following => {
+                let me = self.limiter.call(|| accounts::verify_credentials::get(conn).send())?;
+                let following = self.paginate(kind, |max_id| {
+                    let req = accounts::id::following::get(conn, me.id()).limit(ACCOUNTS_PAGE_LIMIT);
+                    match max_id {
+                        Some(max_id) => req.max_id(max_id).send(),
+                        None => req.send(),
+                    }
+                })?;
+
+                let relationships = self.relationships(&following)?;
+                following.iter()
+                    .map(|account| {
+                        let relationship = relationships.get(account.id());
+                        vec![
+                            full_acct(account, &local_domain),
+                            relationship.map(|r| r.showing_reblogs()).unwrap_or(true).to_string(),
+                            relationship.map(|r| r.notifying()).unwrap_or(false).to_string(),
+                            String::new(),
+                        ]
+                    })
+                    .collect()
+            },
+            ExportKind::Lists => {
+                let mut rows = Vec::new();
+                for list in self.limiter.call(|| lists::get(conn).send())? {
+                    let members = self.paginate(kind, |max_id| {
+                        let req = lists::id::accounts::get(conn, list.id()).limit(ACCOUNTS_PAGE_LIMIT);
+                        match max_id {
+                            Some(max_id) => req.max_id(max_id).send(),
+                            None => req.send(),
+                        }
+                    })?;
+                    rows.extend(members.iter().map(|account| vec![list.title().to_owned(), full_acct(account, &local_domain)]));
+                }
+                rows
+            },
+            ExportKind::Blocks => {
+                self.paginate(kind, |max_id| {
+                    let req = blocks::get(conn).limit(ACCOUNTS_PAGE_LIMIT);
+                    match max_id {
+                        Some(max_id) => req.max_id(max_id).send(),
+                        None => req.send(),
+                    }
+                })?
+                .iter()
+                .map(|account| vec![full_acct(account, &local_domain)])
+                .collect()
+            },
+            ExportKind::Mutes => {
+                let muted = self.paginate(kind, |max_id| {
+                    let req = mutes::get(conn).limit(ACCOUNTS_PAGE_LIMIT);
+                    match max_id {
+                        Some(max_id) => req.max_id(max_id).send(),
+                        None => req.send(),
+                    }
+                })?;
+
+                let relationships = self.relationships(&muted)?;
+                muted.iter()
+                    .map(|account| {
+                        let hide_notifications = relationships.get(account.id()).map(|r| r.muting_notifications()).unwrap_or(true);
+                        vec![full_acct(account, &local_domain), hide_notifications.to_string()]
+                    })
+                    .collect()
+            },
+            ExportKind::DomainBlocks => {
+                self.paginate(kind, |max_id| {
+                    let req = domain_blocks::get(conn).limit(DOMAINS_PAGE_LIMIT);
+                    match max_id {
+                        Some(max_id) => req.max_id(max_id).send(),
+                        None => req.send(),
+                    }
+                })?
+                .into_iter()
+                .map(|domain| vec![domain])
+                .collect()
+            },
+            ExportKind::Bookmarks => {
+                self.paginate(kind, |max_id| {
+                    let req = bookmarks::get(conn).limit(STATUSES_PAGE_LIMIT);
+                    match max_id {
+                        Some(max_id) => req.max_id(max_id).send(),
+                        None => req.send(),
+                    }
+                })?
+                .iter()
+                .map(|status| vec![status.uri().to_string()])
+                .collect()
+            },
+        };
+
+        Ok(rows)
+    }
+
+    fn paginate<T, F>(&mut self, kind: ExportKind, mut fetch: F) -> Result<Vec<T>>
+    where
+        F: FnMut(Option<String>) -> Result<(PageNavigation, Vec<T>)>,
+    {
+        let mut items = Vec::new();
+        let mut max_id: Option<String> = None;
+
+        loop {
+            let (page_navigation, page) = self.limiter.call(|| fetch(max_id.clone()))?;
+            trace!("Got {} items of {}", page.len(), kind);
+            if page.is_empty() {
+                break;
+            }
+            items.extend(page);
+
+            if let Some(on_progress) = self.on_progress.as_mut() {
+                on_progress(&Progress { kind, done: items.len(), total: None, item: None });
+            }
+
+            match page_navigation.max_id() {
+                Some(next) => max_id = Some(next.to_owned()),
+                None => break,
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn relationships(&self, accounts: &[Account]) -> Result<HashMap<AccountId, crate::entities::Relationship>> {
+        let mut relationships = HashMap::with_capacity(accounts.len());
+        for chunk in accounts.chunks(RELATIONSHIPS_CHUNK) {
+            let ids = chunk.iter().map(|account| account.id()).collect::<Vec<&AccountId>>();
+            let got = self.limiter.call(|| accounts::relationships::get(self.conn, &ids).send())?;
+            relationships.extend(got.into_iter().map(|r| (r.id().clone(), r)));
+        }
+        Ok(relationships)
+    }
+}
+
+impl<'a> fmt::Debug for AccountExport<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AccountExport")
+            .field("conn", &self.conn)
+            .field("limiter", &self.limiter)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+/// Represents a row that could not be imported.
+#[derive(Debug)]
+pub struct ImportFailure {
+    line: usize,
+    item: String,
+    error: Error,
+}
+
+impl ImportFailure {
+    /// Get the line number of the row in the CSV, starting with `1`.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the item of the row such as an acct handle.
+    pub fn item(&self) -> &str {
+        &self.item
+    }
+
+    /// Get the reason why the row could not be imported.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+/// Represents the result of importing a CSV.
+#[derive(Debug)]
+pub struct ImportReport {
+    kind: ExportKind,
+    dry_run: bool,
+    total: usize,
+    applied: usize,
+    failures: Vec<ImportFailure>,
+}
+
+impl ImportReport {
+    /// Get the kind of the imported account data.
+    pub fn kind(&self) -> ExportKind {
+        self.kind
+    }
+
+    /// Get whether nothing was changed because of the dry run.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Get the number of rows in the CSV without the header.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the number of applied rows, or rows to be applied if this is a dry run.
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    /// Get rows that could not be imported.
+    pub fn failures(&self) -> &[ImportFailure] {
+        &self.failures
+    }
+}
+
+/// A helper to import CSVs in the formats of Mastodon to the authenticated user.
+///
+/// Acct handles are resolved with [`resolve_account()`](./fn.resolve_account.html) and status URIs are resolved with `/api/v2/search`,
+/// then they are applied with the follow, list, block, mute, domain block and bookmark endpoints.
+/// A row that cannot be resolved or applied is reported as a failure and does not stop importing.
+pub struct AccountImport<'a> {
+    conn: &'a Connection,
+    dry_run: bool,
+    delay: Duration,
+    limiter: RateLimiter,
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a> AccountImport<'a> {
+    /// Create a new `AccountImport` for the authenticated user of `conn`.
+    pub fn new(conn: &'a Connection) -> Self {
+        AccountImport {
+            conn,
+            dry_run: false,
+            delay: Duration::from_secs(0),
+            limiter: RateLimiter::default(),
+            on_progress: None,
+        }
+    }
+
+    /// Set to resolve every row without changing anything.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Set the time to wait between rows to stay under the rate limit.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the time to wait before retrying a request that is rate limited, used only when the server does not tell when the rate limit is reset.
+    ///
+    /// Defaults to [`DEFAULT_RATE_LIMIT_WAIT`](./constant.DEFAULT_RATE_LIMIT_WAIT.html).
+    pub fn rate_limit_wait(mut self, wait: Duration) -> Self {
+        self.limiter.wait = wait;
+        self
+    }
+
+    /// Set the number of retries of a request that is rate limited.
+    ///
+    /// Defaults to [`DEFAULT_MAX_RETRIES`](./constant.DEFAULT_MAX_RETRIES.html).
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.limiter.max_retries = max_retries;
+        self
+    }
+
+    /// Set a callback that is called every time a row is processed.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: FnMut(&Progress) + 'a,
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Import the file exported by Mastodon, the kind is detected by the file name.
+    pub fn import_file(&mut self, path: impl AsRef<Path>) -> Result<ImportReport> {
+        let path = path.as_ref();
+        let kind = file_kind(path)?;
+
+        self.import(kind, &fs::read_to_string(path)?)
+    }
+
+    /// Import `csv` as `kind` of account data.
+    pub fn import(&mut self, kind: ExportKind, csv: &str) -> Result<ImportReport> {
+        let rows = parse_rows(kind, csv);
+        let total = rows.len();
+        debug!("Import {} rows of {}{}", total, kind, if self.dry_run { " (dry run)" } else { "" });
+
+        let mut lists = ListCache::default();
+        let mut applied = 0;
+        let mut failures = Vec::new();
+
+        for (done, (line, row)) in rows.into_iter().enumerate() {
+            let item = row.first().cloned().unwrap_or_default();
+            match self.apply(kind, line, &row, &mut lists) {
+                Ok(()) => applied += 1,
+                Err(error) => {
+                    warn!("Failed to import line {} of {}: {}", line, kind, error);
+                    failures.push(ImportFailure { line, item: item.clone(), error });
+                },
+            }
+
+            if let Some(on_progress) = self.on_progress.as_mut() {
+                on_progress(&Progress { kind, done: done + 1, total: Some(total), item: Some(item) });
+            }
+
+            if !self.dry_run && !self.delay.is_zero() && done + 1 < total {
+                thread::sleep(self.delay);
+            }
+        }
+
+        Ok(ImportReport {
+            kind,
+            dry_run: self.dry_run,
+            total,
+            applied,
+            failures,
+        })
+    }
+
+    fn apply(&self, kind: ExportKind, line: usize, row: &[String], lists: &mut ListCache) -> Result<()> {
+        let conn = self.conn;
+        let field = |index: usize| row.get(index).map(|field| field.trim()).unwrap_or("");
+        let first = field(0);
+        if first.is_empty() {
+            return Err(Error::InvalidCsvRowError(line, row.join(",")));
+        }
+
+        match kind {
+            ExportKind::Following => {
+                let account = self.resolve_acct(first)?;
+                if self.dry_run {
+                    return Ok(());
+                }
+
+                self.limiter.call(|| {
+                    let req = accounts::id::follow::post(conn, account.id());
+                    let req = if parse_bool(field(1), true) { req.reblogs() } else { req.hide_reblogs() };
+                    let req = if parse_bool(field(2), false) { req.notify() } else { req };
+                    req.send()
+                })?;
+            },
+            ExportKind::Lists => {
+                let acct = field(1);
+                if acct.is_empty() {
+                    return Err(Error::InvalidCsvRowError(line, row.join(",")));
+                }
+                let account = self.resolve_acct(acct)?;
+                let list_id = match lists.find(self, first)? {
+                    Some(list_id) => list_id,
+                    None if self.dry_run => return Ok(()),
+                    None => lists.create(self, first)?,
+                };
+                if lists.has_member(self, &list_id, account.id())? || self.dry_run {
+                    return Ok(());
+                }
+
+                // Only accounts you are following can be added to lists.
+                let relationship = self.limiter.call(|| accounts::relationships::get(conn, [account.id()]).send())?;
+                if !relationship.first().map(|r| r.following() || r.requested()).unwrap_or(false) {
+                    self.limiter.call(|| accounts::id::follow::post(conn, account.id()).send())?;
+                }
+                self.limiter.call(|| lists::id::accounts::post(conn, &list_id, [account.id()]).send())?;
+                lists.add_member(&list_id, account.id());
+            },
+            ExportKind::Blocks => {
+                let account = self.resolve_acct(first)?;
+                if !self.dry_run {
+                    self.limiter.call(|| accounts::id::block::post(conn, account.id()).send())?;
+                }
+            },
+            ExportKind::Mutes => {
+                let account = self.resolve_acct(first)?;
+                if !self.dry_run {
+                    self.limiter.call(|| {
+                        let req = accounts::id::mute::post(conn, account.id());
+                        let req = if parse_bool(field(1), true) { req } else { req.without_notifications() };
+                        req.send()
+                    })?;
+                }
+            },
+            ExportKind::DomainBlocks => {
+                if !self.dry_run {
+                    self.limiter.call(|| domain_blocks::post(conn, first).send())?;
+                }
+            },
+            ExportKind::Bookmarks => {
+                let status = self.limiter.call(|| search::get(conn, first).statuses().resolve().limit(1).send())?
+                    .statuses()
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| Error::StatusNotFoundError(first.to_owned()))?;
+                if !self.dry_run {
+                    self.limiter.call(|| statuses::id::bookmark::post(conn, status.id()).send())?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    fn resolve_acct(&self, acct: &str) -> Result<Account> {
+        self.limiter.call(|| resolve_account(self.conn, acct))
+    }
+}
+
+impl<'a> fmt::Debug for AccountImport<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AccountImport")
+            .field("conn", &self.conn)
+            .field("dry_run", &self.dry_run)
+            .field("delay", &self.delay)
+            .field("limiter", &self.limiter)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+// Lists and their members fetched lazily while importing `lists.csv`.
+#[derive(Debug, Default)]
+struct ListCache {
+    ids: Option<HashMap<String, ListId>>,
+    members: HashMap<ListId, Vec<AccountId>>,
+}
+
+impl ListCache {
+    fn find(&mut self, import: &AccountImport, title: &str) -> Result<Option<ListId>> {
+        if self.ids.is_none() {
+            let lists = import.limiter.call(|| lists::get(import.conn).send())?;
+            self.ids = Some(lists.iter().map(|list| (list.title().to_owned(), list.id().clone())).collect());
+        }
+        Ok(self.ids.as_ref().and_then(|ids| ids.get(title).cloned()))
+    }
+
+    fn create(&mut self, import: &AccountImport, title: &str) -> Result<ListId> {
+        let list = import.limiter.call(|| lists::post(import.conn, title).send())?;
+        debug!("Created list '{}' ({})", title, list.id());
+
+        self.ids.get_or_insert_with(HashMap::new).insert(title.to_owned(), list.id().clone());
+        self.members.insert(list.id().clone(), Vec::new());
+        Ok(list.id().clone())
+    }
+
+    fn has_member(&mut self, import: &AccountImport, list_id: &ListId, account_id: &AccountId) -> Result<bool> {
+        if !self.members.contains_key(list_id) {
+            let members = import.limiter.call(|| ListSync::new(import.conn, list_id).current_members())?;
+            self.members.insert(list_id.clone(), members);
+        }
+        Ok(self.members[list_id].iter().any(|id| id == account_id))
+    }
+
+    fn add_member(&mut self, list_id: &ListId, account_id: &AccountId) {
+        self.members.entry(list_id.clone()).or_default().push(account_id.clone());
+    }
+}
+
+// Local accounts are exported with the domain to be imported on other servers.
+fn full_acct(account: &Account, local_domain: &str) -> String {
+    if account.is_local() && !local_domain.is_empty() {
+        format!("{}@{}", account.acct(), local_domain)
+    } else {
+        account.acct().to_owned()
+    }
+}
+
+fn parse_bool(value: &str, default: bool) -> bool {
+    match value.to_ascii_lowercase().as_str() {
+        "true" => true,
+        "false" => false,
+        _ => default,
+    }
+}
+
+// Parse CSV into rows with line numbers, the header row and blank rows are skipped.
+fn parse_rows(kind: ExportKind, csv: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = parse_csv(csv);
+
+    let has_header = match (kind.header(), rows.first()) {
+        (Some(header), Some((_, first))) => first.first().map(|field| field.trim() == header[0]).unwrap_or(false),
+        _ => false,
+    };
+    if has_header {
+        rows.remove(0);
+    }
+
+    rows.retain(|(_, row)| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+// A minimal RFC 4180 parser that supports quoted fields with commas, quotes and line breaks.
+fn parse_csv(csv: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => (),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            },
+            ('\n', true) => {
+                field.push(c);
+                line += 1;
+            },
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+    rows
+}
+
+fn csv_row<T: AsRef<str>>(fields: &[T]) -> String {
+    let mut row = fields.iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        for kind in ExportKind::ALL.iter().copied() {
+            assert_eq!(ExportKind::from_file_name(kind.file_name()), Some(kind));
+        }
+        assert_eq!(ExportKind::from_file_name("follows.csv"), None);
+
+        assert_eq!(file_kind(Path::new("export/lists.csv")).unwrap(), ExportKind::Lists);
+        match file_kind(Path::new("export/follows.csv")) {
+            Err(Error::UnknownExportFileError(name)) => assert_eq!(name, "export/follows.csv"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let limiter = RateLimiter::default();
+        let url = crate::Url::parse("https://example.com/api/v1/follows").unwrap();
+        let now = Utc::now();
+
+        let e = Error::HttpTooManyRequestsError(url.clone(), Some(now + chrono::Duration::seconds(30)));
+        assert_eq!(limiter.wait_for(&e, now), Some(Duration::from_secs(30)));
+
+        // The reset time has already passed.
+        let e = Error::HttpTooManyRequestsError(url.clone(), Some(now - chrono::Duration::seconds(30)));
+        assert_eq!(limiter.wait_for(&e, now), Some(Duration::from_secs(0)));
+
+        let e = Error::HttpTooManyRequestsError(url.clone(), None);
+        assert_eq!(limiter.wait_for(&e, now), Some(DEFAULT_RATE_LIMIT_WAIT));
+
+        let e = Error::HttpServerStatusError(url, 503);
+        assert_eq!(limiter.wait_for(&e, now), None);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let rows = [
+            vec!["Friends, family".to_owned(), "alice@example.social".to_owned()],
+            vec!["\"Quoted\"\nlist".to_owned(), "bob@example.com".to_owned()],
+        ];
+        let csv = rows.iter().map(|row| csv_row(row)).collect::<String>();
+        assert_eq!(csv, "\"Friends, family\",alice@example.social\n\"\"\"Quoted\"\"\nlist\",bob@example.com\n");
+
+        let parsed = parse_csv(&csv);
+        assert_eq!(parsed, vec![(1, rows[0].clone()), (2, rows[1].clone())]);
+    }
+
+    #[test]
+    fn test_parse_rows() {
+        let csv = "Account address,Show boosts,Notify on new posts,Languages\r\nalice@example.social,true,false,\r\n\r\nbob@example.com,false,true,ja\r\n";
+        let rows = parse_rows(ExportKind::Following, csv);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], (2, vec!["alice@example.social".to_owned(), "true".to_owned(), "false".to_owned(), "".to_owned()]));
+        assert_eq!(rows[1].0, 4);
+
+        // Blocked accounts have no header.
+        let rows = parse_rows(ExportKind::Blocks, "alice@example.social\nbob@example.com");
+        assert_eq!(rows, vec![(1, vec!["alice@example.social".to_owned()]), (2, vec!["bob@example.com".to_owned()])]);
+
+        assert!(parse_bool("TRUE", false));
+        assert!(parse_bool("", true));
+        assert!(!parse_bool("false", true));
+    }
+}
@@ -0,0 +1,234 @@
+//! This module provides a helper to keep member accounts of a list in sync with the desired accounts.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::ListSync;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let applied = ListSync::new(&conn, "1")
+//!     .account_ids(["2", "3"])
+//!     .accts(["alice@example.social"])
+//!     .sync()?;
+//!
+//! println!("added: {:?}, removed: {:?}", applied.to_add(), applied.to_remove());
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeSet;
+use log::{ debug, trace };
+use crate::{
+    Connection,
+    Method,
+    MethodWithRespHeader,
+    Result,
+    api::v1::lists,
+    entities::{
+        Account,
+        AccountId,
+        ListId,
+    },
+};
+use super::{
+    ACCOUNTS_PAGE_LIMIT,
+    resolve_account,
+};
+
+/// The default number of accounts to add or remove with a single request.
+pub const DEFAULT_BATCH_SIZE: usize = 40;
+
+/// A helper to make member accounts of the list specified by ID to be the desired accounts.
+///
+/// Desired accounts can be specified by account IDs and/or acct handles such as `alice@example.social`.
+/// Acct handles are resolved to account IDs with [`resolve_account()`](./fn.resolve_account.html).
+#[derive(Debug, Clone)]
+pub struct ListSync<'a> {
+    conn: &'a Connection,
+    list_id: ListId,
+    account_ids: Vec<AccountId>,
+    accts: Vec<String>,
+    batch_size: usize,
+}
+
+impl<'a> ListSync<'a> {
+    /// Create a new `ListSync` for the list specified by `list_id`.
+    ///
+    /// If no desired account is set, `sync()` removes all member accounts from the list.
+    pub fn new(conn: &'a Connection, list_id: impl Into<ListId>) -> Self {
+        ListSync {
+            conn,
+            list_id: list_id.into(),
+            account_ids: Vec::new(),
+            accts: Vec::new(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Add account IDs to the desired accounts.
+    pub fn account_ids<T, U>(mut self, account_ids: T) -> Self
+    where
+        T: AsRef<[U]>,
+        U: AsRef<str>,
+    {
+        self.account_ids.extend(trimmed(account_ids).map(AccountId::from));
+        self
+    }
+
+    /// Add acct handles to the desired accounts, the leading `@` sign is allowed.
+    pub fn accts<T, U>(mut self, accts: T) -> Self
+    where
+        T: AsRef<[U]>,
+        U: AsRef<str>,
+    {
+        self.accts.extend(
+            trimmed(accts).map(|acct| acct.trim_start_matches('@').to_owned())
+        );
+        self
+    }
+
+    /// Set the number of accounts to add or remove with a single request.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_SIZE`](./constant.DEFAULT_BATCH_SIZE.html), `0` is treated as `1`.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Get IDs of all current member accounts of the list by following every page.
+    pub fn current_members(&self) -> Result<Vec<AccountId>> {
+        let mut members: Vec<AccountId> = Vec::new();
+        let mut max_id: Option<String> = None;
+
+        loop {
+            let req = lists::id::accounts::get(self.conn, &self.list_id).limit(ACCOUNTS_PAGE_LIMIT);
+            let req = match max_id {
+                Some(max_id) => req.max_id(max_id),
+                None => req,
+            };
+
+            let (page_navigation, accounts) = req.send()?;
+            trace!("Got {} member accounts of list {}", accounts.len(), self.list_id);
+
+            if accounts.is_empty() {
+                break;
+            }
+            members.extend(accounts.iter().map(|a| a.id().clone()));
+
+            match page_navigation.max_id() {
+                Some(next) => max_id = Some(next.to_owned()),
+                None => break,
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Get the plan to make the member accounts of the list to be the desired accounts without changing anything.
+    pub fn plan(&self) -> Result<ListSyncPlan> {
+        let mut desired = self.account_ids.clone();
+        for acct in self.accts.iter() {
+            desired.push(self.resolve_acct(acct)?.id().clone());
+        }
+
+        Ok(ListSyncPlan::new(self.current_members()?, desired))
+    }
+
+    /// Apply the plan to the list and return the applied plan.
+    ///
+    /// Accounts are removed first and then added in batches of [`batch_size()`](#method.batch_size).
+    pub fn sync(&self) -> Result<ListSyncPlan> {
+        let plan = self.plan()?;
+        debug!(
+            "Sync list {}: add {} accounts, remove {} accounts",
+            self.list_id,
+            plan.to_add.len(),
+            plan.to_remove.len()
+        );
+
+        for chunk in plan.to_remove.chunks(self.batch_size) {
+            lists::id::accounts::delete(self.conn, &self.list_id, chunk).send()?;
+        }
+
+        for chunk in plan.to_add.chunks(self.batch_size) {
+            lists::id::accounts::post(self.conn, &self.list_id, chunk).send()?;
+        }
+
+        Ok(plan)
+    }
+
+    fn resolve_acct(&self, acct: &str) -> Result<Account> {
+        resolve_account(self.conn, acct)
+    }
+}
+
+/// Represents account IDs to be added to and removed from the list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListSyncPlan {
+    to_add: Vec<AccountId>,
+    to_remove: Vec<AccountId>,
+}
+
+impl ListSyncPlan {
+    /// Create a plan that makes `current` account IDs to be `desired` account IDs.
+    pub fn new<T, U, V, W>(current: T, desired: V) -> Self
+    where
+        T: IntoIterator<Item = U>,
+        U: Into<AccountId>,
+        V: IntoIterator<Item = W>,
+        W: Into<AccountId>,
+    {
+        let current = current.into_iter().map(|id| id.into()).collect::<BTreeSet<AccountId>>();
+        let desired = desired.into_iter().map(|id| id.into()).collect::<BTreeSet<AccountId>>();
+
+        ListSyncPlan {
+            to_add: desired.difference(&current).cloned().collect(),
+            to_remove: current.difference(&desired).cloned().collect(),
+        }
+    }
+
+    /// Get account IDs to be added to the list.
+    pub fn to_add(&self) -> &Vec<AccountId> {
+        &self.to_add
+    }
+
+    /// Get account IDs to be removed from the list.
+    pub fn to_remove(&self) -> &Vec<AccountId> {
+        &self.to_remove
+    }
+
+    /// Get whether the list is already in sync.
+    pub fn is_empty(&self) -> bool {
+        self.to_add.is_empty() && self.to_remove.is_empty()
+    }
+}
+
+fn trimmed<T, U>(values: T) -> impl Iterator<Item = String>
+where
+    T: AsRef<[U]>,
+    U: AsRef<str>,
+{
+    values.as_ref()
+        .iter()
+        .map(|v| v.as_ref().trim().to_owned())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<String>>()
+        .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let plan = ListSyncPlan::new(vec!["1", "2", "3"], vec!["3", "4", "4", "5"]);
+        assert_eq!(plan.to_add(), &vec!["4".to_owned(), "5".to_owned()]);
+        assert_eq!(plan.to_remove(), &vec!["1".to_owned(), "2".to_owned()]);
+        assert!(!plan.is_empty());
+
+        let plan = ListSyncPlan::new(vec!["1", "2"], vec!["2", "1"]);
+        assert!(plan.is_empty());
+    }
+}
@@ -0,0 +1,482 @@
+//! This module provides a helper to download media attachments to a directory.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::MediaDownloader;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let status = mastors::api::v1::statuses::id::get(&conn, "1").send()?;
+//!
+//! let mut downloader = MediaDownloader::new(&conn, "media");
+//! for media in downloader.download_status(&status)? {
+//!     println!("{} -> {}", media.attachment_id(), media.path().display());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::HashMap,
+    fs::{ self, File, OpenOptions },
+    io::{ self, Read },
+    path::{ Path, PathBuf },
+};
+use log::{ debug, trace, warn };
+use reqwest::{ StatusCode, header };
+use crate::{
+    Connection,
+    Error,
+    Result,
+    Url,
+    entities::{
+        Attachment,
+        MediaId,
+        Status,
+        attachment::AttachmentType,
+    },
+    utils,
+};
+
+const PART_EXTENSION: &str = "part";
+
+/// Which file of the attachment to download.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum MediaVariant {
+    /// The original full-size file from `url`, or `remote_url` as a fallback.
+    Original,
+
+    /// The scaled-down preview from `preview_url`.
+    Preview,
+}
+
+/// How the downloaded file was obtained.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DownloadState {
+    /// The whole file was downloaded.
+    Downloaded,
+
+    /// A partial download was resumed with a Range request.
+    Resumed,
+
+    /// The file already existed or was downloaded for another status, nothing was downloaded.
+    Existing,
+}
+
+/// Represents a media file saved in the directory.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct DownloadedMedia {
+    attachment_id: MediaId,
+    path: PathBuf,
+    source: Option<Url>,
+    size: u64,
+    state: DownloadState,
+}
+
+impl DownloadedMedia {
+    /// Get the ID of the attachment.
+    pub fn attachment_id(&self) -> &MediaId {
+        &self.attachment_id
+    }
+
+    /// Get the path of the saved file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the URL that the file was downloaded from, `None` if nothing was downloaded.
+    pub fn source(&self) -> Option<&Url> {
+        self.source.as_ref()
+    }
+
+    /// Get the size of the saved file in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get how the file was obtained.
+    pub fn state(&self) -> DownloadState {
+        self.state
+    }
+}
+
+/// A helper to download media attachments into a directory with the HTTP client of the connection.
+///
+/// Files are named by the attachment ID and the extension of the URL, so the same attachment is saved only once
+/// even if it appears in multiple statuses or runs.
+/// Downloads are written to `<name>.part` first and resumed with a Range request if interrupted.
+///
+/// The size of the file is verified against `Content-Length` or `Content-Range`.
+/// In addition, the width and height of PNG, GIF and JPEG images from `url` are verified against `AttachmentMeta`,
+/// because Mastodon does not provide the size of files in bytes.
+#[derive(Debug, Clone)]
+pub struct MediaDownloader<'a> {
+    conn: &'a Connection,
+    dir: PathBuf,
+    variant: MediaVariant,
+    remote_fallback: bool,
+    verify_dimensions: bool,
+    downloaded: HashMap<MediaId, DownloadedMedia>,
+}
+
+impl<'a> MediaDownloader<'a> {
+    /// Create a new `MediaDownloader` that saves files into `dir`.
+    ///
+    /// The directory is created if it does not exist.
+    pub fn new(conn: &'a Connection, dir: impl AsRef<Path>) -> Self {
+        MediaDownloader {
+            conn,
+            dir: dir.as_ref().to_owned(),
+            variant: MediaVariant::Original,
+            remote_fallback: true,
+            verify_dimensions: true,
+            downloaded: HashMap::new(),
+        }
+    }
+
+    /// Download previews instead of original files.
+    pub fn preview(mut self) -> Self {
+        self.variant = MediaVariant::Preview;
+        self
+    }
+
+    /// Set which file of attachments to download, defaults to `MediaVariant::Original`.
+    pub fn variant(mut self, variant: MediaVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Do not fall back to `remote_url` when downloading from `url` failed.
+    pub fn without_remote_fallback(mut self) -> Self {
+        self.remote_fallback = false;
+        self
+    }
+
+    /// Do not verify the width and height of images against `AttachmentMeta`.
+    pub fn without_dimension_check(mut self) -> Self {
+        self.verify_dimensions = false;
+        self
+    }
+
+    /// Get the path that `attachment` is saved to.
+    pub fn path(&self, attachment: &Attachment) -> PathBuf {
+        self.dir.join(file_name(attachment, self.variant))
+    }
+
+    /// Download all media attachments of `status`, or of the reblogged status if `status` is a reblog.
+    pub fn download_status(&mut self, status: &Status) -> Result<Vec<DownloadedMedia>> {
+        let status = status.reblog().unwrap_or(status);
+        status.media_attachments()
+            .iter()
+            .map(|attachment| self.download(attachment))
+            .collect()
+    }
+
+    /// Download `attachment` unless it has already been saved.
+    pub fn download(&mut self, attachment: &Attachment) -> Result<DownloadedMedia> {
+        if let Some(downloaded) = self.downloaded.get(attachment.id()) {
+            trace!("Attachment {} has already been downloaded", attachment.id());
+            return Ok(DownloadedMedia {
+                state: DownloadState::Existing,
+                source: None,
+                ..downloaded.clone()
+            });
+        }
+
+        let path = self.path(attachment);
+        let downloaded = if path.is_file() {
+            trace!("{} already exists", path.display());
+            DownloadedMedia {
+                attachment_id: attachment.id().clone(),
+                size: fs::metadata(&path)?.len(),
+                path,
+                source: None,
+                state: DownloadState::Existing,
+            }
+        } else {
+            fs::create_dir_all(&self.dir)?;
+            self.download_with_fallback(attachment, path)?
+        };
+
+        self.downloaded.insert(attachment.id().clone(), downloaded.clone());
+        Ok(downloaded)
+    }
+
+    fn download_with_fallback(&self, attachment: &Attachment, path: PathBuf) -> Result<DownloadedMedia> {
+        let (url, remote_url) = match self.variant {
+            MediaVariant::Original => (attachment.url(), attachment.remote_url().filter(|_| self.remote_fallback)),
+            MediaVariant::Preview => (attachment.preview_url(), None),
+        };
+
+        let error = match self.download_from(attachment, url, &path, true) {
+            Ok(downloaded) => return Ok(downloaded),
+            Err(e) => e,
+        };
+
+        match remote_url.filter(|remote_url| *remote_url != url) {
+            Some(remote_url) if !matches!(error, Error::FileIoError(_)) => {
+                warn!("Failed to download {}, fall back to {}: {}", url, remote_url, error);
+                // The partial file came from another server, so it cannot be resumed.
+                remove_if_exists(&part_path(&path))?;
+                self.download_from(attachment, remote_url, &path, false)
+            },
+            _ => Err(error),
+        }
+    }
+
+    fn download_from(&self, attachment: &Attachment, url: &Url, path: &Path, local: bool) -> Result<DownloadedMedia> {
+        let part = part_path(path);
+        let mut offset = match fs::metadata(&part) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+
+        let mut res = self.request(url, offset)?;
+        if offset > 0 && res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            debug!("Cannot resume {}, download it again", url);
+            offset = 0;
+            res = self.request(url, offset)?;
+        }
+        let mut res = utils::check_response(res)?;
+
+        let resumed = offset > 0 && res.status() == StatusCode::PARTIAL_CONTENT;
+        let expected = if resumed {
+            header_str(&res, header::CONTENT_RANGE).and_then(content_range_total)
+        } else {
+            offset = 0;
+            header_str(&res, header::CONTENT_LENGTH).and_then(|length| length.parse().ok())
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&part)?;
+        let written = io::copy(&mut res, &mut file)?;
+        file.sync_all()?;
+        drop(file);
+
+        let size = offset + written;
+        trace!("Wrote {} bytes to {}", written, part.display());
+        if let Some(expected) = expected {
+            if size != expected {
+                return Err(Error::IncompleteDownloadError(url.clone(), expected, size));
+            }
+        }
+
+        // Files on the remote server may be different from the processed files on this server.
+        if local && self.verify_dimensions && self.variant == MediaVariant::Original {
+            if let Err(e) = verify_dimensions(attachment, url, &part) {
+                remove_if_exists(&part)?;
+                return Err(e);
+            }
+        }
+
+        fs::rename(&part, path)?;
+        debug!("Downloaded {} to {}", url, path.display());
+
+        Ok(DownloadedMedia {
+            attachment_id: attachment.id().clone(),
+            path: path.to_owned(),
+            source: Some(url.clone()),
+            size,
+            state: if resumed { DownloadState::Resumed } else { DownloadState::Downloaded },
+        })
+    }
+
+    fn request(&self, url: &Url, offset: u64) -> Result<reqwest::blocking::Response> {
+        // Media is public, so the access token is not sent to media servers.
+        let req = self.conn.client()
+            .get(url.clone())
+            .header(header::ACCEPT_ENCODING, "identity");
+        let req = if offset > 0 {
+            req.header(header::RANGE, format!("bytes={}-", offset))
+        } else {
+            req
+        };
+
+        let req = req.build()?;
+        trace!("Send a {} request to {}", req.method(), req.url());
+        Ok(self.conn.client().execute(req)?)
+    }
+}
+
+fn file_name(attachment: &Attachment, variant: MediaVariant) -> String {
+    let (url, suffix) = match variant {
+        MediaVariant::Original => (attachment.url(), ""),
+        MediaVariant::Preview => (attachment.preview_url(), "-preview"),
+    };
+
+    let extension = extension(url)
+        .or_else(|| attachment.remote_url().and_then(extension).filter(|_| variant == MediaVariant::Original))
+        .unwrap_or_else(|| default_extension(attachment.r#type(), variant).to_owned());
+
+    // IDs are numeric strings, but they come from the server.
+    let id = attachment.id().replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    format!("{}{}.{}", id, suffix, extension)
+}
+
+fn extension(url: &Url) -> Option<String> {
+    let name = url.path_segments()?.next_back()?;
+    let (_, extension) = name.rsplit_once('.')?;
+
+    if !extension.is_empty() && extension.len() <= 5 && extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Some(extension.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+fn default_extension(attachment_type: AttachmentType, variant: MediaVariant) -> &'static str {
+    match (attachment_type, variant) {
+        (_, MediaVariant::Preview) | (AttachmentType::Image, _) => "png",
+        (AttachmentType::Gifv, _) | (AttachmentType::Video, _) => "mp4",
+        (AttachmentType::Audio, _) => "mp3",
+        (AttachmentType::Unknown, _) => "bin",
+    }
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".");
+    part.push(PART_EXTENSION);
+    PathBuf::from(part)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn header_str(res: &reqwest::blocking::Response, name: header::HeaderName) -> Option<&str> {
+    res.headers().get(name)?.to_str().ok()
+}
+
+// `bytes 100-199/200` -> 200
+fn content_range_total(content_range: &str) -> Option<u64> {
+    content_range.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+fn verify_dimensions(attachment: &Attachment, url: &Url, path: &Path) -> Result<()> {
+    if !attachment.is_image() {
+        return Ok(());
+    }
+
+    let expected = match attachment.meta().and_then(|meta| meta.original()) {
+        Some(original) => match (original.width(), original.height()) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+
+    let mut head = Vec::new();
+    File::open(path)?.take(64 * 1024).read_to_end(&mut head)?;
+    match image_dimensions(&head) {
+        Some(actual) if actual != expected => Err(Error::MediaDimensionsMismatchError(url.clone(), expected, actual)),
+        _ => Ok(()),
+    }
+}
+
+// Read the width and height from the header of PNG, GIF and JPEG images.
+fn image_dimensions(head: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |b: &[u8]| u16::from_be_bytes([b[0], b[1]]) as u32;
+    let le16 = |b: &[u8]| u16::from_le_bytes([b[0], b[1]]) as u32;
+    let be32 = |b: &[u8]| u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") && head.len() >= 24 && &head[12..16] == b"IHDR" {
+        return Some((be32(&head[16..20]), be32(&head[20..24])));
+    }
+
+    if (head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a")) && head.len() >= 10 {
+        return Some((le16(&head[6..8]), le16(&head[8..10])));
+    }
+
+    if head.starts_with(&[0xff, 0xd8]) {
+        let mut i = 2;
+        while i + 4 <= head.len() {
+            if head[i] != 0xff {
+                return None;
+            }
+            let marker = head[i + 1];
+            // Padding bytes.
+            if marker == 0xff {
+                i += 1;
+                continue;
+            }
+            let length = be16(&head[i + 2..i + 4]) as usize;
+            let is_sof = matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+            if is_sof {
+                if i + 9 > head.len() {
+                    return None;
+                }
+                return Some((be16(&head[i + 7..i + 9]), be16(&head[i + 5..i + 7])));
+            }
+            i += 2 + length;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(id: &str, r#type: &str, url: &str, remote_url: Option<&str>) -> Attachment {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": r#type,
+            "url": url,
+            "preview_url": "https://example.com/media/small/1.jpeg",
+            "remote_url": remote_url,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_file_name() {
+        let image = attachment("1", "image", "https://example.com/media/original/1.JPEG?v=2", None);
+        assert_eq!(file_name(&image, MediaVariant::Original), "1.jpeg");
+        assert_eq!(file_name(&image, MediaVariant::Preview), "1-preview.jpeg");
+
+        let video = attachment("2", "video", "https://example.com/media/2", Some("https://remote.example/2.webm"));
+        assert_eq!(file_name(&video, MediaVariant::Original), "2.webm");
+
+        let unknown = attachment("../3", "unknown", "https://example.com/media/3", None);
+        assert_eq!(file_name(&unknown, MediaVariant::Original), "___3.bin");
+
+        assert_eq!(part_path(Path::new("media/1.jpeg")), PathBuf::from("media/1.jpeg.part"));
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        assert_eq!(content_range_total("bytes 100-199/200"), Some(200));
+        assert_eq!(content_range_total("bytes 100-199/*"), None);
+        assert_eq!(content_range_total("bytes"), None);
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        png.extend(&640u32.to_be_bytes());
+        png.extend(&480u32.to_be_bytes());
+        assert_eq!(image_dimensions(&png), Some((640, 480)));
+
+        let gif = b"GIF89a\x80\x02\xe0\x01";
+        assert_eq!(image_dimensions(gif), Some((640, 480)));
+
+        // SOI, APP0 with 2 bytes of data, and SOF0.
+        let jpeg = [
+            0xff, 0xd8,
+            0xff, 0xe0, 0x00, 0x04, 0x00, 0x00,
+            0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((640, 480)));
+
+        assert_eq!(image_dimensions(b"RIFF"), None);
+    }
+}
@@ -0,0 +1,31 @@
+//! This module provides helpers that combine some API methods to accomplish common tasks.
+pub mod account_data;
+pub mod list_sync;
+pub mod media_downloader;
+pub mod read_position;
+pub mod resolve;
+pub mod scheduler;
+pub mod status_draft;
+pub mod thread;
+pub mod thread_composer;
+pub mod webfinger;
+
+pub use account_data::{ AccountExport, AccountImport, ExportKind, ImportFailure, ImportReport, Progress };
+pub use list_sync::{ ListSync, ListSyncPlan };
+pub use media_downloader::{ DownloadState, DownloadedMedia, MediaDownloader, MediaVariant };
+pub use read_position::{ MarkerId, ReadPosition };
+pub use resolve::resolve_account;
+pub use scheduler::Scheduler;
+pub use status_draft::{ DraftPoll, PostedDraft, ServerConfiguration, StatusDraft };
+pub use thread::{ Thread, ThreadNode };
+pub use thread_composer::ThreadComposer;
+pub use webfinger::{ WebFinger, WebFingerLink, webfinger };
+
+// Max number of accounts to get with a single request, twice of DEFAULT_ACCOUNTS_LIMIT of app/controllers/api/base_controller.rb.
+pub(crate) const ACCOUNTS_PAGE_LIMIT: usize = 80;
+
+// Max number of statuses to get with a single request, twice of DEFAULT_STATUSES_LIMIT of app/controllers/api/base_controller.rb.
+pub(crate) const STATUSES_PAGE_LIMIT: usize = 40;
+
+// Max number of notifications to get with a single request, twice of DEFAULT_NOTIFICATIONS_LIMIT of app/controllers/api/v1/notifications_controller.rb.
+pub(crate) const NOTIFICATIONS_PAGE_LIMIT: usize = 80;
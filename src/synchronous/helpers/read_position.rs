@@ -0,0 +1,389 @@
+//! This module provides a helper to keep the read position of the home timeline or notifications in sync with markers.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::ReadPosition;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let mut position = ReadPosition::home(&conn).load()?;
+//!
+//! loop {
+//!     let statuses = position.next_page()?;
+//!     if statuses.is_empty() {
+//!         break;
+//!     }
+//!
+//!     for status in statuses {
+//!         println!("{}", status.id());
+//!         position.advance(status.id())?;
+//!     }
+//! }
+//! position.flush()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use log::{ debug, trace };
+use crate::{
+    Connection,
+    Error,
+    Method,
+    Result,
+    api::v1::{
+        markers,
+        notifications,
+        timelines,
+    },
+    entities::{
+        Marker,
+        Markers,
+        Notification,
+        NotificationId,
+        Status,
+        StatusId,
+    },
+};
+use super::{
+    NOTIFICATIONS_PAGE_LIMIT,
+    STATUSES_PAGE_LIMIT,
+};
+
+/// The default number of advances to send a marker update with a single request.
+pub const DEFAULT_BATCH_SIZE: usize = 20;
+
+// Max number of times to resolve a version conflict of the marker before giving up.
+const MAX_CONFLICT_RETRIES: usize = 3;
+
+// Marker updates conflict with a concurrent update by another client, see app/controllers/api/v1/markers_controller.rb.
+const HTTP_CONFLICT: u16 = 409;
+
+mod private {
+    use crate::{
+        Connection,
+        api::v1::markers::{ GetMarkers, PostMarkers },
+        entities::{ Marker, Markers },
+    };
+
+    pub trait Sealed: Sized {
+        fn get_marker(conn: &Connection) -> GetMarkers<'_>;
+        fn post_marker(conn: &Connection, id: Self) -> PostMarkers<'_>;
+        fn marker(markers: &Markers) -> Option<&Marker>;
+    }
+}
+
+/// An ID of the timeline that has a marker, implemented for `StatusId` of the home timeline and `NotificationId` of notifications.
+pub trait MarkerId: private::Sealed + Clone + Ord + fmt::Display + From<String> {}
+
+impl private::Sealed for StatusId {
+    fn get_marker(conn: &Connection) -> markers::GetMarkers<'_> {
+        markers::get(conn).without_notifications()
+    }
+
+    fn post_marker(conn: &Connection, id: Self) -> markers::PostMarkers<'_> {
+        markers::post(conn).home(id)
+    }
+
+    fn marker(markers: &Markers) -> Option<&Marker> {
+        markers.home()
+    }
+}
+
+impl MarkerId for StatusId {}
+
+impl private::Sealed for NotificationId {
+    fn get_marker(conn: &Connection) -> markers::GetMarkers<'_> {
+        markers::get(conn).without_home()
+    }
+
+    fn post_marker(conn: &Connection, id: Self) -> markers::PostMarkers<'_> {
+        markers::post(conn).notifications(id)
+    }
+
+    fn marker(markers: &Markers) -> Option<&Marker> {
+        markers.notifications()
+    }
+}
+
+impl MarkerId for NotificationId {}
+
+/// A helper to resume reading the home timeline or notifications from the marker, and to advance the marker.
+///
+/// The position only moves forward, and updates of the marker are batched.
+/// Before an update is sent, the marker is reloaded so that a newer position set by another client is never moved backwards,
+/// and an update that conflicts with another client is retried after reloading the marker.
+///
+/// Pending updates are not sent automatically when the helper is dropped, so call [`flush()`](#method.flush) when finished reading.
+#[derive(Debug, Clone)]
+pub struct ReadPosition<'a, T> {
+    conn: &'a Connection,
+    batch_size: usize,
+    cursor: Cursor<T>,
+}
+
+impl<'a> ReadPosition<'a, StatusId> {
+    /// Create a new `ReadPosition` of the home timeline.
+    pub fn home(conn: &'a Connection) -> Self {
+        ReadPosition::new(conn)
+    }
+
+    /// Get the next page of statuses on the home timeline after the current position, in order from oldest to newest.
+    ///
+    /// The latest statuses are returned if there is no position yet.
+    pub fn next_page(&self) -> Result<Vec<Status>> {
+        let req = timelines::home::get(self.conn).limit(STATUSES_PAGE_LIMIT);
+        let req = match self.position() {
+            Some(position) => req.min_id(position),
+            None => req,
+        };
+
+        let mut statuses = req.send()?;
+        trace!("Got {} statuses after {:?}", statuses.len(), self.position());
+        statuses.sort_by(|a, b| a.id().cmp(b.id()));
+        Ok(statuses)
+    }
+}
+
+impl<'a> ReadPosition<'a, NotificationId> {
+    /// Create a new `ReadPosition` of notifications.
+    pub fn notifications(conn: &'a Connection) -> Self {
+        ReadPosition::new(conn)
+    }
+
+    /// Get the next page of notifications after the current position, in order from oldest to newest.
+    ///
+    /// The latest notifications are returned if there is no position yet.
+    pub fn next_page(&self) -> Result<Vec<Notification>> {
+        let req = notifications::get(self.conn).limit(NOTIFICATIONS_PAGE_LIMIT);
+        let req = match self.position() {
+            Some(position) => req.min_id(position),
+            None => req,
+        };
+
+        let mut notifications = req.send()?;
+        trace!("Got {} notifications after {:?}", notifications.len(), self.position());
+        notifications.sort_by(|a, b| a.id().cmp(b.id()));
+        Ok(notifications)
+    }
+}
+
+impl<'a, T: MarkerId> ReadPosition<'a, T> {
+    fn new(conn: &'a Connection) -> Self {
+        ReadPosition {
+            conn,
+            batch_size: DEFAULT_BATCH_SIZE,
+            cursor: Cursor::new(),
+        }
+    }
+
+    /// Set the number of advances to send a marker update with a single request.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_SIZE`](./constant.DEFAULT_BATCH_SIZE.html), and `1` sends an update on every advance.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Load the marker from the server.
+    pub fn load(mut self) -> Result<Self> {
+        self.reload()?;
+        Ok(self)
+    }
+
+    /// Get the last read ID of the marker on the server as of the last request.
+    pub fn last_read_id(&self) -> Option<&T> {
+        self.cursor.last_read_id.as_ref()
+    }
+
+    /// Get the version of the marker on the server as of the last request.
+    pub fn version(&self) -> Option<u32> {
+        self.cursor.version
+    }
+
+    /// Get the current position including advances that have not been sent yet.
+    pub fn position(&self) -> Option<&T> {
+        self.cursor.position()
+    }
+
+    /// Get whether there are advances that have not been sent yet.
+    pub fn has_pending(&self) -> bool {
+        self.cursor.pending.is_some()
+    }
+
+    /// Advance the position to `id` and get whether the position has moved.
+    ///
+    /// The position does not move if `id` is not newer than the current position.
+    /// The marker is updated once the number of advances reaches the batch size.
+    pub fn advance(&mut self, id: impl Into<T>) -> Result<bool> {
+        if !self.cursor.advance(id.into()) {
+            return Ok(false);
+        }
+
+        if self.cursor.pending_advances >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(true)
+    }
+
+    /// Send the pending advance to the server.
+    ///
+    /// If another client has already moved the marker to the same or a newer position, the pending advance is discarded.
+    pub fn flush(&mut self) -> Result<()> {
+        let mut retries = 0;
+
+        loop {
+            self.reload()?;
+            let pending = match self.cursor.pending.clone() {
+                Some(pending) => pending,
+                None => return Ok(()),
+            };
+
+            debug!("Update marker to {} from version {:?}", pending, self.cursor.version);
+            match T::post_marker(self.conn, pending).send() {
+                Ok(markers) => {
+                    self.cursor.update(T::marker(&markers));
+                    self.cursor.pending = None;
+                    self.cursor.pending_advances = 0;
+                    return Ok(());
+                },
+                Err(e) if is_conflict(&e) && retries < MAX_CONFLICT_RETRIES => {
+                    retries += 1;
+                    debug!("Marker update conflicted, retry {} of {}", retries, MAX_CONFLICT_RETRIES);
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        let markers = T::get_marker(self.conn).send()?;
+        self.cursor.update(T::marker(&markers));
+        Ok(())
+    }
+}
+
+// The position on the server and the pending advance, kept apart from the connection.
+#[derive(Debug, Clone)]
+struct Cursor<T> {
+    last_read_id: Option<T>,
+    version: Option<u32>,
+    pending: Option<T>,
+    pending_advances: usize,
+}
+
+impl<T: MarkerId> Cursor<T> {
+    fn new() -> Self {
+        Cursor {
+            last_read_id: None,
+            version: None,
+            pending: None,
+            pending_advances: 0,
+        }
+    }
+
+    fn position(&self) -> Option<&T> {
+        self.pending.as_ref().or(self.last_read_id.as_ref())
+    }
+
+    fn advance(&mut self, id: T) -> bool {
+        if let Some(position) = self.position() {
+            if id <= *position {
+                trace!("Ignore advance to {} behind {}", id, position);
+                return false;
+            }
+        }
+
+        self.pending = Some(id);
+        self.pending_advances += 1;
+        true
+    }
+
+    // Adopt the marker on the server, and drop the pending advance if the marker has already reached it.
+    fn update(&mut self, marker: Option<&Marker>) {
+        let marker = match marker {
+            Some(marker) => marker,
+            None => return,
+        };
+
+        let last_read_id = T::from(marker.last_read_id().to_owned());
+        if self.pending.as_ref().is_some_and(|pending| *pending <= last_read_id) {
+            trace!("Marker {} has reached the pending advance", last_read_id);
+            self.pending = None;
+            self.pending_advances = 0;
+        }
+
+        self.last_read_id = Some(last_read_id);
+        self.version = Some(marker.version());
+    }
+}
+
+fn is_conflict(e: &Error) -> bool {
+    match e {
+        Error::HttpClientStatusError(_, status, _) | Error::HttpUnexpectedStatusError(_, status) => *status == HTTP_CONFLICT,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Markers;
+
+    fn markers(home: &str, version: u32) -> Markers {
+        serde_json::from_str(&format!(
+            r#"{{ "home": {{ "last_read_id": "{}", "version": {}, "updated_at": "2019-11-24T19:39:39.337Z" }} }}"#,
+            home,
+            version,
+        )).unwrap()
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut cursor = Cursor::<StatusId>::new();
+        assert!(cursor.advance("100".into()));
+        assert_eq!(cursor.position(), Some(&"100".into()));
+
+        cursor.update(markers("100", 1).home());
+        assert!(cursor.pending.is_none());
+        assert!(!cursor.advance("99".into()));
+        assert!(!cursor.advance("100".into()));
+        assert!(cursor.pending.is_none());
+
+        assert!(cursor.advance("101".into()));
+        assert!(cursor.advance("1000".into()));
+        assert!(!cursor.advance("999".into()));
+        assert_eq!(cursor.position(), Some(&"1000".into()));
+        assert_eq!(cursor.last_read_id, Some("100".into()));
+        assert_eq!(cursor.pending_advances, 2);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut cursor = Cursor::<StatusId>::new();
+        cursor.update(markers("100", 1).home());
+        cursor.advance("200".into());
+
+        // Another client has moved the marker, but not as far as the pending advance.
+        cursor.update(markers("150", 2).home());
+        assert_eq!(cursor.last_read_id, Some("150".into()));
+        assert_eq!(cursor.version, Some(2));
+        assert_eq!(cursor.position(), Some(&"200".into()));
+
+        // Another client has moved the marker beyond the pending advance.
+        cursor.update(markers("300", 3).home());
+        assert!(cursor.pending.is_none());
+        assert_eq!(cursor.position(), Some(&"300".into()));
+
+        // The marker is missing from the response.
+        cursor.update(None);
+        assert_eq!(cursor.version, Some(3));
+
+        let markers: Markers = serde_json::from_str(include_str!("../../../test-resources/entities/markers.json")).unwrap();
+        let mut cursor = Cursor::<NotificationId>::new();
+        cursor.update(markers.notifications());
+        assert_eq!(cursor.last_read_id, Some("35098814".into()));
+        assert_eq!(cursor.version, Some(361));
+    }
+}
@@ -0,0 +1,131 @@
+//! This module provides a helper to resolve `user@domain` handles to accounts.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::resolve_account;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let account = resolve_account(&conn, "@alice@example.social")?;
+//! let statuses = mastors::api::v1::accounts::id::statuses::get(&conn, account.id()).send()?;
+//! # Ok(())
+//! # }
+//! ```
+use log::trace;
+use crate::{
+    Connection,
+    Error,
+    Method,
+    Result,
+    api::v1::accounts,
+    entities::Account,
+};
+
+const HTTP_NOT_FOUND: u16 = 404;
+
+/// Resolve `handle` such as `@alice@example.social`, `alice@example.social` or `alice` to the account.
+///
+/// The account is looked up with `/api/v1/accounts/lookup` first.
+/// If the server does not know the account or does not support lookup,
+/// it is resolved with `/api/v1/accounts/search` with WebFinger.
+pub fn resolve_account(conn: &Connection, handle: &str) -> Result<Account> {
+    let (username, domain) = parse_handle(handle)
+        .ok_or_else(|| Error::AccountNotFoundError(handle.to_owned()))?;
+    let acct = match domain {
+        Some(domain) => format!("{}@{}", username, domain),
+        None => username.to_owned(),
+    };
+
+    match accounts::lookup::get(conn, acct.as_str()).send() {
+        Ok(account) => return Ok(account),
+        Err(e) if is_not_found(&e) => trace!("{} is not found by lookup, try search", acct),
+        Err(e) => return Err(e),
+    }
+
+    let local_domain = conn.server_url().host_str();
+    accounts::search::get(conn, acct.as_str())
+        .resolve()
+        .limit(5)
+        .send()?
+        .into_iter()
+        .find(|account| acct_matches(account.acct(), account.is_local(), &acct, local_domain))
+        .ok_or(Error::AccountNotFoundError(acct))
+}
+
+// Split `handle` into the username and the domain, `None` if it is not a handle.
+pub(super) fn parse_handle(handle: &str) -> Option<(&str, Option<&str>)> {
+    let handle = handle.trim();
+    let handle = handle.strip_prefix("acct:").unwrap_or(handle);
+    let handle = handle.strip_prefix('@').unwrap_or(handle);
+
+    let (username, domain) = match handle.split_once('@') {
+        Some((username, domain)) => (username, Some(domain)),
+        None => (handle, None),
+    };
+
+    let is_valid_username = !username.is_empty() && username.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    let is_valid_domain = domain
+        .map(|domain| !domain.is_empty() && !domain.contains(|c: char| c == '@' || c == '/' || c.is_whitespace()))
+        .unwrap_or(true);
+
+    if is_valid_username && is_valid_domain {
+        Some((username, domain))
+    } else {
+        None
+    }
+}
+
+// Local accounts have only username as acct, so compare with the domain of the connected server too.
+pub(super) fn acct_matches(account_acct: &str, is_local: bool, acct: &str, local_domain: Option<&str>) -> bool {
+    if account_acct.eq_ignore_ascii_case(acct) {
+        return true;
+    }
+
+    match (is_local, local_domain) {
+        (true, Some(domain)) => acct.eq_ignore_ascii_case(&format!("{}@{}", account_acct, domain)),
+        _ => false,
+    }
+}
+
+fn is_not_found(e: &Error) -> bool {
+    match e {
+        Error::HttpClientStatusError(_, status, _) | Error::HttpUnexpectedStatusError(_, status) => *status == HTTP_NOT_FOUND,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_handle() {
+        assert_eq!(parse_handle("@alice@example.social"), Some(("alice", Some("example.social"))));
+        assert_eq!(parse_handle("acct:alice@example.social"), Some(("alice", Some("example.social"))));
+        assert_eq!(parse_handle(" alice "), Some(("alice", None)));
+        assert_eq!(parse_handle("@"), None);
+        assert_eq!(parse_handle("alice@"), None);
+        assert_eq!(parse_handle("alice@example.social@evil"), None);
+        assert_eq!(parse_handle("https://example.social/@alice"), None);
+    }
+
+    #[test]
+    fn test_acct_matches() {
+        assert!(acct_matches("alice@example.social", false, "Alice@Example.social", Some("localhost")));
+        assert!(acct_matches("alice", true, "alice@localhost", Some("localhost")));
+        assert!(acct_matches("alice", true, "alice", Some("localhost")));
+        assert!(!acct_matches("alice", true, "alice@example.social", Some("localhost")));
+        assert!(!acct_matches("alice@example.social", false, "alice", Some("localhost")));
+    }
+
+    #[test]
+    fn test_resolve_account() {
+        let conn = Connection::new().unwrap();
+        let me = accounts::verify_credentials::get(&conn).send().unwrap();
+
+        let account = resolve_account(&conn, &format!("@{}", me.acct())).unwrap();
+        assert_eq!(account.id(), me.id());
+    }
+}
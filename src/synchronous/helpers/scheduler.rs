@@ -0,0 +1,259 @@
+//! This module provides a helper to manage scheduled statuses within the limits of the server.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::{ Scheduler, StatusDraft };
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let scheduler = Scheduler::new(&conn);
+//!
+//! let tomorrow = Utc::now() + chrono::Duration::days(1);
+//! scheduler.schedule(&StatusDraft::new("Good morning!"), tomorrow)?;
+//!
+//! // Postpone all scheduled statuses for an hour.
+//! scheduler.shift(chrono::Duration::hours(1), |_| true)?;
+//!
+//! // Cancel scheduled statuses that have no text.
+//! scheduler.cancel(|scheduled| scheduled.params().text().is_none())?;
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use chrono::{ DateTime, Duration, NaiveDate, Utc };
+use log::{ debug, trace };
+use crate::{
+    Connection,
+    Error,
+    Method,
+    Result,
+    api::v1::{
+        scheduled_statuses,
+        statuses::LEAST_SCHEDULABLE_PERIOD,
+    },
+    entities::{
+        ScheduledStatus,
+        ScheduledStatusId,
+    },
+};
+use super::{
+    PostedDraft,
+    StatusDraft,
+};
+
+/// The max number of statuses that can be scheduled on the same day in UTC, specified by app/validators/scheduled_status_validator.rb.
+pub const MAX_DAILY_SCHEDULED_STATUSES: usize = 25;
+
+/// The max number of statuses that can be scheduled in total, specified by app/validators/scheduled_status_validator.rb.
+pub const MAX_SCHEDULED_STATUSES: usize = 300;
+
+// Max number of scheduled statuses to get with a single request, twice of DEFAULT_STATUSES_LIMIT of app/controllers/api/v1/scheduled_statuses_controller.rb.
+const PAGE_LIMIT: usize = 40;
+
+/// A helper to list, schedule, reschedule and cancel scheduled statuses of the authenticated user.
+///
+/// New schedules are checked against [`MAX_DAILY_SCHEDULED_STATUSES`](./constant.MAX_DAILY_SCHEDULED_STATUSES.html)
+/// and [`MAX_SCHEDULED_STATUSES`](./constant.MAX_SCHEDULED_STATUSES.html) before sending requests.
+#[derive(Debug, Clone)]
+pub struct Scheduler<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> Scheduler<'a> {
+    /// Create a new `Scheduler`.
+    pub fn new(conn: &'a Connection) -> Self {
+        Scheduler {
+            conn,
+        }
+    }
+
+    /// Get all scheduled statuses by following every page, in order of the scheduled datetime.
+    pub fn list(&self) -> Result<Vec<ScheduledStatus>> {
+        let mut scheduled_statuses: Vec<ScheduledStatus> = Vec::new();
+        let mut max_id: Option<ScheduledStatusId> = None;
+
+        loop {
+            let req = scheduled_statuses::get(self.conn).limit(PAGE_LIMIT);
+            let req = match max_id {
+                Some(max_id) => req.max_id(max_id),
+                None => req,
+            };
+
+            let page = req.send()?;
+            trace!("Got {} scheduled statuses", page.len());
+
+            max_id = page.iter().map(|scheduled| scheduled.id()).min().cloned();
+            let is_last = page.len() < PAGE_LIMIT;
+            scheduled_statuses.extend(page);
+
+            if is_last || max_id.is_none() {
+                break;
+            }
+        }
+
+        scheduled_statuses.sort_by_key(|scheduled| scheduled.scheduled_at());
+        Ok(scheduled_statuses)
+    }
+
+    /// Check whether a status can be scheduled at `scheduled_at` without exceeding the limits of the server.
+    pub fn check(&self, scheduled_at: DateTime<Utc>) -> Result<()> {
+        let scheduled_statuses = self.list()?;
+        check_limits(scheduled_statuses.iter().map(|scheduled| scheduled.scheduled_at()), Some(scheduled_at))
+    }
+
+    /// Check the limits of the server, and schedule `draft` to be published at `scheduled_at`.
+    ///
+    /// `scheduled_at` overrides the datetime to publish of `draft`.
+    pub fn schedule(&self, draft: &StatusDraft, scheduled_at: DateTime<Utc>) -> Result<ScheduledStatus> {
+        self.check(scheduled_at)?;
+
+        let mut draft = draft.clone();
+        draft.set_scheduled_at(scheduled_at);
+        match draft.post(self.conn)? {
+            PostedDraft::Scheduled(scheduled) => Ok(*scheduled),
+            PostedDraft::Status(_) => unreachable!("a draft that has the datetime to publish is always scheduled"),
+        }
+    }
+
+    /// Shift the scheduled datetime of scheduled statuses that satisfy `predicate` by `by`, and get updated scheduled statuses.
+    ///
+    /// All new datetimes are checked against the limits of the server before any request is sent.
+    /// Scheduled statuses are updated from the one moving away from others first,
+    /// so that the daily limit is not exceeded temporarily while shifting.
+    pub fn shift<P>(&self, by: Duration, mut predicate: P) -> Result<Vec<ScheduledStatus>>
+    where
+        P: FnMut(&ScheduledStatus) -> bool,
+    {
+        let (targets, others): (Vec<ScheduledStatus>, Vec<ScheduledStatus>) = self.list()?
+            .into_iter()
+            .partition(|scheduled| predicate(scheduled));
+
+        let plan = plan_shift(targets.iter().map(|scheduled| (scheduled.id().clone(), scheduled.scheduled_at())), by);
+
+        let now = Utc::now();
+        if let Some((_, earliest)) = plan.iter().min_by_key(|(_, scheduled_at)| *scheduled_at) {
+            if *earliest - now < Duration::seconds(LEAST_SCHEDULABLE_PERIOD) {
+                return Err(Error::ScheduleTooCloseError(now, *earliest));
+            }
+        }
+        check_limits(
+            others.iter().map(|scheduled| scheduled.scheduled_at()),
+            plan.iter().map(|(_, scheduled_at)| *scheduled_at),
+        )?;
+
+        debug!("Shift {} scheduled statuses by {}", plan.len(), by);
+        plan.into_iter()
+            .map(|(id, scheduled_at)| scheduled_statuses::id::put(self.conn, id).scheduled_at(scheduled_at).send())
+            .collect()
+    }
+
+    /// Cancel scheduled statuses that satisfy `predicate`, and get IDs of canceled scheduled statuses.
+    pub fn cancel<P>(&self, mut predicate: P) -> Result<Vec<ScheduledStatusId>>
+    where
+        P: FnMut(&ScheduledStatus) -> bool,
+    {
+        let targets = self.list()?
+            .into_iter()
+            .filter(|scheduled| predicate(scheduled))
+            .map(|scheduled| scheduled.id().clone())
+            .collect::<Vec<ScheduledStatusId>>();
+
+        debug!("Cancel {} scheduled statuses", targets.len());
+        for id in targets.iter() {
+            scheduled_statuses::id::delete(self.conn, id).send()?;
+        }
+        Ok(targets)
+    }
+}
+
+/// Check whether statuses can be scheduled at `adding` in addition to statuses already scheduled at `scheduled`.
+///
+/// Returns `TooManyScheduledStatusesError` if the total exceeds [`MAX_SCHEDULED_STATUSES`](./constant.MAX_SCHEDULED_STATUSES.html),
+/// or `TooManyDailyScheduledStatusesError` if any day in UTC exceeds [`MAX_DAILY_SCHEDULED_STATUSES`](./constant.MAX_DAILY_SCHEDULED_STATUSES.html).
+pub fn check_limits<T, U>(scheduled: T, adding: U) -> Result<()>
+where
+    T: IntoIterator<Item = DateTime<Utc>>,
+    U: IntoIterator<Item = DateTime<Utc>>,
+{
+    let mut daily: HashMap<NaiveDate, usize> = HashMap::new();
+    let mut total = 0;
+
+    for scheduled_at in scheduled {
+        *daily.entry(scheduled_at.date_naive()).or_default() += 1;
+        total += 1;
+    }
+
+    for scheduled_at in adding {
+        total += 1;
+        if total > MAX_SCHEDULED_STATUSES {
+            return Err(Error::TooManyScheduledStatusesError(MAX_SCHEDULED_STATUSES));
+        }
+
+        let date = scheduled_at.date_naive();
+        let count = daily.entry(date).or_default();
+        *count += 1;
+        if *count > MAX_DAILY_SCHEDULED_STATUSES {
+            return Err(Error::TooManyDailyScheduledStatusesError(date, MAX_DAILY_SCHEDULED_STATUSES));
+        }
+    }
+
+    Ok(())
+}
+
+// New datetimes in order to be updated, the latest first when postponing and the earliest first when advancing.
+fn plan_shift<T>(targets: T, by: Duration) -> Vec<(ScheduledStatusId, DateTime<Utc>)>
+where
+    T: IntoIterator<Item = (ScheduledStatusId, DateTime<Utc>)>,
+{
+    let mut plan = targets.into_iter()
+        .map(|(id, scheduled_at)| (id, scheduled_at + by))
+        .collect::<Vec<_>>();
+
+    plan.sort_by_key(|(_, scheduled_at)| *scheduled_at);
+    if by > Duration::zero() {
+        plan.reverse();
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2020, 1, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let full_day = (0..MAX_DAILY_SCHEDULED_STATUSES).map(|_| at(1, 12)).collect::<Vec<_>>();
+
+        assert!(check_limits(full_day.clone(), vec![at(2, 0)]).is_ok());
+        assert!(matches!(
+            check_limits(full_day.clone(), vec![at(1, 23)]),
+            Err(Error::TooManyDailyScheduledStatusesError(date, MAX_DAILY_SCHEDULED_STATUSES)) if date == at(1, 0).date_naive()
+        ));
+        assert!(check_limits(Vec::new(), full_day).is_ok());
+
+        let spread = (0..MAX_SCHEDULED_STATUSES).map(|i| at(1 + (i / 20) as u32, 0)).collect::<Vec<_>>();
+        assert!(check_limits(spread.clone(), Vec::new()).is_ok());
+        assert!(matches!(
+            check_limits(spread, vec![at(31, 0)]),
+            Err(Error::TooManyScheduledStatusesError(MAX_SCHEDULED_STATUSES))
+        ));
+    }
+
+    #[test]
+    fn test_plan_shift() {
+        let targets = vec![("1".into(), at(1, 1)), ("2".into(), at(1, 3)), ("3".into(), at(1, 2))];
+
+        let plan = plan_shift(targets.clone(), Duration::hours(1));
+        assert_eq!(plan, vec![("2".into(), at(1, 4)), ("3".into(), at(1, 3)), ("1".into(), at(1, 2))]);
+
+        let plan = plan_shift(targets, Duration::hours(-1));
+        assert_eq!(plan, vec![("1".into(), at(1, 0)), ("3".into(), at(1, 1)), ("2".into(), at(1, 2))]);
+    }
+}
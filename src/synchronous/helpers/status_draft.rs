@@ -0,0 +1,668 @@
+//! This module provides a status draft that can be stored, validated offline and posted later.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::{ PostedDraft, ServerConfiguration, StatusDraft };
+//!
+//! let mut draft = StatusDraft::new("Hello, world!");
+//! draft.set_spoiler_text("greeting").set_visibility(Visibility::Unlisted);
+//! draft.validate(&ServerConfiguration::default())?;
+//!
+//! let saved = draft.to_toml()?;
+//! let draft = StatusDraft::from_toml(&saved)?;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! match draft.post(&conn)? {
+//!     PostedDraft::Status(status) => println!("posted: {}", status.id()),
+//!     PostedDraft::Scheduled(scheduled) => println!("scheduled: {}", scheduled.id()),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashSet;
+use chrono::{ DateTime, Duration, Utc };
+use isolang::Language;
+use serde::{ Deserialize, Serialize };
+use crate::{
+    Connection,
+    Error,
+    Method,
+    Result,
+    api::v1::statuses,
+    entities::{
+        MediaId,
+        Params,
+        ScheduledPoll,
+        ScheduledStatus,
+        Status,
+        StatusId,
+        Visibility,
+    },
+    status_length,
+};
+
+// Default limits of Mastodon, see app/validators/status_length_validator.rb, app/models/status.rb and app/validators/poll_validator.rb.
+const DEFAULT_MAX_CHARACTERS: usize = 500;
+const DEFAULT_MAX_MEDIA_ATTACHMENTS: usize = 4;
+const DEFAULT_MAX_POLL_OPTIONS: usize = 4;
+const DEFAULT_MAX_POLL_OPTION_CHARACTERS: usize = 50;
+const DEFAULT_MIN_POLL_EXPIRATION: u64 = 300;
+const DEFAULT_MAX_POLL_EXPIRATION: u64 = 2_629_746;
+
+/// Represents limits of the server to validate statuses without network access.
+///
+/// The default value is the default limits of Mastodon.
+/// The limits of characters, media attachments and poll options can be taken from the connection with `ServerConfiguration::from(&conn)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfiguration {
+    max_characters: usize,
+    max_media_attachments: usize,
+    max_poll_options: usize,
+    max_poll_option_characters: usize,
+    min_poll_expiration: u64,
+    max_poll_expiration: u64,
+}
+
+impl ServerConfiguration {
+    /// Get the max number of characters of a status including the spoiler text.
+    pub fn max_characters(&self) -> usize {
+        self.max_characters
+    }
+
+    /// Get the max number of media attachments of a status.
+    pub fn max_media_attachments(&self) -> usize {
+        self.max_media_attachments
+    }
+
+    /// Get the max number of options of a poll.
+    pub fn max_poll_options(&self) -> usize {
+        self.max_poll_options
+    }
+
+    /// Get the max number of characters of each poll option.
+    pub fn max_poll_option_characters(&self) -> usize {
+        self.max_poll_option_characters
+    }
+
+    /// Get the min seconds until a poll expires.
+    pub fn min_poll_expiration(&self) -> u64 {
+        self.min_poll_expiration
+    }
+
+    /// Get the max seconds until a poll expires.
+    pub fn max_poll_expiration(&self) -> u64 {
+        self.max_poll_expiration
+    }
+
+    /// Set the max number of characters of a status including the spoiler text.
+    pub fn set_max_characters(&mut self, max_characters: usize) -> &mut Self {
+        self.max_characters = max_characters;
+        self
+    }
+
+    /// Set the max number of media attachments of a status.
+    pub fn set_max_media_attachments(&mut self, max_media_attachments: usize) -> &mut Self {
+        self.max_media_attachments = max_media_attachments;
+        self
+    }
+
+    /// Set the max number of options of a poll.
+    pub fn set_max_poll_options(&mut self, max_poll_options: usize) -> &mut Self {
+        self.max_poll_options = max_poll_options;
+        self
+    }
+
+    /// Set the max number of characters of each poll option.
+    pub fn set_max_poll_option_characters(&mut self, max_poll_option_characters: usize) -> &mut Self {
+        self.max_poll_option_characters = max_poll_option_characters;
+        self
+    }
+
+    /// Set the range of seconds until a poll expires.
+    pub fn set_poll_expiration(&mut self, min: u64, max: u64) -> &mut Self {
+        self.min_poll_expiration = min;
+        self.max_poll_expiration = max;
+        self
+    }
+}
+
+impl Default for ServerConfiguration {
+    fn default() -> Self {
+        ServerConfiguration {
+            max_characters: DEFAULT_MAX_CHARACTERS,
+            max_media_attachments: DEFAULT_MAX_MEDIA_ATTACHMENTS,
+            max_poll_options: DEFAULT_MAX_POLL_OPTIONS,
+            max_poll_option_characters: DEFAULT_MAX_POLL_OPTION_CHARACTERS,
+            min_poll_expiration: DEFAULT_MIN_POLL_EXPIRATION,
+            max_poll_expiration: DEFAULT_MAX_POLL_EXPIRATION,
+        }
+    }
+}
+
+impl From<&Connection> for ServerConfiguration {
+    fn from(conn: &Connection) -> Self {
+        ServerConfiguration {
+            max_characters: conn.status_max_characters(),
+            max_media_attachments: conn.status_max_medias(),
+            max_poll_options: conn.poll_max_options(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Represents a status to be posted or scheduled later.
+///
+/// Unlike POST requests of [`statuses`](../../api/v1/statuses/index.html), a draft does not borrow the connection,
+/// so it can be stored as JSON or TOML and posted with any connection.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusDraft {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spoiler_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<Visibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_reply_to_id: Option<StatusId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    media_ids: Vec<MediaId>,
+    #[serde(skip_serializing_if = "is_false")]
+    sensitive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_at: Option<DateTime<Utc>>,
+    // Tables must be placed after values in TOML.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll: Option<DraftPoll>,
+}
+
+impl StatusDraft {
+    /// Create a new draft with the status content text.
+    pub fn new(text: impl Into<String>) -> Self {
+        StatusDraft {
+            text: Some(text.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Deserialize a draft from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize this draft to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(Error::SerializeJsonError)
+    }
+
+    /// Deserialize a draft from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Serialize this draft to TOML.
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Get the status content text.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Get the spoiler text.
+    pub fn spoiler_text(&self) -> Option<&str> {
+        self.spoiler_text.as_deref()
+    }
+
+    /// Get the visibility, `None` to use the default visibility of the account.
+    pub fn visibility(&self) -> Option<Visibility> {
+        self.visibility
+    }
+
+    /// Get the ISO 639-1 language code, `None` to use the default language of the connection.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Get the ID of the status to reply to.
+    pub fn in_reply_to_id(&self) -> Option<&StatusId> {
+        self.in_reply_to_id.as_ref()
+    }
+
+    /// Get IDs of media attachments that have been uploaded.
+    pub fn media_ids(&self) -> &Vec<MediaId> {
+        &self.media_ids
+    }
+
+    /// Get whether media attachments are marked as sensitive.
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Get the datetime to publish the status, `None` to post it immediately.
+    pub fn scheduled_at(&self) -> Option<DateTime<Utc>> {
+        self.scheduled_at
+    }
+
+    /// Get the poll.
+    pub fn poll(&self) -> Option<&DraftPoll> {
+        self.poll.as_ref()
+    }
+
+    /// Set the status content text.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the spoiler text, or remove it by `None`.
+    pub fn set_spoiler_text<T: Into<String>>(&mut self, spoiler_text: impl Into<Option<T>>) -> &mut Self {
+        self.spoiler_text = spoiler_text.into().map(Into::into);
+        self
+    }
+
+    /// Set the visibility, or use the default visibility of the account by `None`.
+    pub fn set_visibility(&mut self, visibility: impl Into<Option<Visibility>>) -> &mut Self {
+        self.visibility = visibility.into();
+        self
+    }
+
+    /// Set the ISO 639-1 language code, or use the default language of the connection by `None`.
+    pub fn set_language<T: Into<String>>(&mut self, language: impl Into<Option<T>>) -> &mut Self {
+        self.language = language.into().map(Into::into);
+        self
+    }
+
+    /// Set the ID of the status to reply to, or remove it by `None`.
+    pub fn set_in_reply_to_id<T: Into<StatusId>>(&mut self, in_reply_to_id: impl Into<Option<T>>) -> &mut Self {
+        self.in_reply_to_id = in_reply_to_id.into().map(Into::into);
+        self
+    }
+
+    /// Set IDs of media attachments that have been uploaded.
+    pub fn set_media_ids<T, U>(&mut self, media_ids: T) -> &mut Self
+    where
+        T: AsRef<[U]>,
+        U: Clone + Into<MediaId>,
+    {
+        self.media_ids = media_ids.as_ref().iter().cloned().map(Into::into).collect();
+        self
+    }
+
+    /// Set whether media attachments are marked as sensitive.
+    pub fn set_sensitive(&mut self, sensitive: bool) -> &mut Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Set the datetime to publish the status, or post it immediately by `None`.
+    pub fn set_scheduled_at(&mut self, scheduled_at: impl Into<Option<DateTime<Utc>>>) -> &mut Self {
+        self.scheduled_at = scheduled_at.into();
+        self
+    }
+
+    /// Set the poll, or remove it by `None`.
+    pub fn set_poll(&mut self, poll: impl Into<Option<DraftPoll>>) -> &mut Self {
+        self.poll = poll.into();
+        self
+    }
+
+    /// Validate this draft against `config` without network access.
+    ///
+    /// The schedule is validated against the current time, so a valid draft can become invalid later.
+    pub fn validate(&self, config: &ServerConfiguration) -> Result<()> {
+        let text = self.posted_text().unwrap_or_default();
+
+        if text.is_empty() && self.media_ids.is_empty() {
+            return Err(Error::InvalidStatusError);
+        }
+
+        if self.poll.is_some() && !self.media_ids.is_empty() {
+            return Err(Error::PollWithMediaAttachmentsError);
+        }
+
+        let total_chars = status_length::count_status(text, self.spoiler_text.as_deref().unwrap_or_default());
+        if total_chars > config.max_characters {
+            return Err(Error::TooManyCharactersError(total_chars, config.max_characters));
+        }
+
+        if let Some(language) = self.language.as_ref() {
+            if Language::from_639_1(language).is_none() {
+                return Err(Error::ParseIso639_1Error(language.to_owned()));
+            }
+        }
+
+        if self.media_ids.len() > config.max_media_attachments {
+            return Err(Error::TooManyAttachmentMediasError(self.media_ids.len(), config.max_media_attachments));
+        }
+
+        if self.media_ids.iter().collect::<HashSet<&MediaId>>().len() != self.media_ids.len() {
+            return Err(Error::DuplicateMediaError);
+        }
+
+        if let Some(poll) = self.poll.as_ref() {
+            poll.validate(config)?;
+        }
+
+        if let Some(scheduled_at) = self.scheduled_at {
+            let now = Utc::now();
+            if scheduled_at - now < Duration::seconds(statuses::LEAST_SCHEDULABLE_PERIOD) {
+                return Err(Error::ScheduleTooCloseError(now, scheduled_at));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate this draft against the limits of `conn`, and post or schedule it.
+    pub fn post(&self, conn: &Connection) -> Result<PostedDraft> {
+        self.validate(&ServerConfiguration::from(conn))?;
+
+        let mut req = match self.posted_text() {
+            Some(text) if !text.is_empty() => statuses::post(conn).status(text),
+            _ => statuses::post(conn).without_status(),
+        };
+        if let Some(spoiler_text) = self.spoiler_text.as_ref() {
+            req = req.spoiler_text(spoiler_text);
+        }
+        if let Some(visibility) = self.visibility {
+            req = req.visibility(visibility);
+        }
+        if let Some(language) = self.language.as_ref() {
+            req = req.language(language);
+        }
+        if let Some(in_reply_to_id) = self.in_reply_to_id.as_ref() {
+            req = req.in_reply_to_id(in_reply_to_id);
+        }
+        if self.sensitive {
+            req = req.sensitive();
+        }
+
+        if let Some(poll) = self.poll.as_ref() {
+            let mut req = req.poll(&poll.options, poll.expires_in);
+            if poll.multiple {
+                req = req.poll_multiple();
+            }
+            if poll.hide_totals {
+                req = req.poll_hide_totals();
+            }
+
+            return match self.scheduled_at {
+                Some(scheduled_at) => Ok(PostedDraft::Scheduled(Box::new(req.scheduled_at(scheduled_at).send()?))),
+                None => Ok(PostedDraft::Status(Box::new(req.send()?))),
+            };
+        }
+
+        if !self.media_ids.is_empty() {
+            let req = req.media_ids(&self.media_ids);
+
+            return match self.scheduled_at {
+                Some(scheduled_at) => Ok(PostedDraft::Scheduled(Box::new(req.scheduled_at(scheduled_at).send()?))),
+                None => Ok(PostedDraft::Status(Box::new(req.send()?))),
+            };
+        }
+
+        match self.scheduled_at {
+            Some(scheduled_at) => Ok(PostedDraft::Scheduled(Box::new(req.scheduled_at(scheduled_at).send()?))),
+            None => Ok(PostedDraft::Status(Box::new(req.send()?))),
+        }
+    }
+
+    // Status content text is trimmed when it is posted.
+    fn posted_text(&self) -> Option<&str> {
+        self.text.as_deref().map(str::trim)
+    }
+}
+
+impl From<&Params> for StatusDraft {
+    fn from(params: &Params) -> Self {
+        StatusDraft {
+            text: params.text().cloned(),
+            spoiler_text: params.spoiler_text().map(|spoiler_text| spoiler_text.to_owned()),
+            visibility: params.visibility(),
+            language: None,
+            in_reply_to_id: params.in_reply_to_id().cloned(),
+            media_ids: params.media_ids().cloned().unwrap_or_default(),
+            sensitive: params.sensitive(),
+            scheduled_at: params.scheduled_at(),
+            poll: params.poll().map(DraftPoll::from),
+        }
+    }
+}
+
+/// The datetime to publish is taken from the scheduled status because `Params` may not have it.
+impl From<&ScheduledStatus> for StatusDraft {
+    fn from(scheduled_status: &ScheduledStatus) -> Self {
+        let mut draft = StatusDraft::from(scheduled_status.params());
+        draft.scheduled_at = Some(scheduled_status.scheduled_at());
+        draft
+    }
+}
+
+/// Represents a poll of [`StatusDraft`](./struct.StatusDraft.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DraftPoll {
+    options: Vec<String>,
+    expires_in: u64,
+    #[serde(default)]
+    multiple: bool,
+    #[serde(default)]
+    hide_totals: bool,
+}
+
+impl DraftPoll {
+    /// Create a new poll that has `options` and expires in `expires_in` seconds.
+    ///
+    /// Options are trimmed and blank options are ignored.
+    pub fn new<T, U>(options: T, expires_in: u64) -> Self
+    where
+        T: AsRef<[U]>,
+        U: AsRef<str>,
+    {
+        DraftPoll {
+            options: options.as_ref()
+                .iter()
+                .map(|option| option.as_ref().trim())
+                .filter(|option| !option.is_empty())
+                .map(|option| option.to_owned())
+                .collect(),
+            expires_in,
+            multiple: false,
+            hide_totals: false,
+        }
+    }
+
+    /// Get options of this poll.
+    pub fn options(&self) -> &Vec<String> {
+        &self.options
+    }
+
+    /// Get seconds until this poll expires.
+    pub fn expires_in(&self) -> u64 {
+        self.expires_in
+    }
+
+    /// Get whether this poll allows multiple choices.
+    pub fn multiple(&self) -> bool {
+        self.multiple
+    }
+
+    /// Get whether total number of votes are hidden until this poll expires.
+    pub fn hide_totals(&self) -> bool {
+        self.hide_totals
+    }
+
+    /// Set whether this poll allows multiple choices.
+    pub fn set_multiple(&mut self, multiple: bool) -> &mut Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Set whether total number of votes are hidden until this poll expires.
+    pub fn set_hide_totals(&mut self, hide_totals: bool) -> &mut Self {
+        self.hide_totals = hide_totals;
+        self
+    }
+
+    fn validate(&self, config: &ServerConfiguration) -> Result<()> {
+        if self.options.len() < 2 {
+            return Err(Error::TooLittlePollOptionsError);
+        }
+
+        if self.options.len() > config.max_poll_options {
+            return Err(Error::TooManyPollOptionsError(self.options.len(), config.max_poll_options));
+        }
+
+        if let Some(chars) = self.options.iter().map(|option| status_length::count(option)).max() {
+            if chars > config.max_poll_option_characters {
+                return Err(Error::TooManyPollOptionCharactersError(chars, config.max_poll_option_characters));
+            }
+        }
+
+        if self.options.iter().collect::<HashSet<&String>>().len() != self.options.len() {
+            return Err(Error::DuplicatePollOptionError);
+        }
+
+        if self.expires_in < config.min_poll_expiration || self.expires_in > config.max_poll_expiration {
+            return Err(Error::InvalidPollExpirationError(self.expires_in, config.min_poll_expiration, config.max_poll_expiration));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&ScheduledPoll> for DraftPoll {
+    fn from(poll: &ScheduledPoll) -> Self {
+        DraftPoll {
+            options: poll.options().clone(),
+            expires_in: poll.expires_in(),
+            multiple: poll.multiple(),
+            hide_totals: poll.hide_totals(),
+        }
+    }
+}
+
+/// Represents the result of posting [`StatusDraft`](./struct.StatusDraft.html).
+#[derive(Debug, Clone)]
+pub enum PostedDraft {
+    /// The draft has been posted immediately.
+    Status(Box<Status>),
+
+    /// The draft has been scheduled because it has the datetime to publish.
+    Scheduled(Box<ScheduledStatus>),
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft() -> StatusDraft {
+        let mut poll = DraftPoll::new(["yes", " no ", ""], 3600);
+        poll.set_multiple(true);
+
+        let mut draft = StatusDraft::new("Which do you like?");
+        draft.set_spoiler_text("poll")
+            .set_visibility(Visibility::Unlisted)
+            .set_language("en")
+            .set_in_reply_to_id("103270115826048975")
+            .set_scheduled_at(Utc::now() + Duration::days(1))
+            .set_poll(poll);
+        draft
+    }
+
+    #[test]
+    fn test_serialize() {
+        let draft = draft();
+
+        let json = draft.to_json().unwrap();
+        assert_eq!(StatusDraft::from_json(&json).unwrap(), draft);
+
+        let toml = draft.to_toml().unwrap();
+        assert!(toml.contains("visibility = \"unlisted\""));
+        assert!(toml.contains("[poll]"));
+        assert_eq!(StatusDraft::from_toml(&toml).unwrap(), draft);
+
+        let draft = StatusDraft::from_toml("text = \"Hello\"").unwrap();
+        assert_eq!(draft, StatusDraft::new("Hello"));
+        assert_eq!(draft.to_json().unwrap(), "{\n  \"text\": \"Hello\"\n}");
+    }
+
+    #[test]
+    fn test_from_scheduled_status() {
+        let scheduled_status: ScheduledStatus = serde_json::from_str(
+            include_str!("../../../test-resources/entities/scheduled_status.json")
+        ).unwrap();
+        let draft = StatusDraft::from(&scheduled_status);
+
+        assert_eq!(draft.text(), Some("test content"));
+        assert_eq!(draft.visibility(), Some(Visibility::Private));
+        assert_eq!(draft.media_ids(), &vec![MediaId::new("22345792")]);
+        assert!(!draft.sensitive());
+        assert_eq!(draft.scheduled_at(), Some(scheduled_status.scheduled_at()));
+        assert_eq!(draft.poll().unwrap().options(), &vec!["yes".to_owned(), "no".to_owned()]);
+        assert_eq!(draft.poll().unwrap().expires_in(), 86400);
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = ServerConfiguration::default();
+        assert!(draft().validate(&config).is_ok());
+
+        assert!(matches!(StatusDraft::new("  ").validate(&config), Err(Error::InvalidStatusError)));
+        assert!(StatusDraft::default().set_media_ids(["1"]).validate(&config).is_ok());
+        assert!(matches!(
+            draft().set_media_ids(["1"]).validate(&config),
+            Err(Error::PollWithMediaAttachmentsError)
+        ));
+        assert!(matches!(
+            StatusDraft::new("a".repeat(490)).set_spoiler_text("b".repeat(11)).validate(&config),
+            Err(Error::TooManyCharactersError(501, 500))
+        ));
+        assert!(matches!(draft().set_language("xx").validate(&config), Err(Error::ParseIso639_1Error(_))));
+        assert!(matches!(
+            StatusDraft::default().set_media_ids(["1", "2", "3", "4", "5"]).validate(&config),
+            Err(Error::TooManyAttachmentMediasError(5, 4))
+        ));
+        assert!(matches!(StatusDraft::default().set_media_ids(["1", "1"]).validate(&config), Err(Error::DuplicateMediaError)));
+        assert!(matches!(
+            draft().set_scheduled_at(Utc::now() + Duration::minutes(1)).validate(&config),
+            Err(Error::ScheduleTooCloseError(_, _))
+        ));
+
+        assert!(matches!(
+            draft().set_poll(DraftPoll::new(["yes"], 3600)).validate(&config),
+            Err(Error::TooLittlePollOptionsError)
+        ));
+        assert!(matches!(
+            draft().set_poll(DraftPoll::new(["a", "b", "c", "d", "e"], 3600)).validate(&config),
+            Err(Error::TooManyPollOptionsError(5, 4))
+        ));
+        assert!(matches!(
+            draft().set_poll(DraftPoll::new(["a".repeat(51), "b".to_owned()], 3600)).validate(&config),
+            Err(Error::TooManyPollOptionCharactersError(51, 50))
+        ));
+        assert!(matches!(
+            draft().set_poll(DraftPoll::new(["a", "a"], 3600)).validate(&config),
+            Err(Error::DuplicatePollOptionError)
+        ));
+        assert!(matches!(
+            draft().set_poll(DraftPoll::new(["a", "b"], 60)).validate(&config),
+            Err(Error::InvalidPollExpirationError(60, 300, 2_629_746))
+        ));
+
+        let mut config = ServerConfiguration::default();
+        config.set_max_poll_options(5);
+        assert!(draft().set_poll(DraftPoll::new(["a", "b", "c", "d", "e"], 3600)).validate(&config).is_ok());
+    }
+}
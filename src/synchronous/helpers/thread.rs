@@ -0,0 +1,511 @@
+//! This module provides a tree of statuses built from a status and its context.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::Thread;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let mut thread = Thread::fetch(&conn, "1")?;
+//!
+//! for node in thread.depth_first() {
+//!     match node.status() {
+//!         Some(status) => println!("{}{}", "  ".repeat(node.depth()), status.id()),
+//!         None => println!("{}(missing {})", "  ".repeat(node.depth()), node.id()),
+//!     }
+//! }
+//!
+//! let added = thread.refresh(&conn)?;
+//! println!("{} new replies", added.len());
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::{ HashMap, HashSet, VecDeque };
+use log::trace;
+use crate::{
+    Connection,
+    Method,
+    Result,
+    api::v1::statuses,
+    entities::{
+        Context,
+        Status,
+        StatusId,
+    },
+};
+
+/// A tree of statuses connected by `in_reply_to_id`.
+///
+/// Statuses that are referred by `in_reply_to_id` but not included in the context,
+/// such as deleted or unfetched statuses, are kept as missing nodes that have no status.
+#[derive(Debug, Clone)]
+pub struct Thread {
+    focus_id: StatusId,
+    nodes: HashMap<StatusId, Node>,
+    pruned: HashSet<StatusId>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    status: Option<Status>,
+    parent: Option<StatusId>,
+    children: Vec<StatusId>,
+}
+
+/// Represents a node of [`Thread`](./struct.Thread.html) with the depth from the top of the tree.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadNode<'t> {
+    id: &'t StatusId,
+    status: Option<&'t Status>,
+    depth: usize,
+}
+
+impl<'t> ThreadNode<'t> {
+    /// Get the ID of the status of this node.
+    pub fn id(&self) -> &'t StatusId {
+        self.id
+    }
+
+    /// Get the status of this node, `None` if this node is missing.
+    pub fn status(&self) -> Option<&'t Status> {
+        self.status
+    }
+
+    /// Get whether the status of this node is missing because of deleted or unfetched.
+    pub fn is_missing(&self) -> bool {
+        self.status.is_none()
+    }
+
+    /// Get the depth from the top of the tree, the top is `0`.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl Thread {
+    /// Build a thread from `status` and the `context` of it.
+    pub fn new(status: Status, context: Context) -> Self {
+        let mut thread = Thread {
+            focus_id: status.id().clone(),
+            nodes: HashMap::new(),
+            pruned: HashSet::new(),
+        };
+
+        thread.insert(status);
+        thread.update(context);
+        thread
+    }
+
+    /// Fetch the status specified by `id` and its context, and build a thread.
+    pub fn fetch(conn: &Connection, id: impl Into<StatusId>) -> Result<Self> {
+        let id = id.into();
+        let status = statuses::id::get(conn, &id).send()?;
+        let context = statuses::id::context::get(conn, &id).authorized().send()?;
+
+        Ok(Self::new(status, context))
+    }
+
+    /// Fetch the context again, merge new and edited statuses, and get IDs of added statuses.
+    pub fn refresh(&mut self, conn: &Connection) -> Result<Vec<StatusId>> {
+        let context = statuses::id::context::get(conn, &self.focus_id).authorized().send()?;
+        Ok(self.update(context))
+    }
+
+    /// Merge statuses of `context` of the focused status, and get IDs of added statuses.
+    ///
+    /// Statuses that are no longer included in `context` become missing if they have replies, or are removed otherwise.
+    pub fn update(&mut self, context: Context) -> Vec<StatusId> {
+        let mut seen: HashSet<StatusId> = HashSet::new();
+        seen.insert(self.focus_id.clone());
+
+        let mut added = Vec::new();
+        let statuses = context.ancestors().iter().chain(context.descendants().iter());
+        for status in statuses {
+            seen.insert(status.id().clone());
+            // Descendants come after their parents, so new replies to pruned statuses are pruned down the subtree.
+            if self.pruned.contains(status.id()) || status.in_reply_to_id().is_some_and(|parent| self.pruned.contains(parent)) {
+                self.pruned.insert(status.id().clone());
+                continue;
+            }
+
+            let is_new = self.nodes.get(status.id()).map(|node| node.status.is_none()).unwrap_or(true);
+            self.insert(status.clone());
+            if is_new {
+                added.push(status.id().clone());
+            }
+        }
+
+        let gone = self.nodes.iter()
+            .filter(|(id, node)| node.status.is_some() && !seen.contains(*id))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<StatusId>>();
+        for id in gone {
+            trace!("Status {} is no longer in the context", id);
+            self.forget(&id);
+        }
+
+        trace!("Added {} statuses to the thread", added.len());
+        added
+    }
+
+    /// Get the ID of the status that the thread was built for.
+    pub fn focus_id(&self) -> &StatusId {
+        &self.focus_id
+    }
+
+    /// Get the top node of the tree that includes the focused status.
+    ///
+    /// The top node is missing if the first status of the conversation could not be fetched.
+    pub fn root(&self) -> ThreadNode<'_> {
+        let mut id = &self.focus_id;
+        while let Some(parent) = self.nodes.get(id).and_then(|node| node.parent.as_ref()) {
+            id = parent;
+        }
+        self.node(id, 0).expect("the focused status is always in the thread")
+    }
+
+    /// Get top nodes of all trees, the tree that includes the focused status comes first.
+    ///
+    /// There are multiple trees when replies refer to missing statuses whose parents are unknown.
+    pub fn roots(&self) -> Vec<ThreadNode<'_>> {
+        let root = self.root();
+        let mut others = self.nodes.iter()
+            .filter(|(id, node)| node.parent.is_none() && *id != root.id())
+            .map(|(id, _)| id)
+            .collect::<Vec<&StatusId>>();
+        others.sort();
+
+        std::iter::once(root)
+            .chain(others.into_iter().filter_map(|id| self.node(id, 0)))
+            .collect()
+    }
+
+    /// Get the node of the status specified by `id`.
+    pub fn get(&self, id: &StatusId) -> Option<ThreadNode<'_>> {
+        self.node(id, self.depth(id)?)
+    }
+
+    /// Get the parent node of the status specified by `id`.
+    pub fn parent(&self, id: &StatusId) -> Option<ThreadNode<'_>> {
+        let parent = self.nodes.get(id)?.parent.as_ref()?;
+        self.get(parent)
+    }
+
+    /// Get child nodes of the status specified by `id` in order of posting.
+    pub fn children(&self, id: &StatusId) -> Vec<ThreadNode<'_>> {
+        let depth = match self.depth(id) {
+            Some(depth) => depth + 1,
+            None => return Vec::new(),
+        };
+
+        self.nodes.get(id)
+            .map(|node| node.children.iter().filter_map(|child| self.node(child, depth)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get IDs of missing statuses that are referred by `in_reply_to_id` but not fetched.
+    pub fn missing_ids(&self) -> Vec<&StatusId> {
+        let mut ids = self.nodes.iter()
+            .filter(|(_, node)| node.status.is_none())
+            .map(|(id, _)| id)
+            .collect::<Vec<&StatusId>>();
+        ids.sort();
+        ids
+    }
+
+    /// Get nodes of all trees in depth-first order.
+    pub fn depth_first(&self) -> Vec<ThreadNode<'_>> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        let mut stack = self.roots();
+        stack.reverse();
+
+        while let Some(node) = stack.pop() {
+            let mut children = self.children(node.id());
+            children.reverse();
+            stack.extend(children);
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Get nodes of all trees in breadth-first order.
+    pub fn breadth_first(&self) -> Vec<ThreadNode<'_>> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        let mut queue = self.roots().into_iter().collect::<VecDeque<_>>();
+
+        while let Some(node) = queue.pop_front() {
+            queue.extend(self.children(node.id()));
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Remove statuses that satisfy `predicate` with all replies to them, and get the number of removed nodes.
+    ///
+    /// The focused status and its ancestors are never removed, and removed statuses are not added again by refreshing.
+    pub fn prune<P>(&mut self, mut predicate: P) -> usize
+    where
+        P: FnMut(&Status) -> bool,
+    {
+        let protected = self.ancestor_ids(&self.focus_id);
+        let targets = self.nodes.iter()
+            .filter(|(id, node)| !protected.contains(*id) && node.status.as_ref().map(&mut predicate).unwrap_or(false))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<StatusId>>();
+
+        let mut removed = 0;
+        for id in targets {
+            removed += self.remove_subtree(&id);
+        }
+        removed
+    }
+
+    /// Remove statuses posted by the accounts specified by IDs, such as muted accounts, with all replies to them.
+    pub fn prune_accounts<T, U>(&mut self, account_ids: T) -> usize
+    where
+        T: AsRef<[U]>,
+        U: AsRef<str>,
+    {
+        let account_ids = account_ids.as_ref().iter().map(|id| id.as_ref()).collect::<HashSet<&str>>();
+        self.prune(|status| account_ids.contains(status.account().id().as_str()))
+    }
+
+    /// Get the number of nodes including missing nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Get whether the thread has no nodes, always `false` because of the focused status.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn node(&self, id: &StatusId, depth: usize) -> Option<ThreadNode<'_>> {
+        let (id, node) = self.nodes.get_key_value(id)?;
+        Some(ThreadNode {
+            id,
+            status: node.status.as_ref(),
+            depth,
+        })
+    }
+
+    fn depth(&self, id: &StatusId) -> Option<usize> {
+        self.nodes.get(id)?;
+        Some(self.ancestor_ids(id).len() - 1)
+    }
+
+    // IDs from `id` to the top of the tree.
+    fn ancestor_ids(&self, id: &StatusId) -> HashSet<StatusId> {
+        let mut ids = HashSet::new();
+        let mut current = Some(id.clone());
+
+        while let Some(id) = current {
+            // Guard against broken data that has a loop.
+            if !ids.insert(id.clone()) {
+                break;
+            }
+            current = self.nodes.get(&id).and_then(|node| node.parent.clone());
+        }
+        ids
+    }
+
+    fn insert(&mut self, status: Status) {
+        let id = status.id().clone();
+        let parent = status.in_reply_to_id().cloned();
+
+        if let Some(parent) = parent.as_ref() {
+            let parent_node = self.nodes.entry(parent.clone()).or_insert_with(|| Node {
+                status: None,
+                parent: None,
+                children: Vec::new(),
+            });
+            if !parent_node.children.contains(&id) {
+                parent_node.children.push(id.clone());
+                parent_node.children.sort();
+            }
+        }
+
+        let node = self.nodes.entry(id).or_insert_with(|| Node {
+            status: None,
+            parent: None,
+            children: Vec::new(),
+        });
+        node.status = Some(status);
+        node.parent = parent;
+    }
+
+    // Make the node missing if it has children, or remove it otherwise.
+    fn forget(&mut self, id: &StatusId) {
+        let has_children = match self.nodes.get_mut(id) {
+            Some(node) if !node.children.is_empty() => {
+                node.status = None;
+                true
+            },
+            Some(_) => false,
+            None => return,
+        };
+
+        if !has_children {
+            let parent = self.nodes.get(id).and_then(|node| node.parent.clone());
+            self.detach(id);
+            self.nodes.remove(id);
+
+            // A missing parent is only kept while it has replies.
+            if let Some(parent) = parent {
+                if self.nodes.get(&parent).map(|node| node.status.is_none()).unwrap_or(false) {
+                    self.forget(&parent);
+                }
+            }
+        }
+    }
+
+    fn remove_subtree(&mut self, id: &StatusId) -> usize {
+        self.detach(id);
+
+        let mut removed = 0;
+        let mut stack = vec![id.clone()];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.remove(&id) {
+                stack.extend(node.children);
+                self.pruned.insert(id);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    fn detach(&mut self, id: &StatusId) {
+        let parent = self.nodes.get(id).and_then(|node| node.parent.clone());
+        if let Some(parent) = parent.and_then(|parent| self.nodes.get_mut(&parent)) {
+            parent.children.retain(|child| child != id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::status_fixture;
+
+    // (id, in_reply_to_id, account_id)
+    type Spec<'a> = (&'a str, Option<&'a str>, &'a str);
+
+    fn status_value(spec: Spec) -> serde_json::Value {
+        let (id, in_reply_to_id, account_id) = spec;
+        let mut status = status_fixture(id, account_id);
+        status["in_reply_to_id"] = in_reply_to_id.into();
+        status
+    }
+
+    fn status(spec: Spec) -> Status {
+        serde_json::from_value(status_value(spec)).unwrap()
+    }
+
+    fn context(ancestors: &[Spec], descendants: &[Spec]) -> Context {
+        let values = |specs: &[Spec]| specs.iter()
+            .map(|spec| status_value(*spec))
+            .collect::<Vec<serde_json::Value>>();
+        serde_json::from_value(serde_json::json!({
+            "ancestors": values(ancestors),
+            "descendants": values(descendants),
+        })).unwrap()
+    }
+
+    fn ids(nodes: Vec<ThreadNode>) -> Vec<String> {
+        nodes.iter()
+            .map(|node| format!("{}{}", if node.is_missing() { "?" } else { "" }, node.id()))
+            .collect()
+    }
+
+    //  1
+    //  +- 2
+    //  |  +- 10
+    //  |  +- 4
+    //  +- 3 (focus)
+    //     +- 5
+    fn thread() -> Thread {
+        Thread::new(
+            status(("3", Some("1"), "a")),
+            context(
+                &[("1", None, "a")],
+                &[("2", Some("1"), "b"), ("4", Some("2"), "c"), ("5", Some("3"), "b"), ("10", Some("2"), "a")],
+            ),
+        )
+    }
+
+    #[test]
+    fn test_traversal() {
+        let thread = thread();
+
+        assert_eq!(thread.root().id(), "1");
+        assert_eq!(ids(thread.depth_first()), vec!["1", "2", "4", "10", "3", "5"]);
+        assert_eq!(ids(thread.breadth_first()), vec!["1", "2", "3", "4", "10", "5"]);
+        assert_eq!(thread.get(&"10".into()).unwrap().depth(), 2);
+        assert_eq!(thread.parent(&"5".into()).unwrap().id(), "3");
+        assert!(thread.parent(&"1".into()).is_none());
+        assert_eq!(ids(thread.children(&"2".into())), vec!["4", "10"]);
+        assert!(thread.missing_ids().is_empty());
+    }
+
+    #[test]
+    fn test_missing() {
+        let thread = Thread::new(
+            status(("3", Some("2"), "a")),
+            context(&[], &[("5", Some("4"), "b"), ("6", Some("5"), "c")]),
+        );
+
+        assert_eq!(thread.missing_ids(), vec!["2", "4"]);
+        assert_eq!(thread.root().id(), "2");
+        assert!(thread.root().is_missing());
+        assert_eq!(ids(thread.depth_first()), vec!["?2", "3", "?4", "5", "6"]);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut thread = thread();
+
+        // The root is an ancestor of the focused status, so it is never removed.
+        assert_eq!(thread.prune_accounts(["b"]), 4);
+        assert_eq!(ids(thread.depth_first()), vec!["1", "3"]);
+        assert_eq!(thread.prune_accounts(["a"]), 0);
+
+        // Pruned statuses are not added again.
+        let added = thread.update(context(
+            &[("1", None, "a")],
+            &[("2", Some("1"), "b"), ("4", Some("2"), "c"), ("5", Some("3"), "b"), ("6", Some("3"), "c")],
+        ));
+        assert_eq!(added, vec!["6"]);
+        assert_eq!(ids(thread.depth_first()), vec!["1", "3", "6"]);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut thread = thread();
+
+        let added = thread.update(context(
+            &[("1", None, "a")],
+            &[("4", Some("2"), "c"), ("10", Some("2"), "a"), ("11", Some("5"), "c")],
+        ));
+        assert_eq!(added, vec!["11"]);
+        // 2 is deleted but has replies, and 5 is gone but a new reply refers to it.
+        assert_eq!(thread.missing_ids(), vec!["2", "5"]);
+        assert_eq!(ids(thread.depth_first()), vec!["1", "?2", "4", "10", "3", "?5", "11"]);
+
+        let added = thread.update(context(&[("1", None, "a")], &[("4", Some("2"), "c")]));
+        assert!(added.is_empty());
+        assert_eq!(ids(thread.depth_first()), vec!["1", "?2", "4", "3"]);
+
+        // New replies to a pruned status and replies to them are pruned too.
+        let mut pruned = self::thread();
+        assert_eq!(pruned.prune_accounts(["c"]), 1);
+        let added = pruned.update(context(
+            &[("1", None, "a")],
+            &[("2", Some("1"), "b"), ("4", Some("2"), "c"), ("5", Some("3"), "b"), ("10", Some("2"), "a"), ("12", Some("4"), "a"), ("13", Some("12"), "b")],
+        ));
+        assert!(added.is_empty());
+        assert!(pruned.missing_ids().is_empty());
+        assert_eq!(ids(pruned.depth_first()), vec!["1", "2", "10", "3", "5"]);
+    }
+}
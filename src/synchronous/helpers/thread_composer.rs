@@ -0,0 +1,514 @@
+//! This module provides a helper to post long text as a thread of statuses.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::ThreadComposer;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let posted = ThreadComposer::new(&conn, std::fs::read_to_string("article.txt")?)
+//!     .numbering()
+//!     .visibility(Visibility::Public)
+//!     .unlisted_follow_ups()
+//!     .part_spoiler_text(0, "Long post")
+//!     .send()?;
+//!
+//! println!("Posted {} statuses", posted.len());
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+use log::{ debug, warn };
+use unicode_segmentation::UnicodeSegmentation;
+use crate::{
+    Connection,
+    Error,
+    Method,
+    Result,
+    api::v1::statuses,
+    entities::{
+        MediaId,
+        Status,
+        StatusId,
+        Visibility,
+    },
+    status_length,
+};
+
+/// A helper to split long text into parts under the server limit and post them as a chain of replies.
+///
+/// Text is split at paragraph boundaries first, then sentence boundaries, then whitespaces,
+/// and at last grapheme boundaries, counting characters with [`status_length`](../../status_length/index.html).
+#[derive(Debug, Clone)]
+pub struct ThreadComposer<'a> {
+    conn: &'a Connection,
+    text: String,
+    max_characters: usize,
+    numbering: bool,
+    in_reply_to_id: Option<StatusId>,
+    visibility: Option<Visibility>,
+    follow_up_visibility: Option<Visibility>,
+    language: Option<String>,
+    spoiler_text: Option<String>,
+    part_spoiler_texts: BTreeMap<usize, String>,
+    part_media_ids: BTreeMap<usize, Vec<MediaId>>,
+}
+
+impl<'a> ThreadComposer<'a> {
+    /// Create a new `ThreadComposer` to post `text`.
+    ///
+    /// The limit of characters of each part defaults to [`Connection::status_max_characters()`](../../struct.Connection.html#method.status_max_characters).
+    pub fn new(conn: &'a Connection, text: impl Into<String>) -> Self {
+        ThreadComposer {
+            conn,
+            text: text.into(),
+            max_characters: conn.status_max_characters(),
+            numbering: false,
+            in_reply_to_id: None,
+            visibility: None,
+            follow_up_visibility: None,
+            language: None,
+            spoiler_text: None,
+            part_spoiler_texts: BTreeMap::new(),
+            part_media_ids: BTreeMap::new(),
+        }
+    }
+
+    /// Set the limit of characters of each part.
+    pub fn max_characters(mut self, max_characters: usize) -> Self {
+        self.max_characters = max_characters;
+        self
+    }
+
+    /// Set to append the part number such as `1/5` to each part.
+    pub fn numbering(mut self) -> Self {
+        self.numbering = true;
+        self
+    }
+
+    /// Set the status ID that the first part replies to.
+    pub fn in_reply_to_id(mut self, in_reply_to_id: impl Into<StatusId>) -> Self {
+        self.in_reply_to_id = Some(in_reply_to_id.into());
+        self
+    }
+
+    /// Set the `Visibility` of the first part, follow-up parts have the same visibility by default.
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
+    /// Set the `Visibility` of the parts except for the first part.
+    pub fn follow_up_visibility(mut self, visibility: Visibility) -> Self {
+        self.follow_up_visibility = Some(visibility);
+        self
+    }
+
+    /// Set the visibility of the parts except for the first part to `unlisted`.
+    /// This is equivalent to `follow_up_visibility(Visibility::Unlisted)`.
+    pub fn unlisted_follow_ups(self) -> Self {
+        self.follow_up_visibility(Visibility::Unlisted)
+    }
+
+    /// Set language to all parts.
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set spoiler text to all parts that have no spoiler text set by [`part_spoiler_text()`](#method.part_spoiler_text).
+    pub fn spoiler_text(mut self, spoiler_text: impl Into<String>) -> Self {
+        self.spoiler_text = Some(spoiler_text.into());
+        self
+    }
+
+    /// Set spoiler text to the part of `index` that starts from `0`.
+    pub fn part_spoiler_text(mut self, index: usize, spoiler_text: impl Into<String>) -> Self {
+        self.part_spoiler_texts.insert(index, spoiler_text.into());
+        self
+    }
+
+    /// Set media attachments to the part of `index` that starts from `0`.
+    ///
+    /// Media attachments set to the part that does not exist after splitting are ignored.
+    pub fn part_media_ids<T, U>(mut self, index: usize, media_ids: T) -> Self
+    where
+        T: AsRef<[U]>,
+        U: Clone + Into<MediaId>,
+    {
+        self.part_media_ids.insert(
+            index,
+            media_ids.as_ref().iter().cloned().map(Into::into).collect()
+        );
+        self
+    }
+
+    /// Get the text of each part without posting anything, the part numbers are included if set.
+    pub fn split(&self) -> Result<Vec<String>> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return Err(Error::InvalidStatusError);
+        }
+
+        if !self.numbering {
+            return split_text(text, self.min_limit(0)?, |index| self.limit(index, 0));
+        }
+
+        // The total number is unknown until splitting, so retry while the number of digits grows.
+        let mut digits = 1;
+        loop {
+            let suffix_len = status_length::count(&number_suffix(10_usize.pow(digits) - 1, 10_usize.pow(digits) - 1));
+            let parts = split_text(text, self.min_limit(suffix_len)?, |index| self.limit(index, suffix_len))?;
+
+            if parts.len() < 10_usize.pow(digits) {
+                let total = parts.len();
+                return Ok(
+                    parts.into_iter()
+                        .enumerate()
+                        .map(|(i, part)| part + &number_suffix(i + 1, total))
+                        .collect()
+                );
+            }
+            digits += 1;
+        }
+    }
+
+    /// Post all parts sequentially as a chain of replies and get posted statuses.
+    ///
+    /// If posting a part fails, already posted parts are deleted and the error is returned.
+    pub fn send(&self) -> Result<Vec<Status>> {
+        let parts = self.split()?;
+        debug!("Post a thread of {} statuses", parts.len());
+
+        let mut posted: Vec<Status> = Vec::with_capacity(parts.len());
+        for (index, part) in parts.iter().enumerate() {
+            let in_reply_to_id = posted.last()
+                .map(|status| status.id().to_owned())
+                .or_else(|| self.in_reply_to_id.clone());
+
+            match self.post_part(index, part, in_reply_to_id) {
+                Ok(status) => posted.push(status),
+                Err(e) => {
+                    warn!("Failed to post part {} of the thread, delete posted parts: {}", index + 1, e);
+                    self.rollback(&posted);
+                    return Err(e);
+                },
+            };
+        }
+
+        Ok(posted)
+    }
+
+    fn post_part(&self, index: usize, part: &str, in_reply_to_id: Option<StatusId>) -> Result<Status> {
+        let mut req = statuses::post(self.conn).status(part);
+
+        if let Some(id) = in_reply_to_id {
+            req = req.in_reply_to_id(id);
+        }
+        if let Some(visibility) = self.part_visibility(index) {
+            req = req.visibility(visibility);
+        }
+        if let Some(language) = self.language.as_ref() {
+            req = req.language(language);
+        }
+        if let Some(spoiler_text) = self.part_spoiler_text_of(index) {
+            req = req.spoiler_text(spoiler_text);
+        }
+
+        match self.part_media_ids.get(&index) {
+            Some(media_ids) if !media_ids.is_empty() => req.media_ids(media_ids).send(),
+            _ => req.send(),
+        }
+    }
+
+    fn rollback(&self, posted: &[Status]) {
+        for status in posted.iter().rev() {
+            if let Err(e) = statuses::id::delete(self.conn, status.id()).send() {
+                warn!("Failed to delete status {} in rollback: {}", status.id(), e);
+            }
+        }
+    }
+
+    fn part_visibility(&self, index: usize) -> Option<Visibility> {
+        match index {
+            0 => self.visibility,
+            _ => self.follow_up_visibility.or(self.visibility),
+        }
+    }
+
+    fn part_spoiler_text_of(&self, index: usize) -> Option<&str> {
+        self.part_spoiler_texts.get(&index)
+            .or(self.spoiler_text.as_ref())
+            .map(|s| s.as_str())
+    }
+
+    fn limit(&self, index: usize, suffix_len: usize) -> Result<usize> {
+        self.limit_with(self.part_spoiler_text_of(index), suffix_len)
+    }
+
+    // The smallest limit among all parts.
+    fn min_limit(&self, suffix_len: usize) -> Result<usize> {
+        let mut min = self.limit_with(self.spoiler_text.as_deref(), suffix_len)?;
+        for spoiler_text in self.part_spoiler_texts.values() {
+            min = min.min(self.limit_with(Some(spoiler_text), suffix_len)?);
+        }
+        Ok(min)
+    }
+
+    fn limit_with(&self, spoiler_text: Option<&str>, suffix_len: usize) -> Result<usize> {
+        let reserved = suffix_len + spoiler_text.map(status_length::count).unwrap_or(0);
+
+        match self.max_characters.checked_sub(reserved) {
+            Some(limit) if limit > 0 => Ok(limit),
+            _ => Err(Error::TooManyCharactersError(reserved + 1, self.max_characters)),
+        }
+    }
+}
+
+fn number_suffix(number: usize, total: usize) -> String {
+    format!("\n\n{}/{}", number, total)
+}
+
+// Split text greedily with the limit of each part, units are split to fit `unit_limit` in advance.
+fn split_text<F>(text: &str, unit_limit: usize, limit: F) -> Result<Vec<String>>
+where
+    F: Fn(usize) -> Result<usize>,
+{
+    let mut parts: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in text.split("\n\n").map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        for (i, (unit, separator)) in split_unit(paragraph, unit_limit, Level::Paragraph).into_iter().enumerate() {
+            let separator = if i == 0 { "\n\n" } else { separator };
+            let candidate = format!("{}{}{}", current, separator, unit);
+
+            if current.is_empty() {
+                current = unit;
+            } else if status_length::count(&candidate) <= limit(parts.len())? {
+                current = candidate;
+            } else {
+                parts.push(std::mem::replace(&mut current, unit));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    Ok(parts)
+}
+
+#[derive(Clone, Copy)]
+enum Level {
+    Paragraph,
+    Sentence,
+    Word,
+}
+
+// Split text into units that fit `limit` with the separators that precede each unit.
+fn split_unit(text: &str, limit: usize, level: Level) -> Vec<(String, &'static str)> {
+    if status_length::count(text) <= limit {
+        return vec![(text.to_owned(), "")];
+    }
+
+    let (pieces, next): (Vec<(String, &'static str)>, Option<Level>) = match level {
+        Level::Paragraph => (
+            text.split('\n').map(|line| (line.trim().to_owned(), "\n")).collect(),
+            Some(Level::Sentence),
+        ),
+        Level::Sentence => (sentences(text), Some(Level::Word)),
+        Level::Word => (
+            text.split_whitespace().map(|word| (word.to_owned(), " ")).collect(),
+            None,
+        ),
+    };
+
+    let mut units: Vec<(String, &'static str)> = Vec::new();
+    let mut current: Option<(String, &'static str)> = None;
+
+    let pieces = pieces.into_iter()
+        .filter(|(piece, _)| !piece.is_empty())
+        .flat_map(|(piece, separator)| {
+            let split = match next {
+                Some(next) => split_unit(&piece, limit, next),
+                None => split_graphemes(&piece, limit),
+            };
+            // The first unit of the piece takes over the separator of the piece.
+            split.into_iter()
+                .enumerate()
+                .map(move |(i, (unit, inner))| (unit, if i == 0 { separator } else { inner }))
+        })
+        .collect::<Vec<_>>();
+
+    for (piece, separator) in pieces {
+        current = match current.take() {
+            None => Some((piece, separator)),
+            Some((text, first_separator)) => {
+                let candidate = format!("{}{}{}", text, separator, piece);
+                if status_length::count(&candidate) <= limit {
+                    Some((candidate, first_separator))
+                } else {
+                    units.push((text, first_separator));
+                    Some((piece, separator))
+                }
+            },
+        };
+    }
+    units.extend(current);
+    units
+}
+
+// Split text after sentence terminators, the separator is a space after Latin terminators and empty after CJK terminators.
+fn sentences(text: &str) -> Vec<(String, &'static str)> {
+    let mut sentences: Vec<(String, &'static str)> = Vec::new();
+    let mut current = String::new();
+    let mut separator = "";
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+
+        let end = match c {
+            '。' | '！' | '？' => Some(""),
+            '.' | '!' | '?' if chars.peek().map(|c| c.is_whitespace()).unwrap_or(true) => Some(" "),
+            _ => None,
+        };
+
+        if let Some(next_separator) = end {
+            while chars.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+                chars.next();
+            }
+            sentences.push((current.trim().to_owned(), separator));
+            current.clear();
+            separator = next_separator;
+        }
+    }
+
+    if !current.trim().is_empty() {
+        sentences.push((current.trim().to_owned(), separator));
+    }
+    sentences
+}
+
+fn split_graphemes(text: &str, limit: usize) -> Vec<(String, &'static str)> {
+    let mut units: Vec<(String, &'static str)> = Vec::new();
+    let mut current = String::new();
+
+    for grapheme in text.graphemes(true) {
+        if !current.is_empty() && status_length::count(&format!("{}{}", current, grapheme)) > limit {
+            units.push((std::mem::take(&mut current), ""));
+        }
+        current.push_str(grapheme);
+    }
+    if !current.is_empty() {
+        units.push((current, ""));
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(text: &str, limit: usize) -> Vec<String> {
+        split_text(text, limit, |_| Ok(limit)).unwrap()
+    }
+
+    #[test]
+    fn test_split_paragraphs() {
+        let parts = split("First paragraph.\n\nSecond paragraph.\n\nThird.", 40);
+        assert_eq!(parts, vec!["First paragraph.\n\nSecond paragraph.", "Third."]);
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let parts = split("One two three. Four five six! Seven eight nine? Ten.", 30);
+        assert_eq!(parts, vec!["One two three. Four five six!", "Seven eight nine? Ten."]);
+
+        let parts = split("これはペンです。あれは本です。それは机です。", 16);
+        assert_eq!(parts, vec!["これはペンです。あれは本です。", "それは机です。"]);
+    }
+
+    #[test]
+    fn test_split_words_and_graphemes() {
+        let parts = split("aaaa bbbb cccc dddd", 9);
+        assert_eq!(parts, vec!["aaaa bbbb", "cccc dddd"]);
+
+        let parts = split("abcdefghij", 4);
+        assert_eq!(parts, vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_split_counts_urls() {
+        let url = "https://example.com/a/very/long/path/that/is/counted/as/23/characters";
+        let text = format!("See {} now.", url);
+        assert_eq!(split(&text, 32), vec![text.clone()]);
+    }
+
+    // Check that every part fits the limit with its spoiler text and ends with the right number.
+    fn assert_numbered(composer: &ThreadComposer, parts: &[String], max_characters: usize) {
+        let total = parts.len();
+        for (i, part) in parts.iter().enumerate() {
+            let spoiler_len = composer.part_spoiler_text_of(i).map(status_length::count).unwrap_or(0);
+            assert!(status_length::count(part) + spoiler_len <= max_characters, "part {}: {:?}", i + 1, part);
+            assert!(part.ends_with(&number_suffix(i + 1, total)), "part {}: {:?}", i + 1, part);
+        }
+    }
+
+    #[test]
+    fn test_split_numbered() {
+        let conn = Connection::offline();
+        let sentence = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+        // 9 parts fit a single digit.
+        let composer = ThreadComposer::new(&conn, [sentence; 9].join("\n\n"))
+            .max_characters(70)
+            .numbering();
+        let parts = composer.split().unwrap();
+        assert_eq!(parts.len(), 9);
+        assert!(parts[0].ends_with("\n\n1/9"));
+        assert_numbered(&composer, &parts, 70);
+
+        // 10 parts do not fit a single digit, so the text is split again for two digits.
+        let composer = ThreadComposer::new(&conn, [sentence; 10].join("\n\n"))
+            .max_characters(70)
+            .numbering();
+        let parts = composer.split().unwrap();
+        assert_eq!(parts.len(), 10);
+        assert!(parts[0].ends_with("\n\n1/10"));
+        assert!(parts[9].ends_with("\n\n10/10"));
+        assert_numbered(&composer, &parts, 70);
+    }
+
+    #[test]
+    fn test_split_numbered_with_spoiler_text() {
+        let conn = Connection::offline();
+        let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(30);
+
+        let composer = ThreadComposer::new(&conn, text)
+            .max_characters(100)
+            .numbering()
+            .spoiler_text("CW")
+            .part_spoiler_text(0, "A long content warning of the first part");
+        let parts = composer.split().unwrap();
+        assert!(parts.len() >= 10, "{}", parts.len());
+        assert_numbered(&composer, &parts, 100);
+
+        // The spoiler text leaves no room for the text and the number.
+        let composer = ThreadComposer::new(&conn, "text")
+            .max_characters(10)
+            .numbering()
+            .part_spoiler_text(1, "0123456789");
+        assert!(composer.split().is_err());
+    }
+
+    #[test]
+    fn test_parts_fit_limit() {
+        let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(40);
+        for part in split(&text, 100) {
+            assert!(status_length::count(&part) <= 100, "{}", part);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! This module provides a client-side WebFinger resolver for `user@domain` handles.
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use mastors::prelude::*;
+//! use mastors::helpers::webfinger;
+//!
+//! let conn = Connection::from_file(".env.test")?;
+//! let resource = webfinger(&conn, "@alice@example.social")?;
+//! println!("actor: {:?}, profile: {:?}", resource.actor_url(), resource.profile_url());
+//! # Ok(())
+//! # }
+//! ```
+use log::trace;
+use serde::Deserialize;
+use crate::{
+    Connection,
+    Error,
+    Result,
+    Url,
+    utils,
+};
+use super::resolve::parse_handle;
+
+const WEBFINGER_PATH: &str = "/.well-known/webfinger";
+const JRD_CONTENT_TYPE: &str = "application/jrd+json";
+
+const REL_SELF: &str = "self";
+const REL_PROFILE_PAGE: &str = "http://webfinger.net/rel/profile-page";
+const ACTIVITY_JSON_TYPES: [&str; 2] = [
+    "application/activity+json",
+    "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
+];
+
+/// Represents a JSON Resource Descriptor returned by WebFinger.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+pub struct WebFinger {
+    subject: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    links: Vec<WebFingerLink>,
+}
+
+impl WebFinger {
+    /// Get the subject such as `acct:alice@example.social`.
+    ///
+    /// The domain of the subject may be different from the requested handle if the server uses another domain for accounts.
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Get the acct handle of the subject without `acct:` such as `alice@example.social`.
+    pub fn acct(&self) -> Option<&str> {
+        self.subject.strip_prefix("acct:")
+    }
+
+    /// Get other URIs that identify the subject.
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    /// Get links related to the subject.
+    pub fn links(&self) -> &Vec<WebFingerLink> {
+        &self.links
+    }
+
+    /// Get the URL of the ActivityPub actor.
+    pub fn actor_url(&self) -> Option<&Url> {
+        self.links.iter()
+            .find(|link| link.rel == REL_SELF && link.r#type.as_deref().map(is_activity_json).unwrap_or(false))
+            .and_then(|link| link.href.as_ref())
+    }
+
+    /// Get the URL of the profile page.
+    pub fn profile_url(&self) -> Option<&Url> {
+        self.links.iter()
+            .find(|link| link.rel == REL_PROFILE_PAGE)
+            .and_then(|link| link.href.as_ref())
+    }
+}
+
+/// Represents a link of [`WebFinger`](./struct.WebFinger.html).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+pub struct WebFingerLink {
+    rel: String,
+    r#type: Option<String>,
+    href: Option<Url>,
+    template: Option<String>,
+}
+
+impl WebFingerLink {
+    /// Get the relation type such as `self` or `http://webfinger.net/rel/profile-page`.
+    pub fn rel(&self) -> &str {
+        &self.rel
+    }
+
+    /// Get the media type of the linked resource.
+    pub fn r#type(&self) -> Option<&str> {
+        self.r#type.as_deref()
+    }
+
+    /// Get the URL of the linked resource.
+    pub fn href(&self) -> Option<&Url> {
+        self.href.as_ref()
+    }
+
+    /// Get the URL template such as the template of remote follow.
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+}
+
+/// Resolve `handle` such as `@alice@example.social` with WebFinger on the server of the handle.
+///
+/// The handle without the domain is resolved on the connected server.
+/// This request is sent directly to the server of the handle without the access token.
+pub fn webfinger(conn: &Connection, handle: &str) -> Result<WebFinger> {
+    let (username, domain) = parse_handle(handle)
+        .ok_or_else(|| Error::AccountNotFoundError(handle.to_owned()))?;
+
+    let url = match domain {
+        Some(domain) => {
+            let mut url = Url::parse(&format!("https://{}", domain))?;
+            url.set_path(WEBFINGER_PATH);
+            url
+        },
+        None => conn.url(WEBFINGER_PATH)?,
+    };
+    let domain = match domain {
+        Some(domain) => domain,
+        None => conn.server_url().host_str().unwrap_or_default(),
+    };
+
+    let req = conn.client()
+        .get(url)
+        .query(&[("resource", format!("acct:{}@{}", username, domain))])
+        .header(reqwest::header::ACCEPT, JRD_CONTENT_TYPE)
+        .build()?;
+    trace!("Send a {} request to {}", req.method(), req.url());
+
+    let res = conn.client().execute(req)?;
+    trace!("{:?}", res);
+
+    Ok(utils::check_response(res)?.json::<WebFinger>()?)
+}
+
+fn is_activity_json(media_type: &str) -> bool {
+    ACTIVITY_JSON_TYPES.iter().any(|t| t.replace(' ', "") == media_type.replace(' ', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let resource: WebFinger = serde_json::from_str(r#"{
+            "subject": "acct:alice@example.social",
+            "aliases": ["https://example.social/@alice", "https://example.social/users/alice"],
+            "links": [
+                { "rel": "http://webfinger.net/rel/profile-page", "type": "text/html", "href": "https://example.social/@alice" },
+                { "rel": "self", "type": "application/activity+json", "href": "https://example.social/users/alice" },
+                { "rel": "http://ostatus.org/schema/1.0/subscribe", "template": "https://example.social/authorize_interaction?uri={uri}" }
+            ]
+        }"#).unwrap();
+
+        assert_eq!(resource.acct(), Some("alice@example.social"));
+        assert_eq!(resource.actor_url().unwrap().as_str(), "https://example.social/users/alice");
+        assert_eq!(resource.profile_url().unwrap().as_str(), "https://example.social/@alice");
+        assert_eq!(resource.links()[2].template(), Some("https://example.social/authorize_interaction?uri={uri}"));
+
+        let resource: WebFinger = serde_json::from_str(r#"{
+            "subject": "acct:bob@example.com",
+            "links": [
+                { "rel": "self", "type": "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"", "href": "https://example.com/bob" }
+            ]
+        }"#).unwrap();
+        assert_eq!(resource.actor_url().unwrap().as_str(), "https://example.com/bob");
+        assert!(resource.profile_url().is_none());
+        assert!(resource.aliases().is_empty());
+    }
+}
@@ -1,3 +1,3 @@
 //! This module represents root of API endpoints on the server.
 pub mod v1;
-//pub mod v2;
+pub mod v2;
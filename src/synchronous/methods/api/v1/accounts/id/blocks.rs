@@ -6,11 +6,14 @@ pub mod block {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to block an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostBlock {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostBlock {
     	PostBlock {
     		conn,
     		id: id.into(),
@@ -28,7 +31,7 @@ pub mod block {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -44,11 +47,14 @@ pub mod unblock {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to unblock an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnblock {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostUnblock {
     	PostUnblock {
     		conn,
     		id: id.into(),
@@ -66,7 +72,7 @@ pub mod unblock {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
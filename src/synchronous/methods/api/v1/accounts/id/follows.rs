@@ -6,11 +6,14 @@ pub mod followers {
     use crate::{
         Connection,
         MethodWithRespHeader as Method,
-        entities::Accounts,
+        entities::{
+            AccountId,
+            Accounts,
+        },
     };
     
     /// Get a request to get followers of an account specified by `id`.
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetAccountFollowers {
+    pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetAccountFollowers {
         GetAccountFollowers {
             conn,
             id: id.into(),
@@ -31,7 +34,7 @@ pub mod followers {
     
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: AccountId,
     
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -74,11 +77,14 @@ pub mod following {
     use crate::{
         Connection,
         MethodWithRespHeader as Method,
-        entities::Accounts,
+        entities::{
+            AccountId,
+            Accounts,
+        },
     };
     
     /// Get a request to get following of an account specified by `id`.
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetAccountFollowing {
+    pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetAccountFollowing {
         GetAccountFollowing {
             conn,
             id: id.into(),
@@ -99,7 +105,7 @@ pub mod following {
     
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: AccountId,
     
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -142,16 +148,20 @@ pub mod follow {
     use crate::{
         Connection,
         Method,
-        entities::Relationship,
+        entities::{
+            AccountId,
+            Relationship,
+        },
     };
     
     /// Get a request to follow an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostFollow {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostFollow {
         PostFollow {
             conn,
             id: id.into(),
             authorized: true,
             reblogs: None,
+            notify: None,
         }
     }
     
@@ -165,7 +175,7 @@ pub mod follow {
     
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: AccountId,
     
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -173,6 +183,7 @@ pub mod follow {
     
         // Optional params
         reblogs: Option<bool>,
+        notify: Option<bool>,
     }
     
     impl<'a> PostFollow<'a> {
@@ -181,6 +192,18 @@ pub mod follow {
             self.reblogs = Some(true);
             self
         }
+
+        /// Set to hide reblogs from account specified by ID.
+        pub fn hide_reblogs(mut self) -> Self {
+            self.reblogs = Some(false);
+            self
+        }
+
+        /// Set to get notifications when the account specified by ID posts a status.
+        pub fn notify(mut self) -> Self {
+            self.notify = Some(true);
+            self
+        }
     }
     
     impl<'a> Method<'a, Relationship> for PostFollow<'a> {}
@@ -192,11 +215,14 @@ pub mod unfollow {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to unfollow account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnfollow {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostUnfollow {
     	PostUnfollow {
     		conn,
     		id: id.into(),
@@ -214,7 +240,7 @@ pub mod unfollow {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -231,6 +257,7 @@ mod tests {
 		Connection,
         Method,
 		MethodWithRespHeader,
+		entities::AccountId,
 	};
 
     #[test]
@@ -252,8 +279,8 @@ mod tests {
     }
 
     use crate::api::v1::accounts::verify_credentials;
-    fn id() -> String {
+    fn id() -> AccountId {
         let conn = Connection::new().unwrap();
-        verify_credentials::get(&conn).send().unwrap().id().to_owned()
+        verify_credentials::get(&conn).send().unwrap().id().clone()
     }
 }
@@ -3,11 +3,14 @@ use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::IdentityProofs,
+    entities::{
+        AccountId,
+        IdentityProofs,
+    },
 };
 
 /// Get a request to get identity proofs of account specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetIdentityProofs {
+pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetIdentityProofs {
     GetIdentityProofs {
         conn,
         id: id.into(),
@@ -25,7 +28,7 @@ pub struct GetIdentityProofs<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: AccountId,
 
     #[serde(skip_serializing)]
     #[mastors(authorization)]
@@ -45,8 +48,8 @@ mod tests {
     }
 
     use crate::api::v1::accounts::verify_credentials;
-    fn id() -> String {
+    fn id() -> AccountId {
         let conn = Connection::new().unwrap();
-        verify_credentials::get(&conn).send().unwrap().id().to_owned()
+        verify_credentials::get(&conn).send().unwrap().id().clone()
     }
 }
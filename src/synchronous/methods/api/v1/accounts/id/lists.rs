@@ -3,11 +3,14 @@ use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::Lists,
+    entities::{
+        AccountId,
+        Lists,
+    },
 };
 
 /// Get a request to get lists that the account specified by `id` is owning.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetLists {
+pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetLists {
     GetLists {
         conn,
         id: id.into(),
@@ -25,7 +28,7 @@ pub struct GetLists<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: AccountId,
 
     #[serde(skip_serializing)]
     #[mastors(authorization)]
@@ -45,8 +48,8 @@ mod tests {
     }
 
     use crate::api::v1::accounts::verify_credentials;
-    fn id() -> String {
+    fn id() -> AccountId {
         let conn = Connection::new().unwrap();
-        verify_credentials::get(&conn).send().unwrap().id().to_owned()
+        verify_credentials::get(&conn).send().unwrap().id().clone()
     }
 }
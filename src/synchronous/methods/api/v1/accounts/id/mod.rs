@@ -30,11 +30,14 @@ use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::Account,
+    entities::{
+        Account,
+        AccountId,
+    },
 };
 
 /// Get a request to get account specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetAccount {
+pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetAccount {
     GetAccount {
         conn,
         id: id.into(),
@@ -52,7 +55,7 @@ pub struct GetAccount<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: AccountId,
 
     #[serde(skip_serializing)]
     #[mastors(authorization)]
@@ -73,8 +76,8 @@ mod tests {
     }
 
     use crate::api::v1::accounts::verify_credentials;
-    fn id() -> String {
+    fn id() -> AccountId {
         let conn = Connection::new().unwrap();
-        verify_credentials::get(&conn).send().unwrap().id().to_owned()
+        verify_credentials::get(&conn).send().unwrap().id().clone()
     }
 }
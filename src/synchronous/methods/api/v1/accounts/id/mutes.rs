@@ -6,11 +6,14 @@ pub mod mute {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to mute an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostMute {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostMute {
     	PostMute {
     		conn,
     		id: id.into(),
@@ -29,7 +32,7 @@ pub mod mute {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -56,11 +59,14 @@ pub mod unmute {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to unmute an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnmute {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostUnmute {
     	PostUnmute {
     		conn,
     		id: id.into(),
@@ -78,7 +84,7 @@ pub mod unmute {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -7,11 +7,14 @@ pub mod pin {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to pin an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostPin {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostPin {
     	PostPin {
     		conn,
     		id: id.into(),
@@ -29,7 +32,7 @@ pub mod pin {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -45,11 +48,14 @@ pub mod unpin {
     use crate::{
     	Connection,
     	Method,
-    	entities::Relationship,
+    	entities::{
+    	    AccountId,
+    	    Relationship,
+    	},
     };
     
     /// Get a request to unpin an account specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnpin {
+    pub fn post(conn: &Connection, id: impl Into<AccountId>) -> PostUnpin {
     	PostUnpin {
     		conn,
     		id: id.into(),
@@ -67,7 +73,7 @@ pub mod unpin {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: AccountId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -1,13 +1,18 @@
 //! This module provides features related to statuses posted by account specified by ID.
+use chrono::{ DateTime, Utc };
 use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::Statuses,
+    entities::{
+        AccountId,
+        Statuses,
+        StatusId,
+    },
 };
 
 /// Get a request to get statuses posted by account specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetStatuses {
+pub fn get(conn: &Connection, id: impl Into<AccountId>) -> GetStatuses {
     GetStatuses {
         conn,
         id: id.into(),
@@ -33,15 +38,15 @@ pub struct GetStatuses<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: AccountId,
 
     #[serde(skip_serializing)]
     #[mastors(authorization)]
     authorized: bool,
 
-    max_id: Option<String>,
-    min_id: Option<String>,
-    since_id: Option<String>,
+    max_id: Option<StatusId>,
+    min_id: Option<StatusId>,
+    since_id: Option<StatusId>,
     tagged: Option<String>,
     pinned: Option<bool>,
     only_media: Option<bool>,
@@ -61,7 +66,7 @@ impl<'a> GetStatuses<'a> {
     }
 
     /// Set to get statuses that have ID less than `max_id`.
-    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+    pub fn max_id(mut self, max_id: impl Into<StatusId>) -> Self {
         self.max_id = Some(max_id.into());
         self
     }
@@ -88,11 +93,26 @@ impl<'a> GetStatuses<'a> {
     /// ：
     /// ：
     /// ```
-    pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+    pub fn min_id(mut self, min_id: impl Into<StatusId>) -> Self {
         self.min_id = Some(min_id.into());
         self
     }
 
+    /// Set to get statuses posted after `since`.
+    ///
+    /// This is a shortcut of [`min_id()`](#method.min_id) with [`StatusId::from_datetime()`](../../../../../entities/struct.StatusId.html#method.from_datetime),
+    /// so the oldest statuses after `since` come first.
+    pub fn since(self, since: DateTime<Utc>) -> Self {
+        self.min_id(StatusId::from_datetime(since))
+    }
+
+    /// Set to get statuses posted before `until`.
+    ///
+    /// This is a shortcut of [`max_id()`](#method.max_id) with [`StatusId::from_datetime()`](../../../../../entities/struct.StatusId.html#method.from_datetime).
+    pub fn until(self, until: DateTime<Utc>) -> Self {
+        self.max_id(StatusId::from_datetime(until))
+    }
+
     /// Set to get latest statuses that have ID greater than `since_id`.
     /// 
     /// If an ID you specify is more than 20 older than the latest status on the server, this method gets the latest 20 statuses in between.
@@ -115,7 +135,7 @@ impl<'a> GetStatuses<'a> {
     /// ：
     /// ：
     /// ```
-    pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+    pub fn since_id(mut self, since_id: impl Into<StatusId>) -> Self {
         self.since_id = Some(since_id.into());
         self
     }
@@ -164,9 +184,9 @@ mod tests {
     }
 
     use crate::api::v1::accounts::verify_credentials;
-    fn id() -> String {
+    fn id() -> AccountId {
         let conn = Connection::new().unwrap();
-        verify_credentials::get(&conn).send().unwrap().id().to_owned()
+        verify_credentials::get(&conn).send().unwrap().id().clone()
     }
 
 }
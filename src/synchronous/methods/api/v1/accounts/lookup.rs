@@ -0,0 +1,51 @@
+//! This module provides features related to look up an account by acct handle.
+use serde::Serialize;
+use crate::{
+	Connection,
+	Method,
+	entities::Account,
+};
+
+/// Get a request to look up an account by `acct` such as `alice` or `alice@example.social`.
+///
+/// Unlike `search`, this method does not resolve accounts that are unknown to the server.
+pub fn get(conn: &Connection, acct: impl Into<String>) -> GetLookup<'_> {
+	GetLookup {
+		conn,
+		authorized: true,
+		acct: acct.into().trim_start_matches('@').to_owned(),
+	}
+}
+
+/// GET request for `/api/v1/accounts/lookup`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(GET, Account, "/api/v1/accounts/lookup")]
+pub struct GetLookup<'a> {
+	#[serde(skip_serializing)]
+	#[mastors(connection)]
+	conn: &'a Connection,
+
+	#[serde(skip_serializing)]
+	#[mastors(authorization)]
+	authorized: bool,
+
+	// Required params
+	acct: String,
+}
+
+impl<'a> Method<'a, Account> for GetLookup<'a> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::api::v1::accounts::verify_credentials;
+
+	#[test]
+	fn test_lookup() {
+		let conn = Connection::new().unwrap();
+		let me = verify_credentials::get(&conn).send().unwrap();
+
+		let account = get(&conn, format!("@{}", me.acct())).send().unwrap();
+		assert_eq!(account.id(), me.id());
+	}
+}
@@ -1,5 +1,6 @@
 //! This module provides features related to accounts.
 pub mod id;
+pub mod lookup;
 pub mod relationships;
 pub mod search;
 
@@ -11,14 +11,17 @@ use crate::{
 	Error,
 	Method,
 	Result,
-	entities::Relationships,
+	entities::{
+		AccountId,
+		Relationships,
+	},
 };
 
 /// Get a request to get your relationships specified by `ids`.
 pub fn get<T, U>(conn: &Connection, ids: T) -> GetRelationships
 where
 	T: AsRef<[U]>,
-	U: AsRef<str>
+	U: Clone + Into<AccountId>,
 {
 	GetRelationships {
 		conn,
@@ -69,21 +72,21 @@ impl<'a> Method<'a, Relationships> for GetRelationships<'a> {
 
 #[derive(Debug, Clone)]
 struct RelationshipIds {
-	inner: Vec<String>,
+	inner: Vec<AccountId>,
 }
 
 impl RelationshipIds {
 	fn new<T, U>(ids: T) -> Self
 	where
 		T: AsRef<[U]>,
-		U: AsRef<str>,
+		U: Clone + Into<AccountId>,
 	{
 		let ids = ids.as_ref()
 			.iter()
-			.map(|u| u.as_ref().trim())
+			.map(|u| u.clone().into())
+			.map(|u: AccountId| AccountId::new(u.trim()))
 			.filter(|u| !u.is_empty())
-			.map(|u| u.to_owned())
-			.collect::<Vec<String>>();
+			.collect::<Vec<AccountId>>();
 		
 		RelationshipIds {
 			inner: ids,
@@ -97,7 +100,7 @@ impl RelationshipIds {
 			return Err(Error::NoAccountIdError)
 		}
 
-		if self.inner.iter().collect::<HashSet<&String>>().len() != self.inner.len() {
+		if self.inner.iter().collect::<HashSet<&AccountId>>().len() != self.inner.len() {
 			return Err(Error::DuplicateAccountIdError)
 		}
 
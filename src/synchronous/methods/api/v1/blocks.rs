@@ -0,0 +1,60 @@
+//! This module provides features related to accounts that you are blocking.
+use serde::Serialize;
+use crate::{
+    Connection,
+    MethodWithRespHeader as Method,
+    entities::Accounts,
+};
+
+/// Get a request to get accounts that you are blocking.
+pub fn get(conn: &Connection) -> GetBlocks<'_> {
+    GetBlocks {
+        conn,
+        authorized: true,
+        max_id: None,
+        since_id: None,
+        limit: None,
+    }
+}
+
+/// GET request for `/api/v1/blocks`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(GET, Accounts, "/api/v1/blocks", "Link")]
+pub struct GetBlocks<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    max_id: Option<String>,
+    since_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl<'a> GetBlocks<'a> {
+    /// Set the max ID of the blocked accounts to get.
+    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Set the since ID of the blocked accounts to get.
+    pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Set a number of the blocked accounts to get.
+    ///
+    /// If not set, 40 by default.
+    //  By app/controllers/api/base_controller.rb#DEFAULT_ACCOUNTS_LIMIT
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a> Method<'a, Accounts> for GetBlocks<'a> {}
@@ -0,0 +1,69 @@
+//! This module provides features related to statuses that you have bookmarked.
+use serde::Serialize;
+use crate::{
+    Connection,
+    MethodWithRespHeader as Method,
+    entities::Statuses,
+};
+
+/// Get a request to get statuses that you have bookmarked.
+pub fn get(conn: &Connection) -> GetBookmarks<'_> {
+    GetBookmarks {
+        conn,
+        authorized: true,
+        max_id: None,
+        since_id: None,
+        min_id: None,
+        limit: None,
+    }
+}
+
+/// GET request for `/api/v1/bookmarks`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(GET, Statuses, "/api/v1/bookmarks", "Link")]
+pub struct GetBookmarks<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    max_id: Option<String>,
+    since_id: Option<String>,
+    min_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl<'a> GetBookmarks<'a> {
+    /// Set the max ID to get bookmarks older than it.
+    ///
+    /// This ID is an internal ID of the bookmark, use `PageNavigation` to get it.
+    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Set the since ID to get bookmarks newer than it.
+    pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Set the min ID to get bookmarks immediately newer than it.
+    pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Set a number of the bookmarked statuses to get.
+    ///
+    /// If not set, 20 by default, and max is 40.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a> Method<'a, Statuses> for GetBookmarks<'a> {}
@@ -0,0 +1,116 @@
+//! This module provides features related to domains that you are blocking.
+use serde::Serialize;
+use crate::{
+    Connection,
+    Method,
+    MethodWithRespHeader,
+    entities::{ DomainBlocks, Nothing },
+};
+
+/// Get a request to get domains that you are blocking.
+pub fn get(conn: &Connection) -> GetDomainBlocks<'_> {
+    GetDomainBlocks {
+        conn,
+        authorized: true,
+        max_id: None,
+        since_id: None,
+        limit: None,
+    }
+}
+
+/// Get a request to block a `domain`.
+///
+/// Blocking a domain hides all statuses from the domain, removes your followers from the domain and prevents following accounts of the domain.
+pub fn post(conn: &Connection, domain: impl Into<String>) -> PostDomainBlocks<'_> {
+    PostDomainBlocks {
+        conn,
+        authorized: true,
+        domain: domain.into(),
+    }
+}
+
+/// Get a request to unblock a `domain`.
+pub fn delete(conn: &Connection, domain: impl Into<String>) -> DeleteDomainBlocks<'_> {
+    DeleteDomainBlocks {
+        conn,
+        authorized: true,
+        domain: domain.into(),
+    }
+}
+
+/// GET request for `/api/v1/domain_blocks`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(GET, DomainBlocks, "/api/v1/domain_blocks", "Link")]
+pub struct GetDomainBlocks<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    max_id: Option<String>,
+    since_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl<'a> GetDomainBlocks<'a> {
+    /// Set the max ID to get blocked domains older than it.
+    ///
+    /// This ID is an internal ID of the domain block, use `PageNavigation` to get it.
+    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Set the since ID to get blocked domains newer than it.
+    pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+        self.since_id = Some(since_id.into());
+        self
+    }
+
+    /// Set a number of the blocked domains to get.
+    ///
+    /// If not set, 100 by default, and max is 200.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a> MethodWithRespHeader<'a, DomainBlocks> for GetDomainBlocks<'a> {}
+
+/// POST request for `/api/v1/domain_blocks`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(POST, Nothing, "/api/v1/domain_blocks")]
+pub struct PostDomainBlocks<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    domain: String,
+}
+
+impl<'a> Method<'a, Nothing> for PostDomainBlocks<'a> {}
+
+/// DELETE request for `/api/v1/domain_blocks`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(DELETE, Nothing, "/api/v1/domain_blocks")]
+pub struct DeleteDomainBlocks<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    domain: String,
+}
+
+impl<'a> Method<'a, Nothing> for DeleteDomainBlocks<'a> {}
@@ -6,12 +6,17 @@ use crate::{
 	Method,
 	MethodWithRespHeader,
 	Result,
-	entities::{ Accounts, Nothing },
+	entities::{
+		AccountId,
+		Accounts,
+		ListId,
+		Nothing,
+	},
 	private::MethodInternalWithoutRespHeader,
 };
 
 /// Get a request to get member accounts of list specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetListAccounts {
+pub fn get(conn: &Connection, id: impl Into<ListId>) -> GetListAccounts {
 	GetListAccounts {
 		conn,
 		id: id.into(),
@@ -25,19 +30,19 @@ pub fn get(conn: &Connection, id: impl Into<String>) -> GetListAccounts {
 /// Get request to add accounts to list specified by `id`.
 pub fn post<T, U>(
 	conn: &Connection,
-	id: impl Into<String>,
+	id: impl Into<ListId>,
 	account_ids: T
 ) -> PostListAccounts
 where
 	T: AsRef<[U]>,
-	U: AsRef<str>,
+	U: Clone + Into<AccountId>,
 {
 	let account_ids = account_ids.as_ref()
 		.iter()
-		.map(|i| i.as_ref().trim())
+		.map(|i| i.clone().into())
+		.map(|i: AccountId| AccountId::new(i.trim()))
 		.filter(|i| ! i.is_empty())
-		.map(|i| i.to_owned())
-		.collect::<Vec<String>>();
+		.collect::<Vec<AccountId>>();
 
 	PostListAccounts {
 		conn,
@@ -50,19 +55,19 @@ where
 /// Get request to remove accounts from list specified by `id`.
 pub fn delete<T, U>(
 	conn: &Connection,
-	id: impl Into<String>,
+	id: impl Into<ListId>,
 	account_ids: T
 ) -> DeleteListAccounts
 where
 	T: AsRef<[U]>,
-	U: AsRef<str>,
+	U: Clone + Into<AccountId>,
 {
 	let account_ids = account_ids.as_ref()
 		.iter()
-		.map(|i| i.as_ref().trim())
+		.map(|i| i.clone().into())
+		.map(|i: AccountId| AccountId::new(i.trim()))
 		.filter(|i| ! i.is_empty())
-		.map(|i| i.to_owned())
-		.collect::<Vec<String>>();
+		.collect::<Vec<AccountId>>();
 
 	DeleteListAccounts {
 		conn,
@@ -82,7 +87,7 @@ pub struct GetListAccounts<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 
 	#[serde(skip_serializing)]
 	#[mastors(authorization)]
@@ -130,13 +135,13 @@ pub struct PostListAccounts<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 
 	#[serde(skip_serializing)]
 	#[mastors(authorization)]
 	authorized: bool,
 
-	account_ids: Vec<String>,
+	account_ids: Vec<AccountId>,
 }
 
 impl<'a> Method<'a, Nothing> for PostListAccounts<'a> {
@@ -152,7 +157,7 @@ impl<'a> Method<'a, Nothing> for PostListAccounts<'a> {
 			);
 		}
 
-		if self.account_ids.iter().collect::<HashSet<&String>>().len() != self.account_ids.len() {
+		if self.account_ids.iter().collect::<HashSet<&AccountId>>().len() != self.account_ids.len() {
 			return Err(
 				Error::DuplicateAccountIdError
 			)
@@ -172,13 +177,13 @@ pub struct DeleteListAccounts<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 
 	#[serde(skip_serializing)]
 	#[mastors(authorization)]
 	authorized: bool,
 
-	account_ids: Vec<String>,
+	account_ids: Vec<AccountId>,
 }
 
 impl<'a> Method<'a, Nothing> for DeleteListAccounts<'a> {
@@ -194,7 +199,7 @@ impl<'a> Method<'a, Nothing> for DeleteListAccounts<'a> {
 			);
 		}
 
-		if self.account_ids.iter().collect::<HashSet<&String>>().len() != self.account_ids.len() {
+		if self.account_ids.iter().collect::<HashSet<&AccountId>>().len() != self.account_ids.len() {
 			return Err(
 				Error::DuplicateAccountIdError
 			)
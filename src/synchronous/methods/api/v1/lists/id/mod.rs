@@ -5,11 +5,16 @@ use serde::Serialize;
 use crate::{
 	Connection,
 	Method,
-	entities::{ List, Nothing },
+	entities::{
+		List,
+		ListId,
+		Nothing,
+		RepliesPolicy,
+	},
 };
 
 /// Get a request to get a list specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetList {
+pub fn get(conn: &Connection, id: impl Into<ListId>) -> GetList {
 	GetList {
 		conn,
 		authorized: true,
@@ -18,17 +23,19 @@ pub fn get(conn: &Connection, id: impl Into<String>) -> GetList {
 }
 
 /// Get a request to update title of the list specified by `id`.
-pub fn put(conn: &Connection, id: impl Into<String>, title: impl Into<String>) -> PutList {
+pub fn put(conn: &Connection, id: impl Into<ListId>, title: impl Into<String>) -> PutList {
 	PutList {
 		conn,
 		authorized: true,
 		id: id.into(),
 		title: title.into(),
+		replies_policy: None,
+		exclusive: None,
 	}
 }
 
 /// Get a request to delete a list specified by `id`.
-pub fn delete(conn: &Connection, id: impl Into<String>) -> DeleteList {
+pub fn delete(conn: &Connection, id: impl Into<ListId>) -> DeleteList {
 	DeleteList {
 		conn,
 		authorized: true,
@@ -50,7 +57,7 @@ pub struct GetList<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 }
 
 impl<'a> Method<'a, List> for GetList<'a> {}
@@ -69,9 +76,29 @@ pub struct PutList<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 
 	title: String,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	replies_policy: Option<RepliesPolicy>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	exclusive: Option<bool>,
+}
+
+impl<'a> PutList<'a> {
+	/// Set which replies should be shown in this list.
+	pub fn replies_policy(mut self, replies_policy: RepliesPolicy) -> Self {
+		self.replies_policy = Some(replies_policy);
+		self
+	}
+
+	/// Set whether members of this list are removed from the home timeline.
+	pub fn exclusive(mut self, exclusive: bool) -> Self {
+		self.exclusive = Some(exclusive);
+		self
+	}
 }
 
 impl<'a> Method<'a, List> for PutList<'a> {}
@@ -90,7 +117,7 @@ pub struct DeleteList<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: ListId,
 }
 
 impl<'a> Method<'a, Nothing> for DeleteList<'a> {}
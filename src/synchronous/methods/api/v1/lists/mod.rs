@@ -5,7 +5,7 @@ use serde::Serialize;
 use crate::{
 	Connection,
 	Method,
-	entities::{ List, Lists },
+	entities::{ List, Lists, RepliesPolicy },
 };
 
 /// Get a request to get all your lists.
@@ -22,6 +22,8 @@ pub fn post(conn: &Connection, title: impl Into<String>) -> PostLists {
 		conn,
 		authorized: true,
 		title: title.into(),
+		replies_policy: None,
+		exclusive: None,
 	}
 }
 
@@ -53,6 +55,26 @@ pub struct PostLists<'a> {
 	authorized: bool,
 
 	title: String,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	replies_policy: Option<RepliesPolicy>,
+
+	#[serde(skip_serializing_if = "Option::is_none")]
+	exclusive: Option<bool>,
+}
+
+impl<'a> PostLists<'a> {
+	/// Set which replies should be shown in this list. Defaults to `RepliesPolicy::List`.
+	pub fn replies_policy(mut self, replies_policy: RepliesPolicy) -> Self {
+		self.replies_policy = Some(replies_policy);
+		self
+	}
+
+	/// Set whether members of this list are removed from the home timeline.
+	pub fn exclusive(mut self, exclusive: bool) -> Self {
+		self.exclusive = Some(exclusive);
+		self
+	}
 }
 
 impl<'a> Method<'a, List> for PostLists<'a> {}
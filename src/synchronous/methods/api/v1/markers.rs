@@ -5,7 +5,11 @@ use crate::{
 	Error,
 	Method,
 	Result,
-	entities::Markers,
+	entities::{
+		Markers,
+		NotificationId,
+		StatusId,
+	},
 };
 
 /// Get a request to get markers that represents the position you read on the timeline.
@@ -108,20 +112,26 @@ pub struct PostMarkers<'a> {
 	#[mastors(authorization)]
 	authorized: bool,
 
-	home: Option<Vec<String>>,
-	notifications: Option<Vec<String>>,
+	home: Option<MarkerParams<StatusId>>,
+	notifications: Option<MarkerParams<NotificationId>>,
+}
+
+// Parameters of each timeline are nested as `home[last_read_id]`, specified by app/controllers/api/v1/markers_controller.rb.
+#[derive(Debug, Clone, Serialize)]
+struct MarkerParams<T> {
+	last_read_id: T,
 }
 
 impl<'a> PostMarkers<'a> {
 	/// Set an `id` of status you have read.
-	pub fn home(mut self, id: impl Into<String>) -> Self {
-		self.home = Some(vec![id.into()]);
+	pub fn home(mut self, id: impl Into<StatusId>) -> Self {
+		self.home = Some(MarkerParams { last_read_id: id.into() });
 		self
 	}
 
 	/// Set an `id` of notification you have checked.
-	pub fn notifications(mut self, id: impl Into<String>) -> Self {
-		self.notifications = Some(vec![id.into()]);
+	pub fn notifications(mut self, id: impl Into<NotificationId>) -> Self {
+		self.notifications = Some(MarkerParams { last_read_id: id.into() });
 		self
 	}
 
@@ -2,11 +2,15 @@
 //! 
 //! If any API method had breaking changes, v2 method will be created and v1 method will be maintained for backward compatibility.
 pub mod accounts;
+pub mod blocks;
+pub mod bookmarks;
 pub mod custom_emojis;
+pub mod domain_blocks;
 pub mod instance;
 pub mod lists;
 pub mod markers;
 pub mod media;
+pub mod mutes;
 pub mod notifications;
 pub mod polls;
 pub mod scheduled_statuses;
@@ -3,11 +3,14 @@ use serde::Serialize;
 use crate::{
 	Connection,
 	Method,
-	entities::Notification,
+	entities::{
+		Notification,
+		NotificationId,
+	},
 };
 
 /// Get a request to get notification specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetNotification {
+pub fn get(conn: &Connection, id: impl Into<NotificationId>) -> GetNotification {
 	GetNotification {
 		conn,
 		id: id.into(),
@@ -25,7 +28,7 @@ pub struct GetNotification<'a> {
 
 	#[serde(skip_serializing)]
 	#[mastors(path_param)]
-	id: String,
+	id: NotificationId,
 
 	#[serde(skip_serializing)]
 	#[mastors(authorization)]
@@ -40,11 +43,14 @@ pub mod dismiss {
 	use crate::{
 		Connection,
 		Method,
-		entities::Nothing,
+		entities::{
+			Nothing,
+			NotificationId,
+		},
 	};
 
 	/// Get a request to dismiss a notification specified by `id`.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostDismissNotification {
+	pub fn post(conn: &Connection, id: impl Into<NotificationId>) -> PostDismissNotification {
 		PostDismissNotification {
 			conn,
 			id: id.into(),
@@ -62,7 +68,7 @@ pub mod dismiss {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: NotificationId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
@@ -8,7 +8,7 @@ use crate::{
 	Connection,
 	Method,
 	Result,
-	entities::{ Notifications, NotificationType },
+	entities::{ AccountId, Notifications, NotificationId, NotificationType },
 	private::MethodInternalWithoutRespHeader,
 };
 
@@ -42,17 +42,17 @@ pub struct GetNotifications<'a> {
 	#[mastors(authorization)]
 	authorized: bool,
 
-	max_id: Option<String>,
-	since_id: Option<String>,
-	min_id: Option<String>,
+	max_id: Option<NotificationId>,
+	since_id: Option<NotificationId>,
+	min_id: Option<NotificationId>,
 	limit: Option<usize>,
 	exclude_types: Option<Vec<String>>,
-	account_id: Option<String>,
+	account_id: Option<AccountId>,
 }
 
 impl<'a> GetNotifications<'a> {
     /// Set to get notifications that have ID less than `max_id`.
-	pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+	pub fn max_id(mut self, max_id: impl Into<NotificationId>) -> Self {
 		self.max_id = Some(max_id.into());
 		self
 	}
@@ -80,7 +80,7 @@ impl<'a> GetNotifications<'a> {
     /// ：
     /// ：
     /// ```
-	pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+	pub fn since_id(mut self, since_id: impl Into<NotificationId>) -> Self {
 		self.since_id = Some(since_id.into());
 		self
 	}
@@ -108,7 +108,7 @@ impl<'a> GetNotifications<'a> {
     /// ：
     /// ：
     /// ```
-	pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+	pub fn min_id(mut self, min_id: impl Into<NotificationId>) -> Self {
 		self.min_id = Some(min_id.into());
 		self
 	}
@@ -139,7 +139,7 @@ impl<'a> GetNotifications<'a> {
 	}
 
 	/// Set an account ID that is origin of the notification.
-	pub fn account_id(mut self, account_id: impl Into<String>) -> Self {
+	pub fn account_id(mut self, account_id: impl Into<AccountId>) -> Self {
 		self.account_id = Some(account_id.into());
 		self
 	}
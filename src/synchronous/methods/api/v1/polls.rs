@@ -6,11 +6,14 @@ pub mod id {
     use crate:: {
         Connection,
         Method,
-        entities::Poll,
+        entities::{
+            Poll,
+            PollId,
+        },
     };
 
     /// Get a request to get a poll specified by ID.
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetPolls {
+    pub fn get(conn: &Connection, id: impl Into<PollId>) -> GetPolls {
         GetPolls {
             conn,
             id: id.into(),
@@ -28,7 +31,7 @@ pub mod id {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: PollId,
 
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -62,14 +65,17 @@ pub mod id {
             Error,
             Method,
             Result,
-            entities::Poll,
+            entities::{
+                Poll,
+                PollId,
+            },
             private::MethodInternalWithoutRespHeader,
         };
         
         /// Get a request to vote to the poll.
         pub fn post(
             conn: &Connection,
-            id: impl Into<String>,
+            id: impl Into<PollId>,
             choices: impl AsRef<[usize]>,
         ) -> PostPolls {
         
@@ -96,7 +102,7 @@ pub mod id {
         
             #[serde(skip_serializing)]
             #[mastors(path_param)]
-            id: String,
+            id: PollId,
         
             #[serde(skip_serializing)]
             #[mastors(authorization)]
@@ -3,7 +3,10 @@ use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::ScheduledStatuses,
+    entities::{
+        ScheduledStatusId,
+        ScheduledStatuses,
+    },
 };
 
 /// Get a request to get scheduled statuses that are created by the authenticated user.
@@ -31,9 +34,9 @@ pub struct GetScheduledStatuses<'a> {
     auth: bool,
 
     limit: Option<usize>,
-    max_id: Option<String>,
-    since_id: Option<String>,
-    min_id: Option<String>,
+    max_id: Option<ScheduledStatusId>,
+    since_id: Option<ScheduledStatusId>,
+    min_id: Option<ScheduledStatusId>,
 }
 
 impl<'a> GetScheduledStatuses<'a> {
@@ -44,19 +47,19 @@ impl<'a> GetScheduledStatuses<'a> {
     }
 
     /// Set to return scheduled statuses older than ID.
-    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+    pub fn max_id(mut self, max_id: impl Into<ScheduledStatusId>) -> Self {
         self.max_id = Some(max_id.into());
         self
     }
 
     /// Set to return scheduled statuses newer than ID.
-    pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+    pub fn since_id(mut self, since_id: impl Into<ScheduledStatusId>) -> Self {
         self.since_id = Some(since_id.into());
         self
     }
 
     /// Set to return scheduled statuses immediately newer than ID.
-    pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+    pub fn min_id(mut self, min_id: impl Into<ScheduledStatusId>) -> Self {
         self.min_id = Some(min_id.into());
         self
     }
@@ -75,11 +78,12 @@ pub mod id {
         entities::{
             DeletedScheduledStatus,
             ScheduledStatus,
+            ScheduledStatusId,
         },
     };
 
     /// Get a scheduled status specified by `id`.
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetScheduledStatuses {
+    pub fn get(conn: &Connection, id: impl Into<ScheduledStatusId>) -> GetScheduledStatuses {
         GetScheduledStatuses {
             conn,
             auth: true,
@@ -88,7 +92,7 @@ pub mod id {
     }
 
     /// Update a scheduled date and time of scheduled status specified by `id`.
-    pub fn put(conn: &Connection, id: impl Into<String>) -> PutScheduledStatuses {
+    pub fn put(conn: &Connection, id: impl Into<ScheduledStatusId>) -> PutScheduledStatuses {
         PutScheduledStatuses {
             conn,
             auth: true,
@@ -98,7 +102,7 @@ pub mod id {
     }
 
     /// Delete a scheduled status specified by `id`.
-    pub fn delete(conn: &Connection, id: impl Into<String>) -> DeleteScheduledStatuses {
+    pub fn delete(conn: &Connection, id: impl Into<ScheduledStatusId>) -> DeleteScheduledStatuses {
         DeleteScheduledStatuses {
             conn,
             auth: true,
@@ -120,7 +124,7 @@ pub mod id {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: ScheduledStatusId,
     }
 
     impl<'a> Method<'a, ScheduledStatus> for GetScheduledStatuses<'a> {}
@@ -139,7 +143,7 @@ pub mod id {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: ScheduledStatusId,
 
         scheduled_at: Option<DateTime<Utc>>,
     }
@@ -177,7 +181,7 @@ pub mod id {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: ScheduledStatusId,
     }
 
     impl<'a> Method<'a, DeletedScheduledStatus> for DeleteScheduledStatuses<'a> {}
@@ -7,11 +7,14 @@ pub mod bookmark {
     use crate::{
     	Connection,
     	Method,
-    	entities::Status,
+    	entities::{
+    	    Status,
+    	    StatusId,
+    	},
     };
     
     /// Get a request to bookmark a status specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostBookmark {
+    pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostBookmark {
     	PostBookmark {
     		conn,
     		id: id.into(),
@@ -29,7 +32,7 @@ pub mod bookmark {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: StatusId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
@@ -45,11 +48,14 @@ pub mod unbookmark {
     use crate::{
     	Connection,
     	Method,
-    	entities::Status,
+    	entities::{
+    	    Status,
+    	    StatusId,
+    	},
     };
     
     /// Get a request to unbookmark a status specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnbookmark {
+    pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostUnbookmark {
     	PostUnbookmark {
     		conn,
     		id: id.into(),
@@ -67,7 +73,7 @@ pub mod unbookmark {
     
     	#[serde(skip_serializing)]
     	#[mastors(path_param)]
-    	id: String,
+    	id: StatusId,
     
     	#[serde(skip_serializing)]
     	#[mastors(authorization)]
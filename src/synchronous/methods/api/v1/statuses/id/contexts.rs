@@ -6,11 +6,14 @@ pub mod context {
     use crate::{
         Connection,
         Method,
-        entities::Context,
+        entities::{
+            Context,
+            StatusId,
+        },
     };
 
     /// Get a request to get statuses that are ancestors or descendants of status specified by id. 
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetContext {
+    pub fn get(conn: &Connection, id: impl Into<StatusId>) -> GetContext {
         GetContext {
             conn,
             id: id.into(),
@@ -28,7 +31,7 @@ pub mod context {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: StatusId,
 
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -51,7 +54,10 @@ pub mod context {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Method;
+    use crate::{
+        Method,
+        entities::StatusId,
+    };
 
     #[test]
     fn test_get_context() {
@@ -80,14 +86,14 @@ mod tests {
             .unwrap();
 
         let contexts = context::get(&conn, third.id()).send().unwrap();
-        let ancestors = contexts.ancestors().iter().map(|status| status.id().to_owned()).collect::<Vec<String>>();
-        let descendants = contexts.descendants().iter().map(|status| status.id().to_owned()).collect::<Vec<String>>();
+        let ancestors = contexts.ancestors().iter().map(|status| status.id().clone()).collect::<Vec<StatusId>>();
+        let descendants = contexts.descendants().iter().map(|status| status.id().clone()).collect::<Vec<StatusId>>();
 
         assert_eq!(ancestors.len(), 2);
-        assert_eq!(vec![first.id(), second.id()], ancestors);
+        assert_eq!(vec![first.id().clone(), second.id().clone()], ancestors);
 
         assert_eq!(descendants.len(), 1);
-        assert_eq!(vec![fourth.id()], descendants);
+        assert_eq!(vec![fourth.id().clone()], descendants);
 
         statuses::id::delete(&conn, first.id()).send().unwrap();
         statuses::id::delete(&conn, second.id()).send().unwrap();
@@ -6,11 +6,14 @@ pub mod favourited_by {
     use crate::{
         Connection,
         Method,
-        entities::Accounts,
+        entities::{
+            Accounts,
+            StatusId,
+        },
     };
 
     /// Get a request to get accounts who favourited status specified by `id`.
-    pub fn get(conn: &Connection, id: impl Into<String>) -> GetFavouritedBy{
+    pub fn get(conn: &Connection, id: impl Into<StatusId>) -> GetFavouritedBy{
         GetFavouritedBy{
             conn,
             id: id.into(),
@@ -28,7 +31,7 @@ pub mod favourited_by {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: StatusId,
 
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -44,11 +47,14 @@ pub mod favourite {
     use crate::{
         Connection,
         Method,
-        entities::Status,
+        entities::{
+            Status,
+            StatusId,
+        },
     };
 
     /// Get a request to set favourite to a status specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostFavourite {
+    pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostFavourite {
         PostFavourite {
             conn,
             id: id.into(),
@@ -66,7 +72,7 @@ pub mod favourite {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: StatusId,
 
         #[serde(skip_serializing)]
         #[mastors(authorization)]
@@ -82,11 +88,14 @@ pub mod unfavourite {
     use crate::{
         Connection,
         Method,
-        entities::Status,
+        entities::{
+            Status,
+            StatusId,
+        },
     };
 
     /// Get a request to unset favourite of a status specified by `id`.
-    pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnfavourite {
+    pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostUnfavourite {
         PostUnfavourite {
             conn,
             id: id.into(),
@@ -104,7 +113,7 @@ pub mod unfavourite {
 
         #[serde(skip_serializing)]
         #[mastors(path_param)]
-        id: String,
+        id: StatusId,
 
         #[serde(skip_serializing)]
         #[mastors(authorization)]
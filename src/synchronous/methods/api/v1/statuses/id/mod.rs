@@ -35,11 +35,14 @@ use serde::Serialize;
 use crate::{
     Connection,
     Method,
-    entities::Status,
+    entities::{
+        Status,
+        StatusId,
+    },
 };
 
 /// Create a request to get a status specified by `id`.
-pub fn get(conn: &Connection, id: impl Into<String>) -> GetStatuses {
+pub fn get(conn: &Connection, id: impl Into<StatusId>) -> GetStatuses {
     GetStatuses {
         conn,
         id: id.into(),
@@ -48,7 +51,7 @@ pub fn get(conn: &Connection, id: impl Into<String>) -> GetStatuses {
 }
 
 /// Create a request to delete the status specified by `id`.
-pub fn delete(conn: &Connection, id: impl Into<String>) -> DeleteStatuses {
+pub fn delete(conn: &Connection, id: impl Into<StatusId>) -> DeleteStatuses {
     DeleteStatuses {
         conn,
         auth: true,
@@ -70,7 +73,7 @@ pub struct GetStatuses<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: StatusId,
 }
 
 impl<'a> GetStatuses<'a> {
@@ -103,7 +106,7 @@ pub struct DeleteStatuses<'a> {
 
     #[serde(skip_serializing)]
     #[mastors(path_param)]
-    id: String,
+    id: StatusId,
 }
 
 impl<'a> Method<'a, Status> for DeleteStatuses<'a> {}
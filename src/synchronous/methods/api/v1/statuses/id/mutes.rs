@@ -6,11 +6,14 @@ pub mod mute {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 	};
 
 	/// Get a request to mute notification from the conversation that contains status specified by `id`.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostMute {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostMute {
 		PostMute {
 			conn,
 			id: id.into(),
@@ -28,7 +31,7 @@ pub mod mute {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
@@ -44,11 +47,14 @@ pub mod unmute {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 	};
 
 	/// Get a request to unmute notification from the conversation that contains status specified by `id`.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnmute {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostUnmute {
 		PostUnmute {
 			conn,
 			id: id.into(),
@@ -66,7 +72,7 @@ pub mod unmute {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
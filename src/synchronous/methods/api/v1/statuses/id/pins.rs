@@ -6,11 +6,14 @@ pub mod pin {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 	};
 
 	/// Get a request to pin the status specified by `id` to your public profile.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostPin {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostPin {
 		PostPin {
 			conn,
 			id: id.into(),
@@ -28,7 +31,7 @@ pub mod pin {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
@@ -44,11 +47,14 @@ pub mod unpin {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 	};
 
 	/// Get a request to uppin the status specified by `id` from your public profile.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnpin {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostUnpin {
 		PostUnpin {
 			conn,
 			id: id.into(),
@@ -66,7 +72,7 @@ pub mod unpin {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
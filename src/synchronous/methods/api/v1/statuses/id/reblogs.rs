@@ -6,11 +6,14 @@ pub mod reblogged_by {
 	use crate::{
 		Connection,
 		Method,
-		entities::Accounts,
+		entities::{
+			Accounts,
+			StatusId,
+		},
 	};
 
 	/// Get a request to get accounts that reblogged a status specified by `id`.
-	pub fn get(conn: &Connection, id: impl Into<String>) -> GetRebloggedBy {
+	pub fn get(conn: &Connection, id: impl Into<StatusId>) -> GetRebloggedBy {
 		GetRebloggedBy {
 			conn,
 			id: id.into(),
@@ -28,7 +31,7 @@ pub mod reblogged_by {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
@@ -44,12 +47,15 @@ pub mod reblog {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 		entities::Visibility,
 	};
 
 	/// Get a request to reblog a status specified by `id`.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostReblog {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostReblog {
 		PostReblog {
 			conn,
 			id: id.into(),
@@ -68,7 +74,7 @@ pub mod reblog {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
@@ -109,11 +115,14 @@ pub mod unreblog {
 	use crate::{
 		Connection,
 		Method,
-		entities::Status,
+		entities::{
+			Status,
+			StatusId,
+		},
 	};
 
 	/// Get a request to unreblog the status specified by `id`.
-	pub fn post(conn: &Connection, id: impl Into<String>) -> PostUnreblog {
+	pub fn post(conn: &Connection, id: impl Into<StatusId>) -> PostUnreblog {
 		PostUnreblog {
 			conn,
 			id: id.into(),
@@ -131,7 +140,7 @@ pub mod unreblog {
 
 		#[serde(skip_serializing)]
 		#[mastors(path_param)]
-		id: String,
+		id: StatusId,
 
 		#[serde(skip_serializing)]
 		#[mastors(authorization)]
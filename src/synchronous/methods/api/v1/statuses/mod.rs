@@ -10,9 +10,12 @@ use crate::{
     Error,
     Method,
     Result,
+    status_length,
     entities::{
+        MediaId,
         ScheduledStatus,
         Status,
+        StatusId,
         Visibility,
     },
 };
@@ -47,6 +50,7 @@ impl<'a> PostStatusesBase<'a> {
             auth: true,
             status: Some(status.as_ref().trim().to_string()),
             in_reply_to_id: None,
+            sensitive: None,
             spoiler_text: None,
             visibility: None,
             language: self.language,
@@ -57,7 +61,7 @@ impl<'a> PostStatusesBase<'a> {
     pub fn media_ids<T, U>(self, media_ids: T) -> PostStatusesWithMediaAttachments<'a>
     where
         T: AsRef<[U]>,
-        U: AsRef<str>,
+        U: Clone + Into<MediaId>,
     {
         PostStatusesWithMediaAttachments {
             conn: self.conn,
@@ -67,6 +71,7 @@ impl<'a> PostStatusesBase<'a> {
                 auth: true,
                 status: None,
                 in_reply_to_id: None,
+                sensitive: None,
                 spoiler_text: None,
                 visibility: None,
                 language: self.language,
@@ -75,6 +80,20 @@ impl<'a> PostStatusesBase<'a> {
         }
     }
 
+    // For statuses that have only media attachments but need other parameters such as a spoiler text.
+    pub(crate) fn without_status(self) -> PostStatusesSimple<'a> {
+        PostStatusesSimple {
+            conn: self.conn,
+            auth: true,
+            status: None,
+            in_reply_to_id: None,
+            sensitive: None,
+            spoiler_text: None,
+            visibility: None,
+            language: self.language,
+        }
+    }
+
     pub fn poll<T, U>(self, status: impl AsRef<str>, options: T, expires_in: u64) -> PostStatusesWithPoll<'a>
     where
         T: AsRef<[U]>,
@@ -104,7 +123,8 @@ pub struct PostStatusesSimple<'a> {
     auth: bool,
 
     status: Option<String>,
-    in_reply_to_id: Option<String>,
+    in_reply_to_id: Option<StatusId>,
+    sensitive: Option<bool>,
     spoiler_text: Option<String>,
     visibility: Option<Visibility>,
     language: Option<String>,
@@ -122,15 +142,22 @@ impl<'a> PostStatusesSimple<'a> {
     }
 
     /// Add the status ID that is reply to. 
-    pub fn in_reply_to_id(mut self, in_reply_to_id: impl AsRef<str>) -> Self {
-        let in_reply_to_id = in_reply_to_id.as_ref().trim();
+    pub fn in_reply_to_id(mut self, in_reply_to_id: impl Into<StatusId>) -> Self {
+        let in_reply_to_id = in_reply_to_id.into();
+        let in_reply_to_id = in_reply_to_id.trim();
 
         if !in_reply_to_id.is_empty() {
-            self.in_reply_to_id = Some(in_reply_to_id.to_string());
+            self.in_reply_to_id = Some(in_reply_to_id.into());
         }
         self
     }
 
+    /// Mark media attachments of this status as sensitive.
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = Some(true);
+        self
+    }
+
     /// Add spoiler text to this status.
     pub fn spoiler_text(mut self, spoiler_text: impl AsRef<str>) -> Self {
         let spoiler_text = spoiler_text.as_ref().trim();
@@ -185,7 +212,7 @@ impl<'a> PostStatusesSimple<'a> {
     pub fn media_ids<T, U>(self, media_ids: T) -> PostStatusesWithMediaAttachments<'a>
     where
         T: AsRef<[U]>,
-        U: AsRef<str>,
+        U: Clone + Into<MediaId>,
     {
         let status_max_characters = self.conn.status_max_characters();
         PostStatusesWithMediaAttachments {
@@ -220,6 +247,7 @@ impl<'a> PostStatusesSimple<'a> {
         }
     }
 
+    // Characters are counted in the same way as the server, see `status_length`.
     fn validate(&self) -> Result<()> {
         let mut total_chars: usize = 0;
 
@@ -231,7 +259,7 @@ impl<'a> PostStatusesSimple<'a> {
                 );
             }
 
-            total_chars += status.chars().count();
+            total_chars += status_length::count(status);
         }
 
         // Check language if set
@@ -245,7 +273,7 @@ impl<'a> PostStatusesSimple<'a> {
     
         // Check total number of characters
         if let Some(spoiler_text) = self.spoiler_text.as_ref() {
-            total_chars += spoiler_text.chars().count();
+            total_chars += status_length::count(spoiler_text);
         }
     
         if total_chars > self.conn.status_max_characters() {
@@ -519,7 +547,7 @@ impl<'a> Method<'a, ScheduledStatus> for PostScheduledStatusesWithPoll<'a> {
 /// Wrapper for the media_ids.
 #[derive(Debug, Clone, Serialize)]
 struct MediaIds {
-    media_ids: Vec<String>,
+    media_ids: Vec<MediaId>,
 
     #[serde(skip_serializing)]
     status_max_medias: usize,
@@ -529,14 +557,14 @@ impl MediaIds {
     fn new<T, U>(media_ids: T, status_max_medias: usize) -> Self
     where
         T: AsRef<[U]>,
-        U: AsRef<str>,
+        U: Clone + Into<MediaId>,
     {
         let media_ids = media_ids.as_ref()
             .iter()
-            .map(|u| u.as_ref().trim())
+            .map(|u| u.clone().into())
+            .map(|u: MediaId| MediaId::new(u.trim()))
             .filter(|u| !u.is_empty())
-            .map(|u| u.to_owned())
-            .collect::<Vec<String>>();
+            .collect::<Vec<MediaId>>();
 
         MediaIds {
             media_ids,
@@ -567,7 +595,7 @@ impl MediaIds {
             );
         }
 
-        if self.media_ids.iter().collect::<HashSet<&String>>().len() != self.media_ids.len() {
+        if self.media_ids.iter().collect::<HashSet<&MediaId>>().len() != self.media_ids.len() {
             return Err(
                 Error::DuplicateMediaError
             );
@@ -785,8 +813,8 @@ mod tests {
         let got_media_ids = got
             .media_attachments()
             .iter()
-            .map(|ma| ma.id().to_owned())
-            .collect::<Vec<String>>();
+            .map(|ma| ma.id().clone())
+            .collect::<Vec<MediaId>>();
 
         assert_eq!(posted.id(), got.id());
         assert_eq!(&media_ids, &got_media_ids);
@@ -800,8 +828,8 @@ mod tests {
             media_ids,
             deleted.media_attachments()
                 .iter()
-                .map(|ma| ma.id().to_owned())
-                .collect::<Vec<String>>()
+                .map(|ma| ma.id().clone())
+                .collect::<Vec<MediaId>>()
         );
     }
 
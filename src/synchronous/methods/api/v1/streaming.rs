@@ -1,11 +1,8 @@
 //! This module provides features related to get streaming timelines.
-use reqwest::{
-    header,
-    blocking::Client as ReqwestClient,
-};
+use std::time::Duration;
+use log::trace;
 use crate::{
     Connection,
-    Error,
     Result,
     streaming::*,
     utils,
@@ -16,6 +13,7 @@ pub fn get(conn: &Connection, stream_type: StreamType) -> GetStreaming {
     GetStreaming {
         conn,
         stream_type,
+        stall_timeout: DEFAULT_STALL_TIMEOUT,
     }
 }
 
@@ -23,38 +21,32 @@ pub fn get(conn: &Connection, stream_type: StreamType) -> GetStreaming {
 pub struct GetStreaming<'a> {
     conn: &'a Connection,
     stream_type: StreamType,
+    stall_timeout: Duration,
 }
 
 impl<'a> GetStreaming<'a> {
+    /// Set the duration without any bytes, including heartbeats, until the stream yields `Error::StreamStalled`.
+    ///
+    /// Defaults to [`DEFAULT_STALL_TIMEOUT`](../../../streaming/constant.DEFAULT_STALL_TIMEOUT.html).
+    /// Stalls are checked every 5 seconds, so a shorter duration is not precise.
+    pub fn stall_timeout(mut self, stall_timeout: Duration) -> Self {
+        self.stall_timeout = stall_timeout;
+        self
+    }
+
+    /// Connect to the streaming server advertised by [`Connection::streaming_url()`](../../../struct.Connection.html#method.streaming_url).
     pub fn send(&self) -> Result<SseStream> {
-        let url = self.conn.server_url()
+        let url = self.conn.streaming_url()?
             .join(&self.stream_type.to_string())?;
+        trace!("Connect to the streaming timeline: {}", url);
 
-        let mut headers = header::HeaderMap::new();
-
-        headers.insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(
-            format!(
-                "Bearer {}", self.conn.access_token()
-                ).as_str()
-            )?
-        );
-
-        let custom_client = ReqwestClient::builder()
-            .default_headers(headers)
-            .user_agent(self.conn.user_agent())
-            .build()
-            .map_err(Error::HttpClientError)?;
-          
-        Ok(SseStream::new(url, custom_client))
+        Ok(SseStream::new(url, self.conn.streaming_client().clone(), self.stall_timeout))
     }
 }
 
 /// This module provides features related to check about streaming of the server is alives.
 pub mod health {
     use super::*;
-    use log::trace;
 
     /// Gets whether the server's streaming is alive.
     pub fn get(conn: &Connection) -> GetHealth {
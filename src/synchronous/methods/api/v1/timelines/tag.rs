@@ -1,9 +1,13 @@
 //! This module provides features related to get the hashtag timeline.
+use chrono::{ DateTime, Utc };
 use serde::Serialize;
 use crate::{
 	Connection,
 	Method,
-	entities::Statuses,
+	entities::{
+		Statuses,
+		StatusId,
+	},
 };
 
 /// Get a request to get timeline only statuses contains tag specified by `hashtag`.
@@ -38,9 +42,9 @@ pub struct GetTagTimeline<'a> {
     #[mastors(path_param)]
     hashtag: String,
 
-	max_id: Option<String>,
-	since_id: Option<String>,
-	min_id: Option<String>,
+	max_id: Option<StatusId>,
+	since_id: Option<StatusId>,
+	min_id: Option<StatusId>,
     limit: Option<usize>,
 
     local: Option<bool>,
@@ -56,7 +60,7 @@ impl<'a> GetTagTimeline<'a> {
 	}
 
     /// Set to get statuses that have ID less than `max_id`.
-	pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+	pub fn max_id(mut self, max_id: impl Into<StatusId>) -> Self {
 		self.max_id = Some(max_id.into());
 		self
 	}
@@ -84,7 +88,7 @@ impl<'a> GetTagTimeline<'a> {
     /// ：
     /// ：
     /// ```
-	pub fn since_id(mut self, since_id: impl Into<String>) -> Self {
+	pub fn since_id(mut self, since_id: impl Into<StatusId>) -> Self {
 		self.since_id = Some(since_id.into());
 		self
 	}
@@ -112,11 +116,26 @@ impl<'a> GetTagTimeline<'a> {
     /// ：
     /// ：
     /// ```
-	pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+	pub fn min_id(mut self, min_id: impl Into<StatusId>) -> Self {
 		self.min_id = Some(min_id.into());
 		self
 	}
 
+	/// Set to get statuses posted after `since`.
+	///
+	/// This is a shortcut of [`min_id()`](#method.min_id) with [`StatusId::from_datetime()`](../../../../entities/struct.StatusId.html#method.from_datetime),
+	/// so the oldest statuses after `since` come first.
+	pub fn since(self, since: DateTime<Utc>) -> Self {
+		self.min_id(StatusId::from_datetime(since))
+	}
+
+	/// Set to get statuses posted before `until`.
+	///
+	/// This is a shortcut of [`max_id()`](#method.max_id) with [`StatusId::from_datetime()`](../../../../entities/struct.StatusId.html#method.from_datetime).
+	pub fn until(self, until: DateTime<Utc>) -> Self {
+		self.max_id(StatusId::from_datetime(until))
+	}
+
 	/// Set a limit number of statuses to get.
 	pub fn limit(mut self, limit: usize) -> Self {
 		self.limit = Some(limit);
@@ -1,4 +1,4 @@
-//! This module provides features related to hashtags that are frequently used around the server.
+//! This module provides features related to hashtags, statuses and links that are frequently used around the server.
 use serde::Serialize;
 use crate::{
     Connection,
@@ -7,6 +7,8 @@ use crate::{
 };
 
 /// Get a request to get trending hashtags.
+///
+/// `/api/v1/trends` is an alias of `/api/v1/trends/tags`, use [`tags::get()`](./tags/fn.get.html) to get next pages with `offset`.
 pub fn get(conn: &Connection) -> GetTrends {
     GetTrends {
         conn,
@@ -35,6 +37,147 @@ impl<'a> GetTrends<'a> {
 
 impl<'a> Method<'a, Trends> for GetTrends<'a> {}
 
+/// This module provides features related to hashtags that are frequently used around the server.
+pub mod tags {
+    use serde::Serialize;
+    use crate::{
+        Connection,
+        Method,
+        entities::Trends,
+    };
+
+    /// Get a request to get trending hashtags.
+    pub fn get(conn: &Connection) -> GetTrendsTags<'_> {
+        GetTrendsTags {
+            conn,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// GET request for `/api/v1/trends/tags`.
+    #[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+    #[method_params(GET, Trends, "/api/v1/trends/tags")]
+    pub struct GetTrendsTags<'a> {
+        #[serde(skip_serializing)]
+        #[mastors(connection)]
+        conn: &'a Connection,
+
+        limit: Option<usize>,
+        offset: Option<usize>,
+    }
+
+    impl<'a> GetTrendsTags<'a> {
+        /// Set the limit on the number of hashtags to get. Defaults to 10 and max is 20.
+        pub fn limit(mut self, limit: usize) -> Self {
+            self.limit = Some(limit);
+            self
+        }
+
+        /// Set the number of hashtags to skip to get the next page.
+        pub fn offset(mut self, offset: usize) -> Self {
+            self.offset = Some(offset);
+            self
+        }
+    }
+
+    impl<'a> Method<'a, Trends> for GetTrendsTags<'a> {}
+}
+
+/// This module provides features related to statuses that are frequently interacted around the server.
+pub mod statuses {
+    use serde::Serialize;
+    use crate::{
+        Connection,
+        Method,
+        entities::Statuses,
+    };
+
+    /// Get a request to get trending statuses.
+    pub fn get(conn: &Connection) -> GetTrendsStatuses<'_> {
+        GetTrendsStatuses {
+            conn,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// GET request for `/api/v1/trends/statuses`.
+    #[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+    #[method_params(GET, Statuses, "/api/v1/trends/statuses")]
+    pub struct GetTrendsStatuses<'a> {
+        #[serde(skip_serializing)]
+        #[mastors(connection)]
+        conn: &'a Connection,
+
+        limit: Option<usize>,
+        offset: Option<usize>,
+    }
+
+    impl<'a> GetTrendsStatuses<'a> {
+        /// Set the limit on the number of statuses to get. Defaults to 20 and max is 40.
+        pub fn limit(mut self, limit: usize) -> Self {
+            self.limit = Some(limit);
+            self
+        }
+
+        /// Set the number of statuses to skip to get the next page.
+        pub fn offset(mut self, offset: usize) -> Self {
+            self.offset = Some(offset);
+            self
+        }
+    }
+
+    impl<'a> Method<'a, Statuses> for GetTrendsStatuses<'a> {}
+}
+
+/// This module provides features related to links that are frequently shared around the server.
+pub mod links {
+    use serde::Serialize;
+    use crate::{
+        Connection,
+        Method,
+        entities::TrendsLinks,
+    };
+
+    /// Get a request to get trending links.
+    pub fn get(conn: &Connection) -> GetTrendsLinks<'_> {
+        GetTrendsLinks {
+            conn,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// GET request for `/api/v1/trends/links`.
+    #[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+    #[method_params(GET, TrendsLinks, "/api/v1/trends/links")]
+    pub struct GetTrendsLinks<'a> {
+        #[serde(skip_serializing)]
+        #[mastors(connection)]
+        conn: &'a Connection,
+
+        limit: Option<usize>,
+        offset: Option<usize>,
+    }
+
+    impl<'a> GetTrendsLinks<'a> {
+        /// Set the limit on the number of links to get. Defaults to 10 and max is 20.
+        pub fn limit(mut self, limit: usize) -> Self {
+            self.limit = Some(limit);
+            self
+        }
+
+        /// Set the number of links to skip to get the next page.
+        pub fn offset(mut self, offset: usize) -> Self {
+            self.offset = Some(offset);
+            self
+        }
+    }
+
+    impl<'a> Method<'a, TrendsLinks> for GetTrendsLinks<'a> {}
+}
+
 #[cfg(test)]
 mod tests {
     // fmm...Local development servers usually don't have trending hashtags.
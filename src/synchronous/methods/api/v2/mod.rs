@@ -0,0 +1,2 @@
+//! This module provides ver.2 API methods.
+pub mod search;
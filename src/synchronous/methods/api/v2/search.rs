@@ -0,0 +1,123 @@
+//! This module provides features related to search accounts, statuses and hashtags.
+use serde::Serialize;
+use crate::{
+    Connection,
+    Method,
+    entities::SearchResults,
+};
+
+/// Get a request to search accounts, statuses and hashtags with keyword `q`.
+pub fn get(conn: &Connection, q: impl Into<String>) -> GetSearch<'_> {
+    GetSearch {
+        conn,
+        authorized: true,
+        q: q.into(),
+        r#type: None,
+        resolve: None,
+        following: None,
+        account_id: None,
+        exclude_unreviewed: None,
+        max_id: None,
+        min_id: None,
+        limit: None,
+        offset: None,
+    }
+}
+
+/// GET request for `/api/v2/search`.
+#[derive(Debug, Clone, Serialize, mastors_derive::Method)]
+#[method_params(GET, SearchResults, "/api/v2/search")]
+pub struct GetSearch<'a> {
+    #[serde(skip_serializing)]
+    #[mastors(connection)]
+    conn: &'a Connection,
+
+    #[serde(skip_serializing)]
+    #[mastors(authorization)]
+    authorized: bool,
+
+    // Required params
+    q: String,
+
+    // Optional params
+    r#type: Option<&'static str>,
+    resolve: Option<bool>,
+    following: Option<bool>,
+    account_id: Option<String>,
+    exclude_unreviewed: Option<bool>,
+    max_id: Option<String>,
+    min_id: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+impl<'a> GetSearch<'a> {
+    /// Set to search accounts only.
+    pub fn accounts(mut self) -> Self {
+        self.r#type = Some("accounts");
+        self
+    }
+
+    /// Set to search statuses only.
+    pub fn statuses(mut self) -> Self {
+        self.r#type = Some("statuses");
+        self
+    }
+
+    /// Set to search hashtags only.
+    pub fn hashtags(mut self) -> Self {
+        self.r#type = Some("hashtags");
+        self
+    }
+
+    /// Set to attempt WebFinger lookup or fetching the status by URL.
+    /// Use this when search keyword is an exact address or URL.
+    pub fn resolve(mut self) -> Self {
+        self.resolve = Some(true);
+        self
+    }
+
+    /// Set search target to accounts you are following only.
+    pub fn following(mut self) -> Self {
+        self.following = Some(true);
+        self
+    }
+
+    /// Set to search statuses posted by the account specified by ID only.
+    pub fn account_id(mut self, account_id: impl Into<String>) -> Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Set to exclude unreviewed hashtags from results.
+    pub fn exclude_unreviewed(mut self) -> Self {
+        self.exclude_unreviewed = Some(true);
+        self
+    }
+
+    /// Set the max ID of the statuses to get.
+    pub fn max_id(mut self, max_id: impl Into<String>) -> Self {
+        self.max_id = Some(max_id.into());
+        self
+    }
+
+    /// Set the min ID of the statuses to get.
+    pub fn min_id(mut self, min_id: impl Into<String>) -> Self {
+        self.min_id = Some(min_id.into());
+        self
+    }
+
+    /// Set a limit on the number of results of each type. Defaults to 20 and max is 40.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the number of results to skip to get the next page.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl<'a> Method<'a, SearchResults> for GetSearch<'a> {}
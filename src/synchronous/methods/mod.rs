@@ -73,6 +73,9 @@ pub(crate) mod private {
 
         fn get(&'a self) -> Result<E> {
             let req = build_request(self, reqwest::Method::GET)?.query(&self).build()?;
+            if let Some(cache) = self.connection().cache() {
+                return cache.send(self.connection(), Self::ENDPOINT, req)?.json::<E>();
+            }
             trace!("Send a {} request to {}", req.method(), req.url());
 
             let res = self.connection().client().execute(req)?;
@@ -123,6 +126,10 @@ pub(crate) mod private {
 
         fn get(&'a self) -> Result<(PageNavigation, E)> {
             let req = build_request(self, reqwest::Method::GET)?.query(&self).build()?;
+            if let Some(cache) = self.connection().cache() {
+                let res = cache.send(self.connection(), Self::ENDPOINT, req)?;
+                return Ok((PageNavigation::new(res.header(self.response_header_name())), res.json::<E>()?));
+            }
             trace!("Send a {} request to {}", req.method(), req.url());
 
             let res = self.connection().client().execute(req)?;
@@ -1,3 +1,5 @@
+pub mod bot;
+pub mod helpers;
 pub mod methods;
 pub mod streaming;
 
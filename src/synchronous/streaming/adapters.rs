@@ -0,0 +1,540 @@
+//! This module provides adapters that filter and transform events of the streaming timeline.
+//!
+//! Every adapter implements [`StreamingTimeline`](../trait.StreamingTimeline.html) too,
+//! so adapters can be chained and the result can be attached to listeners.
+use std::collections::{ HashSet, VecDeque };
+use std::sync::{ Arc, Mutex, mpsc::{ self, RecvTimeoutError } };
+use std::thread;
+use std::time::{ Duration, Instant };
+use log::trace;
+use crate::{
+    Result,
+    entities::{
+        Status,
+        Visibility,
+    },
+};
+use super::{
+    EventType,
+    StreamingTimeline,
+};
+
+/// The default number of URIs remembered by [`SeenUris`](./struct.SeenUris.html).
+pub const DEFAULT_SEEN_URIS_CAPACITY: usize = 10_000;
+
+/// This trait represents a condition that statuses of the streaming timeline must satisfy.
+///
+/// This trait is implemented for closures `FnMut(&Status) -> bool`.
+pub trait StatusPredicate {
+    /// Get whether `status` satisfies this condition.
+    fn test(&mut self, status: &Status) -> bool;
+}
+
+impl<F> StatusPredicate for F
+where
+    F: FnMut(&Status) -> bool,
+{
+    fn test(&mut self, status: &Status) -> bool {
+        self(status)
+    }
+}
+
+/// Represents conditions that are used by the filter methods of [`StreamingTimeline`](../trait.StreamingTimeline.html).
+///
+/// Language, hashtag and media conditions are tested against the reblogged status if the status is a reblog.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum StatusCondition {
+    /// The status is written in the language of ISO 639-1 code. Statuses whose language is unknown never match.
+    Language(String),
+
+    /// The status has the visibility.
+    Visibility(Visibility),
+
+    /// The status has the hashtag. The name is compared case-insensitively without the leading `#`.
+    Hashtag(String),
+
+    /// The status is posted or reblogged by the account of the ID.
+    Account(String),
+
+    /// Whether the status has media attachments.
+    Media(bool),
+
+    /// Whether the status is a reblog.
+    Reblog(bool),
+}
+
+impl StatusPredicate for StatusCondition {
+    fn test(&mut self, status: &Status) -> bool {
+        let content = status.reblog().unwrap_or(status);
+
+        match self {
+            StatusCondition::Language(lang) => content.language()
+                .map(|l| l.eq_ignore_ascii_case(lang))
+                .unwrap_or(false),
+            StatusCondition::Visibility(visibility) => status.visibility() == *visibility,
+            StatusCondition::Hashtag(tag) => content.tags()
+                .iter()
+                .any(|t| t.name().eq_ignore_ascii_case(tag.trim_start_matches('#'))),
+            StatusCondition::Account(id) => status.account().id() == id,
+            StatusCondition::Media(media) => content.media_attachments().is_empty() != *media,
+            StatusCondition::Reblog(reblog) => status.reblog().is_some() == *reblog,
+        }
+    }
+}
+
+/// A streaming timeline that skips statuses not satisfying the predicate.
+///
+/// Only `Update` and `StatusUpdate` events are tested, other events are passed through.
+/// This is created by [`StreamingTimeline::filter_status()`](../trait.StreamingTimeline.html#method.filter_status) and other filter methods.
+#[derive(Debug, Clone)]
+pub struct FilterStatus<T, P> {
+    timeline: T,
+    predicate: P,
+}
+
+impl<T, P> FilterStatus<T, P> {
+    pub(crate) fn new(timeline: T, predicate: P) -> Self {
+        FilterStatus {
+            timeline,
+            predicate,
+        }
+    }
+}
+
+impl<T, P> Iterator for FilterStatus<T, P>
+where
+    T: StreamingTimeline,
+    P: StatusPredicate,
+{
+    type Item = Result<EventType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.timeline.next()? {
+                Ok(EventType::Update(status)) | Ok(EventType::StatusUpdate(status))
+                    if !self.predicate.test(&status) =>
+                {
+                    trace!("Skip status {} by filter", status.id());
+                },
+                event => return Some(event),
+            }
+        }
+    }
+}
+
+impl<T: StreamingTimeline, P: StatusPredicate> StreamingTimeline for FilterStatus<T, P> {}
+
+/// A streaming timeline that replaces reblogs with the reblogged statuses.
+///
+/// This is created by [`StreamingTimeline::unwrap_reblogs()`](../trait.StreamingTimeline.html#method.unwrap_reblogs).
+#[derive(Debug, Clone)]
+pub struct UnwrapReblogs<T> {
+    timeline: T,
+}
+
+impl<T> UnwrapReblogs<T> {
+    pub(crate) fn new(timeline: T) -> Self {
+        UnwrapReblogs {
+            timeline,
+        }
+    }
+}
+
+impl<T: StreamingTimeline> Iterator for UnwrapReblogs<T> {
+    type Item = Result<EventType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.timeline.next()? {
+            Ok(EventType::Update(status)) => match status.reblog() {
+                Some(reblog) => Ok(EventType::Update(Box::new(reblog.clone()))),
+                None => Ok(EventType::Update(status)),
+            },
+            event => event,
+        })
+    }
+}
+
+impl<T: StreamingTimeline> StreamingTimeline for UnwrapReblogs<T> {}
+
+/// URIs of statuses that have already been seen, shared between streaming timelines.
+///
+/// Clones of `SeenUris` share the same URIs, so pass a clone to each streaming timeline to de-duplicate statuses across them.
+/// The oldest URI is forgotten when the number of URIs exceeds the capacity.
+#[derive(Debug, Clone)]
+pub struct SeenUris {
+    inner: Arc<Mutex<SeenUrisInner>>,
+}
+
+#[derive(Debug)]
+struct SeenUrisInner {
+    capacity: usize,
+    order: VecDeque<String>,
+    uris: HashSet<String>,
+}
+
+impl SeenUris {
+    /// Create a new `SeenUris` that remembers [`DEFAULT_SEEN_URIS_CAPACITY`](./constant.DEFAULT_SEEN_URIS_CAPACITY.html) URIs.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_SEEN_URIS_CAPACITY)
+    }
+
+    /// Create a new `SeenUris` that remembers `capacity` URIs, `0` is treated as `1`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SeenUris {
+            inner: Arc::new(Mutex::new(SeenUrisInner {
+                capacity: capacity.max(1),
+                order: VecDeque::new(),
+                uris: HashSet::new(),
+            })),
+        }
+    }
+
+    /// Remember `uri` and get whether it is seen for the first time.
+    pub fn insert(&self, uri: impl Into<String>) -> bool {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let uri = uri.into();
+
+        if !inner.uris.insert(uri.clone()) {
+            return false;
+        }
+        inner.order.push_back(uri);
+
+        while inner.order.len() > inner.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.uris.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Get whether `uri` has been seen.
+    pub fn contains(&self, uri: &str) -> bool {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).uris.contains(uri)
+    }
+}
+
+impl Default for SeenUris {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A streaming timeline that skips `Update` events of statuses whose `uri` has already been seen.
+///
+/// `StatusUpdate` events are always passed through because edited statuses keep their `uri`.
+/// This is created by [`StreamingTimeline::dedup_by_uri()`](../trait.StreamingTimeline.html#method.dedup_by_uri).
+#[derive(Debug, Clone)]
+pub struct DedupByUri<T> {
+    timeline: T,
+    seen: SeenUris,
+}
+
+impl<T> DedupByUri<T> {
+    pub(crate) fn new(timeline: T, seen: SeenUris) -> Self {
+        DedupByUri {
+            timeline,
+            seen,
+        }
+    }
+}
+
+impl<T: StreamingTimeline> Iterator for DedupByUri<T> {
+    type Item = Result<EventType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.timeline.next()? {
+                Ok(EventType::Update(status)) if !self.seen.insert(status.uri().as_str()) => {
+                    trace!("Skip duplicate status {}", status.uri());
+                },
+                event => return Some(event),
+            }
+        }
+    }
+}
+
+impl<T: StreamingTimeline> StreamingTimeline for DedupByUri<T> {}
+
+/// A streaming timeline that holds `Update` events for a while and skips statuses deleted in the meantime.
+///
+/// The underlying streaming timeline is read on a worker thread, so held statuses are released after the delay even on a quiet timeline.
+/// `Delete` events of held statuses are skipped, and `StatusUpdate` events of held statuses replace them.
+/// Other events are passed through immediately.
+/// When this is dropped, the worker thread stops after the underlying streaming timeline yields the next event.
+/// This is created by [`StreamingTimeline::delay()`](../trait.StreamingTimeline.html#method.delay).
+#[derive(Debug)]
+pub struct Delay<T> {
+    timeline: Option<T>,
+    receiver: Option<mpsc::Receiver<Result<EventType>>>,
+    delay: Duration,
+    buffer: VecDeque<(Instant, Box<Status>)>,
+    finished: bool,
+}
+
+impl<T> Delay<T> {
+    pub(crate) fn new(timeline: T, delay: Duration) -> Self {
+        Delay {
+            timeline: Some(timeline),
+            receiver: None,
+            delay,
+            buffer: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    fn pop_due(&mut self) -> Option<Box<Status>> {
+        match self.buffer.front() {
+            Some((due, _)) if self.finished || *due <= Instant::now() => {
+                self.buffer.pop_front().map(|(_, status)| status)
+            },
+            _ => None,
+        }
+    }
+}
+
+// What the worker thread has delivered while waiting.
+enum Received {
+    Event(Result<EventType>),
+    Due,
+    Ended,
+}
+
+impl<T: StreamingTimeline + Send + 'static> Delay<T> {
+    // Wait for the next event until the first held status is due.
+    fn recv(&mut self) -> Result<Received> {
+        if let Some(timeline) = self.timeline.take() {
+            let (tx, rx) = mpsc::channel();
+            thread::Builder::new()
+                .name("mastors-delay".to_owned())
+                .spawn(move || {
+                    for event in timeline {
+                        if tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                })?;
+            self.receiver = Some(rx);
+        }
+
+        let receiver = self.receiver.as_ref().expect("the worker thread has been spawned");
+        let received = match self.buffer.front() {
+            Some((due, _)) => match receiver.recv_timeout(due.saturating_duration_since(Instant::now())) {
+                Ok(event) => Received::Event(event),
+                Err(RecvTimeoutError::Timeout) => Received::Due,
+                Err(RecvTimeoutError::Disconnected) => Received::Ended,
+            },
+            None => match receiver.recv() {
+                Ok(event) => Received::Event(event),
+                Err(_) => Received::Ended,
+            },
+        };
+        Ok(received)
+    }
+}
+
+impl<T: StreamingTimeline + Send + 'static> Iterator for Delay<T> {
+    type Item = Result<EventType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(status) = self.pop_due() {
+                return Some(Ok(EventType::Update(status)));
+            }
+            if self.finished {
+                return None;
+            }
+
+            let event = match self.recv() {
+                Ok(Received::Event(event)) => event,
+                Ok(Received::Due) => continue,
+                Ok(Received::Ended) => {
+                    self.finished = true;
+                    continue;
+                },
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                },
+            };
+
+            match event {
+                Ok(EventType::Update(status)) => {
+                    self.buffer.push_back((Instant::now() + self.delay, status));
+                },
+                Ok(EventType::StatusUpdate(status)) => {
+                    match self.buffer.iter_mut().find(|(_, held)| held.id() == status.id()) {
+                        Some((_, held)) => *held = status,
+                        None => return Some(Ok(EventType::StatusUpdate(status))),
+                    }
+                },
+                Ok(EventType::Delete(status_id)) => {
+                    match self.buffer.iter().position(|(_, held)| held.id() == &status_id) {
+                        Some(index) => {
+                            trace!("Skip status {} deleted within the delay", status_id);
+                            self.buffer.remove(index);
+                        },
+                        None => return Some(Ok(EventType::Delete(status_id))),
+                    }
+                },
+                event => return Some(event),
+            }
+        }
+    }
+}
+
+impl<T: StreamingTimeline + Send + 'static> StreamingTimeline for Delay<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::status_fixture;
+
+    struct Events(std::vec::IntoIter<Result<EventType>>);
+
+    impl Iterator for Events {
+        type Item = Result<EventType>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl StreamingTimeline for Events {}
+
+    fn events(events: Vec<EventType>) -> Events {
+        Events(events.into_iter().map(Ok).collect::<Vec<_>>().into_iter())
+    }
+
+    fn status_value(id: &str, account_id: &str, lang: &str, tag: &str) -> serde_json::Value {
+        let mut status = status_fixture(id, account_id);
+        status["language"] = lang.into();
+        status["tags"] = serde_json::json!([{ "name": tag, "url": format!("https://example.com/tags/{}", tag) }]);
+        status
+    }
+
+    fn status(id: &str, account_id: &str, lang: &str, tag: &str) -> Box<Status> {
+        Box::new(serde_json::from_value(status_value(id, account_id, lang, tag)).unwrap())
+    }
+
+    fn reblog(id: &str, account_id: &str, reblogged: &str) -> Box<Status> {
+        let mut status = status_fixture(id, account_id);
+        status["language"] = serde_json::Value::Null;
+        status["tags"] = serde_json::json!([]);
+        status["reblog"] = status_value(reblogged, "9", "ja", "rust");
+        Box::new(serde_json::from_value(status).unwrap())
+    }
+
+    fn ids<T: StreamingTimeline>(timeline: T) -> Vec<String> {
+        timeline.map(|event| match event.unwrap() {
+            EventType::Update(status) => status.id().to_string(),
+            EventType::StatusUpdate(status) => format!("edit:{}", status.id()),
+            EventType::Delete(id) => format!("delete:{}", id),
+            other => format!("{:?}", other),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let timeline = || events(vec![
+            EventType::Update(status("1", "10", "en", "Rust")),
+            EventType::Update(status("2", "11", "ja", "mastodon")),
+            EventType::Delete("3".into()),
+            EventType::Update(reblog("4", "10", "5")),
+        ]);
+
+        assert_eq!(ids(timeline().filter_language("en")), vec!["1", "delete:3"]);
+        assert_eq!(ids(timeline().filter_language("ja")), vec!["2", "delete:3", "4"]);
+        assert_eq!(ids(timeline().filter_hashtag("#rust")), vec!["1", "delete:3", "4"]);
+        assert_eq!(ids(timeline().filter_account("10")), vec!["1", "delete:3", "4"]);
+        assert_eq!(ids(timeline().filter_reblog(false)), vec!["1", "2", "delete:3"]);
+        assert_eq!(ids(timeline().filter_media(true)), vec!["delete:3"]);
+        assert_eq!(ids(timeline().filter_visibility(Visibility::Public)), vec!["1", "2", "delete:3", "4"]);
+        assert_eq!(
+            ids(timeline().filter_language("ja").filter_status(|s: &Status| s.id() != "2")),
+            vec!["delete:3", "4"]
+        );
+    }
+
+    #[test]
+    fn test_unwrap_reblogs_and_dedup() {
+        let seen = SeenUris::with_capacity(2);
+        let timeline = events(vec![
+            EventType::Update(status("5", "9", "ja", "rust")),
+            EventType::Update(reblog("4", "10", "5")),
+            EventType::StatusUpdate(status("5", "9", "ja", "rust")),
+            EventType::Update(status("6", "9", "ja", "rust")),
+        ]);
+
+        assert_eq!(ids(timeline.unwrap_reblogs().dedup_by_uri(seen.clone())), vec!["5", "edit:5", "6"]);
+
+        let other = events(vec![
+            EventType::Update(status("6", "9", "ja", "rust")),
+            EventType::Update(status("7", "9", "ja", "rust")),
+            EventType::Update(status("5", "9", "ja", "rust")),
+        ]);
+        // "5" has been forgotten by the capacity.
+        assert_eq!(ids(other.dedup_by_uri(seen)), vec!["7", "5"]);
+    }
+
+    #[test]
+    fn test_delay() {
+        let timeline = events(vec![
+            EventType::Update(status("1", "10", "en", "rust")),
+            EventType::Update(status("2", "10", "en", "rust")),
+            EventType::Delete("1".into()),
+            EventType::StatusUpdate(status("2", "10", "ja", "rust")),
+            EventType::Delete("0".into()),
+            EventType::StatusUpdate(status("0", "10", "en", "rust")),
+        ]);
+
+        let released = timeline.delay(Duration::from_secs(60))
+            .map(|event| event.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(released.len(), 3);
+        assert_eq!(released[0], EventType::Delete("0".into()));
+        assert!(matches!(&released[1], EventType::StatusUpdate(s) if s.id() == "0"));
+        assert!(matches!(&released[2], EventType::Update(s) if s.id() == "2" && s.language() == Some("ja")));
+
+        let timeline = events(vec![
+            EventType::Update(status("1", "10", "en", "rust")),
+            EventType::Delete("0".into()),
+        ]);
+        assert_eq!(ids(timeline.delay(Duration::from_secs(0))), vec!["1", "delete:0"]);
+    }
+
+    #[test]
+    fn test_delay_on_quiet_timeline() {
+        struct Quiet(Option<Box<Status>>);
+
+        impl Iterator for Quiet {
+            type Item = Result<EventType>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match self.0.take() {
+                    Some(status) => Some(Ok(EventType::Update(status))),
+                    None => {
+                        std::thread::sleep(Duration::from_secs(5));
+                        None
+                    },
+                }
+            }
+        }
+
+        impl StreamingTimeline for Quiet {}
+
+        let start = Instant::now();
+        let mut timeline = Quiet(Some(status("1", "10", "en", "rust"))).delay(Duration::from_millis(50));
+        assert!(matches!(timeline.next(), Some(Ok(EventType::Update(s))) if s.id() == "1"));
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_sse_stream_can_be_delayed() {
+        fn assert_send<T: Send + 'static>() {}
+        assert_send::<crate::streaming::SseStream>();
+    }
+}
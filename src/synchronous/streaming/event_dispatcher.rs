@@ -0,0 +1,528 @@
+//! This module provides an event dispatcher that delivers streaming events to multiple listeners.
+//!
+//! ## Note
+//!
+//! This module is experimental.
+use std::any::Any;
+use std::fmt;
+use std::panic::{ self, AssertUnwindSafe };
+use std::result::Result as StdResult;
+use std::error::Error as StdError;
+use std::sync::mpsc;
+use std::thread;
+use log::{ debug, error, trace };
+use super::{
+    EventListener,
+    EventListenerMut,
+    EventType,
+    StreamingTimeline,
+    dispatch_event,
+};
+
+type BoxedError = Box<dyn StdError + Send + Sync>;
+
+/// Represents how [`EventDispatcher`](./struct.EventDispatcher.html) treats errors returned from listeners.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy, Default)]
+pub enum ErrorPolicy {
+    /// Stop the streaming timeline and return the first error. This is the default.
+    #[default]
+    Abort,
+
+    /// Log errors with `log::error!` and continue the streaming timeline.
+    Log,
+
+    /// Collect errors and continue the streaming timeline.
+    ///
+    /// Collected errors can be taken with [`EventDispatcher::take_errors()`](./struct.EventDispatcher.html#method.take_errors).
+    Collect,
+}
+
+/// Represents an error returned from a listener registered to [`EventDispatcher`](./struct.EventDispatcher.html).
+#[derive(Debug)]
+pub struct ListenerError {
+    index: usize,
+    error: BoxedError,
+}
+
+impl ListenerError {
+    fn new(index: usize, error: BoxedError) -> Self {
+        ListenerError {
+            index,
+            error,
+        }
+    }
+
+    /// Get the index of the listener that returned this error, in order of registration.
+    pub fn listener_index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the error returned from the listener.
+    pub fn error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.error.as_ref()
+    }
+
+    /// Get the error returned from the listener as owned.
+    pub fn into_inner(self) -> BoxedError {
+        self.error
+    }
+}
+
+impl fmt::Display for ListenerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Listener #{} returned an error: {}", self.index, self.error)
+    }
+}
+
+impl StdError for ListenerError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// A dispatcher that delivers events of the streaming timeline to multiple listeners.
+///
+/// Each listener is called in order of registration on the calling thread by default.
+/// If [`threaded()`](#method.threaded) is set, each listener runs on its own worker thread and receives events through a channel.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mastors::prelude::*;
+///
+/// struct Counter(usize);
+///
+/// impl EventListenerMut for Counter {
+///     type Error = std::io::Error;
+///
+///     fn update(&mut self, _status: &Status) -> Result<(), Self::Error> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// let conn = Connection::from_file(".env.test")?;
+/// let mut dispatcher = EventDispatcher::new()
+///     .error_policy(ErrorPolicy::Log)
+///     .threaded();
+/// dispatcher.add_listener_mut(Counter(0));
+///
+/// local_timeline(&conn)?.dispatch(&mut dispatcher)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EventDispatcher {
+    listeners: Vec<Box<dyn ErasedListener>>,
+    error_policy: ErrorPolicy,
+    threaded: bool,
+    errors: Vec<ListenerError>,
+}
+
+impl EventDispatcher {
+    /// Create a new `EventDispatcher` that has no listeners.
+    pub fn new() -> Self {
+        EventDispatcher {
+            listeners: Vec::new(),
+            error_policy: ErrorPolicy::default(),
+            threaded: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Set how to treat errors returned from listeners.
+    pub fn error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Set to run each listener on its own worker thread.
+    ///
+    /// With `ErrorPolicy::Abort`, errors are checked each time an event is received,
+    /// so the streaming timeline may stop a few events after the error has occurred.
+    /// A panic of a listener is treated as an error of the listener, and the listener stays registered.
+    pub fn threaded(mut self) -> Self {
+        self.threaded = true;
+        self
+    }
+
+    /// Register an implementation of [`EventListener`](./trait.EventListener.html).
+    pub fn add_listener<L>(&mut self, listener: L) -> &mut Self
+    where
+        L: EventListener + Send + 'static,
+        L::Error: Into<BoxedError>,
+    {
+        self.listeners.push(Box::new(SharedListener(listener)));
+        self
+    }
+
+    /// Register an implementation of [`EventListenerMut`](./trait.EventListenerMut.html).
+    pub fn add_listener_mut<L>(&mut self, listener: L) -> &mut Self
+    where
+        L: EventListenerMut + Send + 'static,
+    {
+        self.listeners.push(Box::new(MutListener(listener)));
+        self
+    }
+
+    /// Get the number of registered listeners.
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    /// Get whether no listener is registered.
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// Get errors collected with `ErrorPolicy::Collect`.
+    pub fn errors(&self) -> &Vec<ListenerError> {
+        &self.errors
+    }
+
+    /// Take errors collected with `ErrorPolicy::Collect`.
+    pub fn take_errors(&mut self) -> Vec<ListenerError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Deliver events of `timeline` to registered listeners until the streaming timeline ends.
+    ///
+    /// An error of the streaming timeline itself always stops the dispatching.
+    pub fn run<T>(&mut self, timeline: &mut T) -> StdResult<(), Box<dyn StdError>>
+    where
+        T: StreamingTimeline + ?Sized,
+    {
+        debug!("Run dispatcher with {} listeners (threaded: {})", self.listeners.len(), self.threaded);
+
+        if self.threaded {
+            self.run_threaded(timeline)
+        } else {
+            self.run_inline(timeline)
+        }
+    }
+
+    fn run_inline<T>(&mut self, timeline: &mut T) -> StdResult<(), Box<dyn StdError>>
+    where
+        T: StreamingTimeline + ?Sized,
+    {
+        for event in timeline {
+            let event = event?;
+
+            for (index, listener) in self.listeners.iter_mut().enumerate() {
+                if let Err(e) = listener.dispatch(&event) {
+                    let e = ListenerError::new(index, e);
+                    match self.error_policy {
+                        ErrorPolicy::Abort => return Err(e.into()),
+                        ErrorPolicy::Log => error!("{}", e),
+                        ErrorPolicy::Collect => self.errors.push(e),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_threaded<T>(&mut self, timeline: &mut T) -> StdResult<(), Box<dyn StdError>>
+    where
+        T: StreamingTimeline + ?Sized,
+    {
+        let error_policy = self.error_policy;
+        let (error_tx, error_rx) = mpsc::channel::<ListenerError>();
+        let mut workers = Vec::with_capacity(self.listeners.len());
+
+        // Spawn all workers before moving listeners, so that no listener is lost if spawning fails.
+        for index in 0..self.listeners.len() {
+            let (listener_tx, listener_rx) = mpsc::channel::<Box<dyn ErasedListener>>();
+            let (event_tx, event_rx) = mpsc::channel::<EventType>();
+            let error_tx = error_tx.clone();
+
+            let spawned = thread::Builder::new()
+                .name(format!("mastors-listener-{}", index))
+                .spawn(move || {
+                    let mut listener = listener_rx.recv().ok()?;
+                    for event in event_rx.iter() {
+                        if let Err(e) = dispatch_caught(listener.as_mut(), &event) {
+                            let e = ListenerError::new(index, e);
+                            match error_policy {
+                                ErrorPolicy::Log => error!("{}", e),
+                                _ => if error_tx.send(e).is_err() {
+                                    break;
+                                },
+                            }
+                        }
+                    }
+                    Some(listener)
+                });
+
+            match spawned {
+                Ok(handle) => workers.push((listener_tx, event_tx, handle)),
+                Err(e) => {
+                    // Workers that have not received a listener finish without doing anything.
+                    drop(workers);
+                    return Err(e.into());
+                },
+            }
+        }
+        drop(error_tx);
+
+        let workers = self.listeners.drain(..)
+            .zip(workers)
+            .map(|(listener, (listener_tx, event_tx, handle))| {
+                // The worker is waiting for the listener, so it is received unless the worker has gone.
+                let _ = listener_tx.send(listener);
+                (event_tx, handle)
+            })
+            .collect::<Vec<_>>();
+
+        let mut result: StdResult<(), Box<dyn StdError>> = Ok(());
+        for event in timeline {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    result = Err(e.into());
+                    break;
+                },
+            };
+
+            for (event_tx, _) in workers.iter() {
+                // A worker that has gone is noticed when it is joined.
+                let _ = event_tx.send(event.clone());
+            }
+
+            if error_policy == ErrorPolicy::Abort {
+                if let Ok(e) = error_rx.try_recv() {
+                    result = Err(e.into());
+                    break;
+                }
+            }
+        }
+
+        trace!("Wait for listener threads to finish");
+        let mut errors = Vec::new();
+        for (index, (event_tx, handle)) in workers.into_iter().enumerate() {
+            drop(event_tx);
+            match handle.join() {
+                Ok(Some(listener)) => self.listeners.push(listener),
+                Ok(None) => (),
+                Err(panic) => errors.push(ListenerError::new(index, panic_error(panic))),
+            }
+        }
+
+        for e in error_rx.try_iter().chain(errors) {
+            match error_policy {
+                ErrorPolicy::Abort => if result.is_ok() {
+                    result = Err(e.into());
+                },
+                ErrorPolicy::Log => error!("{}", e),
+                ErrorPolicy::Collect => self.errors.push(e),
+            }
+        }
+
+        result
+    }
+}
+
+impl Default for EventDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for EventDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventDispatcher")
+            .field("listeners", &self.listeners.len())
+            .field("error_policy", &self.error_policy)
+            .field("threaded", &self.threaded)
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+// Object safe listener that is able to box both of EventListener and EventListenerMut.
+trait ErasedListener: Send {
+    fn dispatch(&mut self, event_type: &EventType) -> StdResult<(), BoxedError>;
+}
+
+struct SharedListener<L>(L);
+
+impl<L> ErasedListener for SharedListener<L>
+where
+    L: EventListener + Send,
+    L::Error: Into<BoxedError>,
+{
+    fn dispatch(&mut self, event_type: &EventType) -> StdResult<(), BoxedError> {
+        dispatch_event(&self.0, event_type).map_err(|e| e.into())
+    }
+}
+
+struct MutListener<L>(L);
+
+impl<L> ErasedListener for MutListener<L>
+where
+    L: EventListenerMut + Send,
+{
+    fn dispatch(&mut self, event_type: &EventType) -> StdResult<(), BoxedError> {
+        dispatch_event_mut(&mut self.0, event_type).map_err(|e| e.into())
+    }
+}
+
+// Dispatch the event with a panic of the listener turned into an error, so that the listener is kept registered.
+fn dispatch_caught(listener: &mut dyn ErasedListener, event_type: &EventType) -> StdResult<(), BoxedError> {
+    panic::catch_unwind(AssertUnwindSafe(|| listener.dispatch(event_type)))
+        .unwrap_or_else(|panic| Err(panic_error(panic)))
+}
+
+fn panic_error(panic: Box<dyn Any + Send>) -> BoxedError {
+    let message = panic.downcast_ref::<&str>().copied()
+        .or_else(|| panic.downcast_ref::<String>().map(|s| s.as_str()))
+        .unwrap_or("unknown panic");
+    format!("Listener panicked: {}", message).into()
+}
+
+fn dispatch_event_mut<L: EventListenerMut>(
+    listener: &mut L,
+    event_type: &EventType
+) -> StdResult<(), L::Error> {
+    match event_type {
+        EventType::Update(status) => listener.update(status.as_ref()),
+        EventType::StatusUpdate(status) => listener.status_update(status.as_ref()),
+        EventType::Notification(notification) => listener.notification(notification.as_ref()),
+        EventType::Delete(status_id) => listener.delete(status_id),
+        EventType::Conversation(conversation) => listener.conversation(conversation.as_ref()),
+        EventType::Announcement(announcement) => listener.announcement(announcement.as_ref()),
+        EventType::AnnouncementReaction(reaction) => listener.announcement_reaction(reaction.as_ref()),
+        EventType::AnnouncementDelete(announcement_id) => listener.announcement_delete(announcement_id),
+        EventType::EncryptedMessage(message) => listener.encrypted_message(message.as_ref()),
+        EventType::FiltersChanged => listener.filters_changed(),
+        EventType::Unknown(msg) => listener.unknown(msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{ Arc, Mutex };
+    use crate::Result;
+
+    struct Events(std::vec::IntoIter<Result<EventType>>);
+
+    impl Iterator for Events {
+        type Item = Result<EventType>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl StreamingTimeline for Events {}
+
+    fn events() -> Events {
+        Events(vec![
+            Ok(EventType::Delete("1".into())),
+            Ok(EventType::Delete("fail".into())),
+            Ok(EventType::Delete("3".into())),
+        ].into_iter())
+    }
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl EventListenerMut for Recorder {
+        type Error = std::io::Error;
+
+        fn delete(&mut self, deleted_status: impl AsRef<str>) -> StdResult<(), Self::Error> {
+            if deleted_status.as_ref() == "fail" {
+                return Err(std::io::Error::other("failed"));
+            }
+            self.0.lock().unwrap().push(deleted_status.as_ref().to_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_abort() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.add_listener_mut(Recorder(recorded.clone()));
+
+        assert!(dispatcher.run(&mut events()).is_err());
+        assert_eq!(*recorded.lock().unwrap(), vec!["1"]);
+    }
+
+    #[test]
+    fn test_collect() {
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = EventDispatcher::new().error_policy(ErrorPolicy::Collect);
+        dispatcher
+            .add_listener_mut(Recorder(first.clone()))
+            .add_listener_mut(Recorder(second.clone()));
+
+        assert!(dispatcher.run(&mut events()).is_ok());
+        assert_eq!(*first.lock().unwrap(), vec!["1", "3"]);
+        assert_eq!(*second.lock().unwrap(), vec!["1", "3"]);
+
+        let errors = dispatcher.take_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].listener_index(), 0);
+        assert_eq!(errors[1].listener_index(), 1);
+    }
+
+    struct Panicker;
+
+    impl EventListenerMut for Panicker {
+        type Error = std::io::Error;
+
+        fn delete(&mut self, deleted_status: impl AsRef<str>) -> StdResult<(), Self::Error> {
+            if deleted_status.as_ref() == "fail" {
+                panic!("boom");
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_threaded_panic() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = EventDispatcher::new()
+            .error_policy(ErrorPolicy::Collect)
+            .threaded();
+        dispatcher
+            .add_listener_mut(Panicker)
+            .add_listener_mut(Recorder(recorded.clone()));
+
+        assert!(dispatcher.run(&mut events()).is_ok());
+        assert_eq!(dispatcher.len(), 2);
+        assert_eq!(*recorded.lock().unwrap(), vec!["1", "3"]);
+
+        // Errors of worker threads arrive in any order.
+        let mut errors = dispatcher.take_errors();
+        errors.sort_by_key(|e| e.listener_index());
+        assert_eq!(errors.iter().map(|e| e.listener_index()).collect::<Vec<usize>>(), vec![0, 1]);
+        assert_eq!(errors[0].error().to_string(), "Listener panicked: boom");
+
+        // Listeners are kept in order, so indices of errors stay the same in the next run.
+        let mut dispatcher = dispatcher.error_policy(ErrorPolicy::Abort);
+        let e = dispatcher.run(&mut events()).unwrap_err();
+        assert!(e.to_string().starts_with("Listener #"), "{}", e);
+        assert_eq!(dispatcher.len(), 2);
+    }
+
+    #[test]
+    fn test_threaded_collect() {
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+        let mut dispatcher = EventDispatcher::new()
+            .error_policy(ErrorPolicy::Collect)
+            .threaded();
+        dispatcher
+            .add_listener_mut(Recorder(first.clone()))
+            .add_listener_mut(Recorder(second.clone()));
+
+        assert!(dispatcher.run(&mut events()).is_ok());
+        assert_eq!(dispatcher.len(), 2);
+        assert_eq!(*first.lock().unwrap(), vec!["1", "3"]);
+        assert_eq!(*second.lock().unwrap(), vec!["1", "3"]);
+        assert_eq!(dispatcher.errors().len(), 2);
+    }
+}
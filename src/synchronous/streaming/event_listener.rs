@@ -7,6 +7,10 @@ use std::result::Result as StdResult;
 use std::error::Error as StdError;
 use crate::{
 	entities::{
+		Announcement,
+		AnnouncementReaction,
+		Conversation,
+		EncryptedMessage,
 		Notification,
 		Status,
 	},
@@ -29,18 +33,54 @@ pub trait EventListener {
 		Ok(())
 	}
 
+	/// This method will called when receive `status.update` event from streaming timeline.
+	#[allow(unused_variables)]
+	fn status_update(&self, status: &Status) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
 	/// This method will called when receive `notification` event from the user streaming timeline.
 	#[allow(unused_variables)]
 	fn notification(&self, notification: &Notification) -> StdResult<(), Self::Error> {
 		Ok(())
 	}
 
-	/// This method will called when receive `update` event from streaming timeline.
+	/// This method will called when receive `delete` event from streaming timeline.
 	#[allow(unused_variables)]
 	fn delete(&self, deleted_status: impl AsRef<str>) -> StdResult<(), Self::Error> {
 		Ok(())
 	}
 
+	/// This method will called when receive `conversation` event from the direct streaming timeline.
+	#[allow(unused_variables)]
+	fn conversation(&self, conversation: &Conversation) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement(&self, announcement: &Announcement) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement.reaction` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement_reaction(&self, reaction: &AnnouncementReaction) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement.delete` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement_delete(&self, deleted_announcement: impl AsRef<str>) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `encrypted_message` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn encrypted_message(&self, message: &EncryptedMessage) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
 	/// This method will called when receive `filters_chenged` event from streaming timeline.
 	fn filters_changed(&self) -> StdResult<(), Self::Error> {
 		Ok(())
@@ -54,3 +94,77 @@ pub trait EventListener {
 		Ok(())
 	}
 }
+
+/// This trait is a variant of [`EventListener`](./trait.EventListener.html) that can change its own state.
+/// 
+/// Methods of `EventListenerMut` take `&mut self` instead of `&self`, so stateful listeners do not need interior mutability.
+/// Register the implementation of `EventListenerMut` to [`EventDispatcher`](./struct.EventDispatcher.html) with [`add_listener_mut()`](./struct.EventDispatcher.html#method.add_listener_mut).
+pub trait EventListenerMut {
+	/// Error type that will return from overrided methods.
+	type Error: Into<Box<dyn StdError + Send + Sync>>;
+
+	/// This method will called when receive `update` event from streaming timeline.
+	#[allow(unused_variables)]
+	fn update(&mut self, status: &Status) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `status.update` event from streaming timeline.
+	#[allow(unused_variables)]
+	fn status_update(&mut self, status: &Status) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `notification` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn notification(&mut self, notification: &Notification) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `delete` event from streaming timeline.
+	#[allow(unused_variables)]
+	fn delete(&mut self, deleted_status: impl AsRef<str>) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `conversation` event from the direct streaming timeline.
+	#[allow(unused_variables)]
+	fn conversation(&mut self, conversation: &Conversation) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement(&mut self, announcement: &Announcement) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement.reaction` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement_reaction(&mut self, reaction: &AnnouncementReaction) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `announcement.delete` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn announcement_delete(&mut self, deleted_announcement: impl AsRef<str>) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `encrypted_message` event from the user streaming timeline.
+	#[allow(unused_variables)]
+	fn encrypted_message(&mut self, message: &EncryptedMessage) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive `filters_chenged` event from streaming timeline.
+	fn filters_changed(&mut self) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+
+	/// This method will called when receive unknown event from streaming timeline.
+	#[allow(unused_variables)]
+	fn unknown(&mut self, msg: impl AsRef<str>) -> StdResult<(), Self::Error> {
+		Ok(())
+	}
+}
@@ -1,6 +1,12 @@
 use crate::entities::{
+	Announcement,
+	AnnouncementId,
+	AnnouncementReaction,
+	Conversation,
+	EncryptedMessage,
 	Notification,
 	Status,
+	StatusId,
 };
 
 /// Represent the event types of the streaming timeline.
@@ -11,6 +17,11 @@ pub enum EventType {
     /// This event type has `Status`.
     Update(Box<Status>),
 
+    /// A status has been edited.
+    /// 
+    /// This event type has edited `Status`.
+    StatusUpdate(Box<Status>),
+
     /// A new notification has appeared.
     /// 
     /// This event type has `Notification`.
@@ -18,8 +29,33 @@ pub enum EventType {
 
     /// A status as been deleted.
     /// 
-    /// This event type has ID of the deleted status as `String`.
-    Delete(String),
+    /// This event type has ID of the deleted status as `StatusId`.
+    Delete(StatusId),
+
+    /// A direct conversation has been updated.
+    /// 
+    /// This event type has `Conversation`.
+    Conversation(Box<Conversation>),
+
+    /// An announcement has been published.
+    /// 
+    /// This event type has `Announcement`.
+    Announcement(Box<Announcement>),
+
+    /// An announcement has received an emoji reaction.
+    /// 
+    /// This event type has `AnnouncementReaction`.
+    AnnouncementReaction(Box<AnnouncementReaction>),
+
+    /// An announcement has been deleted.
+    /// 
+    /// This event type has ID of the deleted announcement as `AnnouncementId`.
+    AnnouncementDelete(AnnouncementId),
+
+    /// An encrypted message has been received.
+    /// 
+    /// This event type has `EncryptedMessage`.
+    EncryptedMessage(Box<EncryptedMessage>),
 
     /// Keyword filters have been changed.
     /// 
@@ -1,19 +1,33 @@
 //! This module provides the streaming timeline and the parts to process streamed items.
+pub mod adapters;
+mod event_dispatcher;
 mod event_listener;
 mod event_type;
+mod recording;
 mod sse_stream;
 mod stream_type;
 
-pub use event_listener::EventListener;
+pub use adapters::{ SeenUris, StatusCondition, StatusPredicate };
+pub use event_dispatcher::{ ErrorPolicy, EventDispatcher, ListenerError };
+pub use event_listener::{ EventListener, EventListenerMut };
 pub use event_type::EventType;
-pub use sse_stream::SseStream;
+pub use recording::{ RecordedEvent, ReplayStream };
+pub use sse_stream::{ CloseHandle, SseStream, DEFAULT_STALL_TIMEOUT };
 pub use stream_type::StreamType;
 
 use std::result::Result as StdResult;
 use std::error::Error as StdError;
+use std::time::Duration;
 use log::{ debug, trace };
 use crate::{
 	Result,
+	entities::Visibility,
+};
+use adapters::{
+    DedupByUri,
+    Delay,
+    FilterStatus,
+    UnwrapReblogs,
 };
 
 /// This trait represents the abstract streaming timeline.
@@ -28,7 +42,7 @@ pub trait StreamingTimeline: Iterator<Item = Result<EventType>> {
 		for event in self.into_iter() {
             match event {
                 Ok(event_type) => {
-                    if let Err(e) = dispatch_event(listener, event_type) {
+                    if let Err(e) = dispatch_event(listener, &event_type) {
                         return Err(e.into());
                     }
                 },
@@ -39,11 +53,103 @@ pub trait StreamingTimeline: Iterator<Item = Result<EventType>> {
 		}
 		Ok(())
 	}
+
+    /// Deliver events of this streaming timeline to listeners registered to [`EventDispatcher`](./struct.EventDispatcher.html).
+    fn dispatch(&mut self, dispatcher: &mut EventDispatcher) -> StdResult<(), Box<dyn StdError>>
+    where
+        Self: Sized,
+    {
+        dispatcher.run(self)
+    }
+
+    /// Skip statuses that do not satisfy `predicate`.
+    ///
+    /// Only `Update` and `StatusUpdate` events are tested, other events are passed through.
+    fn filter_status<P: StatusPredicate>(self, predicate: P) -> FilterStatus<Self, P>
+    where
+        Self: Sized,
+    {
+        FilterStatus::new(self, predicate)
+    }
+
+    /// Skip statuses that are not written in the language specified by ISO 639-1 code.
+    fn filter_language(self, lang: impl Into<String>) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Language(lang.into()))
+    }
+
+    /// Skip statuses that do not have `visibility`.
+    fn filter_visibility(self, visibility: Visibility) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Visibility(visibility))
+    }
+
+    /// Skip statuses that do not have the hashtag.
+    fn filter_hashtag(self, tag: impl Into<String>) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Hashtag(tag.into()))
+    }
+
+    /// Skip statuses that are not posted or reblogged by the account specified by ID.
+    fn filter_account(self, account_id: impl Into<String>) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Account(account_id.into()))
+    }
+
+    /// Skip statuses that have media attachments if `media` is false, and that have no media attachments otherwise.
+    fn filter_media(self, media: bool) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Media(media))
+    }
+
+    /// Skip reblogs if `reblog` is false, and statuses that are not reblogs otherwise.
+    fn filter_reblog(self, reblog: bool) -> FilterStatus<Self, StatusCondition>
+    where
+        Self: Sized,
+    {
+        self.filter_status(StatusCondition::Reblog(reblog))
+    }
+
+    /// Replace reblogs with the reblogged statuses.
+    fn unwrap_reblogs(self) -> UnwrapReblogs<Self>
+    where
+        Self: Sized,
+    {
+        UnwrapReblogs::new(self)
+    }
+
+    /// Skip statuses whose `uri` is in `seen`, pass clones of the same `seen` to de-duplicate across streaming timelines.
+    fn dedup_by_uri(self, seen: SeenUris) -> DedupByUri<Self>
+    where
+        Self: Sized,
+    {
+        DedupByUri::new(self, seen)
+    }
+
+    /// Hold statuses for `delay` and skip statuses deleted in the meantime.
+    ///
+    /// This streaming timeline is read on a worker thread, so it must be `Send`.
+    fn delay(self, delay: Duration) -> Delay<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        Delay::new(self, delay)
+    }
 }
 
 fn dispatch_event<E>(
     listener: &impl EventListener<Error = E>,
-    event_type: EventType
+    event_type: &EventType
 ) -> StdResult<(), E>
 where
     E: Into<Box<dyn StdError>>,
@@ -53,6 +159,10 @@ where
             trace!("Dispatch an update to listener");
             listener.update(status.as_ref())
         },
+        EventType::StatusUpdate(status) => {
+            trace!("Dispatch a status.update to listener");
+            listener.status_update(status.as_ref())
+        },
         EventType::Notification(notification) => {
             trace!("Dispatch a notification to listener");
             listener.notification(notification.as_ref())
@@ -61,6 +171,26 @@ where
             trace!("Dispatch a delete to listener");
             listener.delete(status_id)
         },
+        EventType::Conversation(conversation) => {
+            trace!("Dispatch a conversation to listener");
+            listener.conversation(conversation.as_ref())
+        },
+        EventType::Announcement(announcement) => {
+            trace!("Dispatch an announcement to listener");
+            listener.announcement(announcement.as_ref())
+        },
+        EventType::AnnouncementReaction(reaction) => {
+            trace!("Dispatch an announcement.reaction to listener");
+            listener.announcement_reaction(reaction.as_ref())
+        },
+        EventType::AnnouncementDelete(announcement_id) => {
+            trace!("Dispatch an announcement.delete to listener");
+            listener.announcement_delete(announcement_id)
+        },
+        EventType::EncryptedMessage(message) => {
+            trace!("Dispatch an encrypted_message to listener");
+            listener.encrypted_message(message.as_ref())
+        },
         EventType::FiltersChanged => {
             trace!("Dispatch a filters_changed to listener");
             listener.filters_changed()
@@ -0,0 +1,244 @@
+//! This module provides recording and replaying of raw Server-sent events as JSON Lines.
+//!
+//! Each line of the recorded file is a JSON object such as:
+//!
+//! ```json
+//! {"event":"delete","data":"103270115826048975\n","timestamp":"2020-07-03T01:27:38.726Z"}
+//! ```
+//!
+//! Record events of the live streaming timeline with [`SseStream::record()`](./struct.SseStream.html#method.record),
+//! and replay them offline with [`ReplayStream`](./struct.ReplayStream.html).
+use std::fs::File;
+use std::io::{ BufRead, BufReader, Write };
+use std::path::Path;
+use std::thread;
+use eventsource::event::Event;
+use log::{ trace, warn };
+use serde::{ Deserialize, Serialize };
+use crate::{
+    DateTime,
+    Result,
+    Utc,
+};
+use super::{
+    EventType,
+    StreamingTimeline,
+    sse_stream::get_event_type,
+};
+
+/// Represents a raw Server-sent event with the received time.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    event: Option<String>,
+    data: String,
+    timestamp: DateTime<Utc>,
+}
+
+impl RecordedEvent {
+    pub(crate) fn new(event: &Event, timestamp: DateTime<Utc>) -> Self {
+        RecordedEvent {
+            id: event.id.clone(),
+            event: event.event_type.clone(),
+            data: event.data.clone(),
+            timestamp,
+        }
+    }
+
+    /// Get the ID of this event if present.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Get the event name such as `update` if present.
+    pub fn event(&self) -> Option<&str> {
+        self.event.as_deref()
+    }
+
+    /// Get the raw data of this event, lines are terminated by `\n`.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Get the time when this event was received.
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Parse this event as an [`EventType`](./enum.EventType.html) in the same way as the live streaming timeline.
+    pub fn event_type(&self) -> Result<EventType> {
+        get_event_type(&Event {
+            id: self.id.clone(),
+            event_type: self.event.clone(),
+            data: self.data.clone(),
+        })
+    }
+}
+
+// Write a recorded event as a line, failures are only logged to keep the streaming timeline alive.
+pub(crate) fn record_event(writer: &mut dyn Write, event: &Event) {
+    let recorded = RecordedEvent::new(event, Utc::now());
+
+    let result = serde_json::to_writer(&mut *writer, &recorded)
+        .map_err(std::io::Error::from)
+        .and_then(|_| writer.write_all(b"\n"))
+        .and_then(|_| writer.flush());
+
+    if let Err(e) = result {
+        warn!("Failed to record a streaming event: {}", e);
+    }
+}
+
+/// A streaming timeline that replays events recorded as JSON Lines.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mastors::prelude::*;
+/// use mastors::streaming::ReplayStream;
+///
+/// for event in ReplayStream::open("user_stream.jsonl")?.honor_timing() {
+///     println!("{:?}", event?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplayStream {
+    reader: Box<dyn BufRead + Send>,
+    honor_timing: bool,
+    speed: f64,
+    last_timestamp: Option<DateTime<Utc>>,
+}
+
+impl ReplayStream {
+    /// Create a new `ReplayStream` that reads the recorded file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::from_reader(BufReader::new(File::open(path)?)))
+    }
+
+    /// Create a new `ReplayStream` that reads recorded events from `reader`.
+    pub fn from_reader(reader: impl BufRead + Send + 'static) -> Self {
+        ReplayStream {
+            reader: Box::new(reader),
+            honor_timing: false,
+            speed: 1.0,
+            last_timestamp: None,
+        }
+    }
+
+    /// Set to wait between events as long as the original intervals.
+    pub fn honor_timing(mut self) -> Self {
+        self.honor_timing = true;
+        self
+    }
+
+    /// Set the playback speed when honoring the original timing, `2.0` waits half of the original intervals.
+    ///
+    /// Non-positive values are ignored.
+    pub fn speed(mut self, speed: f64) -> Self {
+        if speed > 0.0 {
+            self.speed = speed;
+        }
+        self
+    }
+
+    fn wait_for(&mut self, timestamp: DateTime<Utc>) {
+        if let Some(last) = self.last_timestamp {
+            if let Ok(interval) = (timestamp - last).to_std() {
+                thread::sleep(interval.div_f64(self.speed));
+            }
+        }
+        self.last_timestamp = Some(timestamp);
+    }
+}
+
+impl StreamingTimeline for ReplayStream {}
+
+impl Iterator for ReplayStream {
+    type Item = Result<EventType>;
+
+    /// Get the next recorded event.
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => break,
+                Err(e) => return Some(Err(e.into())),
+            };
+        }
+
+        let recorded = match serde_json::from_str::<RecordedEvent>(&line) {
+            Ok(recorded) => recorded,
+            Err(e) => return Some(Err(e.into())),
+        };
+        trace!("Replay an event recorded at {}", recorded.timestamp());
+
+        if self.honor_timing {
+            self.wait_for(recorded.timestamp());
+        }
+        Some(recorded.event_type())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::time::{ Duration, Instant };
+
+    fn event(event_type: &str, data: &str) -> Event {
+        Event {
+            id: None,
+            event_type: Some(event_type.to_owned()),
+            data: data.to_owned() + "\n",
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut recorded = Vec::new();
+        record_event(&mut recorded, &event("delete", "1"));
+        record_event(&mut recorded, &event("filters_changed", ""));
+        record_event(&mut recorded, &event("something.new", "{}"));
+
+        let line = String::from_utf8(recorded.clone()).unwrap();
+        assert!(line.starts_with(r#"{"event":"delete","data":"1\n","timestamp":""#));
+
+        let replayed = ReplayStream::from_reader(Cursor::new(recorded))
+            .map(|e| e.unwrap())
+            .collect::<Vec<EventType>>();
+        assert_eq!(replayed, vec![
+            EventType::Delete("1".into()),
+            EventType::FiltersChanged,
+            EventType::Unknown("something.new: {}\n".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn test_replay_with_timing() {
+        let lines = r#"
+{"event":"delete","data":"1\n","timestamp":"2020-07-03T01:27:38.000Z"}
+{"event":"delete","data":"2\n","timestamp":"2020-07-03T01:27:38.100Z"}
+"#;
+
+        let started = Instant::now();
+        let mut stream = ReplayStream::from_reader(Cursor::new(lines)).honor_timing().speed(2.0);
+        assert_eq!(stream.next().unwrap().unwrap(), EventType::Delete("1".into()));
+        assert_eq!(stream.next().unwrap().unwrap(), EventType::Delete("2".into()));
+        assert!(stream.next().is_none());
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_replay_broken_line() {
+        let mut stream = ReplayStream::from_reader(Cursor::new("not a json\n"));
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+}
@@ -1,13 +1,32 @@
 //! This module provides an implementation of [`StreamingTimeline`](./trait.StreamingTimeline.html) using Server-sent events.
+use std::fs::OpenOptions;
+use std::io::{ self, BufRead, BufReader, BufWriter, Write };
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use std::thread;
+use std::time::{ Duration, Instant };
 use eventsource::{
-    event::Event,
-    reqwest::Client,
+    event::{ Event, ParseResult, parse_event_line },
+    reqwest::{
+        Error as SseError,
+        ErrorKind as SseErrorKind,
+    },
+};
+use log::{ debug, trace, warn };
+use reqwest::{
+    blocking::Client,
+    header,
 };
 use crate::{
     Error,
     Result,
     Url,
     entities::{
+        Announcement,
+        AnnouncementReaction,
+        Conversation,
+        EncryptedMessage,
         Notification,
         Status,
     },
@@ -15,19 +34,173 @@ use crate::{
 use super::{
 	EventType,
 	StreamingTimeline,
+	recording::record_event,
 };
 
+/// The default duration without any bytes, including heartbeats, until the stream is regarded as stalled.
+///
+/// Mastodon sends a heartbeat every 15 seconds.
+pub const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+// The default reconnection time, same as the eventsource crate.
+const DEFAULT_RETRY: Duration = Duration::from_millis(5000);
+
 /// Represents the stream of each timeline with Server-sent events.
+///
+/// The stream reconnects transparently when the connection is closed by the server.
+/// If no bytes arrive within the stall timeout, the stream yields `Error::StreamStalled` and reconnects on the next call.
+/// Heartbeats are not yielded as events.
 pub struct SseStream {
     client: Client,
+    url: Url,
+    reader: Option<Box<dyn BufRead + Send>>,
+    last_event_id: Option<String>,
+    last_try: Option<Instant>,
+    last_activity: Instant,
+    retry: Duration,
+    stall_timeout: Duration,
+    closed: Arc<AtomicBool>,
+    line: Vec<u8>,
+    event: Event,
+    recorder: Option<Box<dyn Write + Send>>,
 }
 
 impl SseStream {
-    pub(crate) fn new(url: Url, client: reqwest::blocking::Client) -> Self {
+    pub(crate) fn new(url: Url, client: Client, stall_timeout: Duration) -> Self {
         SseStream {
-            client: Client::new_with_client(url, client),
+            client,
+            url,
+            reader: None,
+            last_event_id: None,
+            last_try: None,
+            last_activity: Instant::now(),
+            retry: DEFAULT_RETRY,
+            stall_timeout,
+            closed: Arc::new(AtomicBool::new(false)),
+            line: Vec::new(),
+            event: Event::new(),
+            recorder: None,
+        }
+    }
+
+    /// Tee raw events to `writer` as JSON Lines that can be replayed with [`ReplayStream`](./struct.ReplayStream.html).
+    ///
+    /// Failures to write are logged and do not stop the stream.
+    pub fn record(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.recorder = Some(Box::new(writer));
+        self
+    }
+
+    /// Tee raw events to the file at `path`, events are appended if the file exists.
+    pub fn record_to_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(self.record(BufWriter::new(file)))
+    }
+
+    /// Get the time when any bytes, including heartbeats, arrived last.
+    pub fn last_activity(&self) -> Instant {
+        self.last_activity
+    }
+
+    /// Get the duration without any bytes until the stream is regarded as stalled.
+    pub fn stall_timeout(&self) -> Duration {
+        self.stall_timeout
+    }
+
+    /// Close this stream, the iterator ends at the next call or within the read timeout of the current call.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Get a handle to close this stream from another thread.
+    pub fn close_handle(&self) -> CloseHandle {
+        CloseHandle {
+            closed: self.closed.clone(),
         }
     }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    fn connect(&mut self) -> std::result::Result<Box<dyn BufRead + Send>, SseError> {
+        // Wait for the reconnection time since the last try.
+        if let Some(last_try) = self.last_try {
+            let elapsed = last_try.elapsed();
+            if elapsed < self.retry {
+                thread::sleep(self.retry - elapsed);
+            }
+        }
+        self.last_try = Some(Instant::now());
+        debug!("Connect to the streaming timeline: {}", self.url);
+
+        let mut req = self.client.get(self.url.clone())
+            .header(header::ACCEPT, "text/event-stream");
+        if let Some(id) = self.last_event_id.as_ref() {
+            req = req.header("Last-Event-ID", id.as_str());
+        }
+        let res = req.send()?;
+
+        if !res.status().is_success() {
+            return Err(SseErrorKind::Http(res.status()).into());
+        }
+
+        match res.headers().get(header::CONTENT_TYPE).map(|v| v.to_str()) {
+            Some(Ok(content_type)) if content_type.starts_with("text/event-stream") => (),
+            Some(content_type) => return Err(
+                format!("unexpected Content-Type: {:?}", content_type).into()
+            ),
+            None => return Err(SseErrorKind::NoContentType.into()),
+        };
+
+        Ok(Box::new(BufReader::new(res)))
+    }
+
+    fn read_event(&mut self) -> io::Result<Option<Event>> {
+        loop {
+            let reader = match self.reader.as_mut() {
+                Some(reader) => reader,
+                None => return Ok(None),
+            };
+
+            // Bytes read before an error are kept in the line buffer, so a timed out line continues at the next read.
+            let len = self.line.len();
+            let result = reader.read_until(b'\n', &mut self.line);
+            if self.line.len() > len {
+                self.last_activity = Instant::now();
+            }
+
+            match result? {
+                0 => return Ok(None),
+                _ if !self.line.ends_with(b"\n") => continue,
+                _ => (),
+            };
+
+            let line = String::from_utf8_lossy(&self.line).into_owned();
+            self.line.clear();
+
+            match parse_event_line(&line, &mut self.event) {
+                ParseResult::Next => (),
+                ParseResult::SetRetry(retry) => self.retry = retry,
+                ParseResult::Dispatch => {
+                    if self.event.is_empty() {
+                        trace!("Received a heartbeat");
+                        continue;
+                    }
+                    if let Some(id) = self.event.id.as_ref() {
+                        self.last_event_id = Some(id.to_owned());
+                    }
+                    return Ok(Some(std::mem::replace(&mut self.event, Event::new())));
+                },
+            };
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.reader = None;
+        self.line.clear();
+        self.event.clear();
+    }
 }
 
 impl StreamingTimeline for SseStream {}
@@ -37,16 +210,82 @@ impl Iterator for SseStream {
 
     /// Get the next streaming event from streaming timeline.
     fn next(&mut self) -> Option<Self::Item> {
-        self.client.next().map(|result| {
-            match result {
-                Ok(event) => get_event_type(&event),
-                Err(e) => Err(Error::SseStreamError(e)),
+        loop {
+            if self.is_closed() {
+                debug!("Streaming timeline has been closed");
+                self.disconnect();
+                return None;
             }
-        })
+
+            if self.reader.is_none() {
+                match self.connect() {
+                    Ok(reader) => {
+                        self.reader = Some(reader);
+                        self.last_activity = Instant::now();
+                    },
+                    Err(e) => return Some(Err(Error::SseStreamError(e))),
+                };
+            }
+
+            match self.read_event() {
+                Ok(Some(event)) => {
+                    if let Some(recorder) = self.recorder.as_mut() {
+                        record_event(recorder, &event);
+                    }
+                    return Some(get_event_type(&event));
+                },
+                Ok(None) => {
+                    debug!("Streaming timeline has been closed by the server, reconnect");
+                    self.disconnect();
+                },
+                Err(e) if is_timeout(&e) => {
+                    let elapsed = self.last_activity.elapsed();
+                    if elapsed >= self.stall_timeout && !self.is_closed() {
+                        warn!("Streaming timeline has been stalled for {:?}", elapsed);
+                        self.disconnect();
+                        self.last_activity = Instant::now();
+                        return Some(Err(Error::StreamStalled(elapsed)));
+                    }
+                },
+                Err(e) => {
+                    debug!("Failed to read the streaming timeline, reconnect: {}", e);
+                    self.disconnect();
+                },
+            };
+        }
+    }
+}
+
+/// A handle to close [`SseStream`](./struct.SseStream.html) from another thread.
+#[derive(Debug, Clone)]
+pub struct CloseHandle {
+    closed: Arc<AtomicBool>,
+}
+
+impl CloseHandle {
+    /// Close the stream, the iterator ends at the next call or within the read timeout of the current call.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Get whether the stream has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
     }
 }
 
-fn get_event_type(event: &Event) -> Result<EventType> {
+// The blocking reqwest returns a timed out read as an io::Error that wraps reqwest::Error.
+fn is_timeout(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => true,
+        _ => e.get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+            .map(|inner| inner.is_timeout())
+            .unwrap_or(false),
+    }
+}
+
+pub(super) fn get_event_type(event: &Event) -> Result<EventType> {
     if let Some(event_type) = &event.event_type {
         match event_type.as_str() {
             "update" => {
@@ -54,13 +293,41 @@ fn get_event_type(event: &Event) -> Result<EventType> {
                     Box::new(serde_json::from_str::<Status>(&event.data)?)
                 ))
             },
+            "status.update" => {
+                Ok(EventType::StatusUpdate(
+                    Box::new(serde_json::from_str::<Status>(&event.data)?)
+                ))
+            },
             "notification" => {
                 Ok(EventType::Notification(
                     Box::new(serde_json::from_str::<Notification>(&event.data)?)
                 ))
             },
             "delete" => {
-                Ok(EventType::Delete(event.data.to_owned()))
+                Ok(EventType::Delete(event.data.trim().into()))
+            },
+            "conversation" => {
+                Ok(EventType::Conversation(
+                    Box::new(serde_json::from_str::<Conversation>(&event.data)?)
+                ))
+            },
+            "announcement" => {
+                Ok(EventType::Announcement(
+                    Box::new(serde_json::from_str::<Announcement>(&event.data)?)
+                ))
+            },
+            "announcement.reaction" => {
+                Ok(EventType::AnnouncementReaction(
+                    Box::new(serde_json::from_str::<AnnouncementReaction>(&event.data)?)
+                ))
+            },
+            "announcement.delete" => {
+                Ok(EventType::AnnouncementDelete(event.data.trim().into()))
+            },
+            "encrypted_message" => {
+                Ok(EventType::EncryptedMessage(
+                    Box::new(serde_json::from_str::<EncryptedMessage>(&event.data)?)
+                ))
             },
             "filters_changed" => {
                 Ok(EventType::FiltersChanged)
@@ -75,3 +342,147 @@ fn get_event_type(event: &Event) -> Result<EventType> {
         Ok(EventType::Unknown(format!("{}: {}", "", event.data)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::io::Read;
+
+    // A reader that returns chunks and timeouts in order, and then blocks forever with timeouts.
+    struct Chunks(VecDeque<Option<&'static [u8]>>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.pop_front() {
+                Some(Some(chunk)) => {
+                    buf[..chunk.len()].copy_from_slice(chunk);
+                    Ok(chunk.len())
+                },
+                _ => Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+            }
+        }
+    }
+
+    fn stream(chunks: Vec<Option<&'static [u8]>>, stall_timeout: Duration) -> SseStream {
+        let mut stream = SseStream::new(
+            Url::parse("https://example.com/api/v1/streaming/user").unwrap(),
+            Client::new(),
+            stall_timeout,
+        );
+        stream.reader = Some(Box::new(BufReader::new(Chunks(chunks.into_iter().collect()))));
+        stream
+    }
+
+    #[test]
+    fn test_read_events_with_heartbeats_and_timeouts() {
+        let mut stream = stream(vec![
+            Some(b":thump\n\n"),
+            Some(b"event: del"),
+            None,
+            Some(b"ete\ndata: 1"),
+            None,
+            Some(b"23\n\n"),
+        ], Duration::from_secs(60));
+
+        assert_eq!(stream.next().unwrap().unwrap(), EventType::Delete("123".into()));
+    }
+
+    #[test]
+    fn test_stalled() {
+        let mut stream = stream(vec![Some(b":thump\n\n")], Duration::from_millis(0));
+
+        match stream.next() {
+            Some(Err(Error::StreamStalled(_))) => (),
+            other => panic!("Unexpected result: {:?}", other.map(|r| r.is_ok())),
+        }
+        assert!(stream.reader.is_none());
+    }
+
+    #[test]
+    fn test_close() {
+        let mut stream = stream(vec![Some(b"event: delete\ndata: 1\n\n")], Duration::from_secs(60));
+        let handle = stream.close_handle();
+
+        handle.close();
+        assert!(handle.is_closed());
+        assert!(stream.next().is_none());
+    }
+
+    fn event(event_type: &str, data: &str) -> Event {
+        Event {
+            id: None,
+            event_type: Some(event_type.to_owned()),
+            data: data.to_owned() + "\n",
+        }
+    }
+
+    #[test]
+    fn test_delete_events() {
+        assert_eq!(
+            get_event_type(&event("delete", "103270115826048975")).unwrap(),
+            EventType::Delete("103270115826048975".into())
+        );
+        assert_eq!(
+            get_event_type(&event("announcement.delete", "8")).unwrap(),
+            EventType::AnnouncementDelete("8".into())
+        );
+    }
+
+    #[test]
+    fn test_announcement_reaction_event() {
+        let got = get_event_type(
+            &event("announcement.reaction", r#"{"name":"bongoCat","count":9,"announcement_id":"8"}"#)
+        ).unwrap();
+
+        match got {
+            EventType::AnnouncementReaction(reaction) => {
+                assert_eq!(reaction.name(), "bongoCat");
+                assert_eq!(reaction.count(), 9);
+                assert_eq!(reaction.announcement_id(), "8");
+            },
+            _ => panic!("Unexpected event type: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn test_announcement_event() {
+        let got = get_event_type(&event("announcement", r#"{
+            "id": "8",
+            "content": "<p>Looks like there was an issue processing audio attachments without embedded art since yesterday due to an experimental new feature. That issue has now been fixed, so you may see older posts with audio from other servers pop up in your feeds now as they are being finally properly processed. Sorry!</p>",
+            "starts_at": null,
+            "ends_at": null,
+            "all_day": false,
+            "published_at": "2020-07-03T01:27:38.726Z",
+            "updated_at": "2020-07-03T01:27:38.752Z",
+            "read": true,
+            "mentions": [],
+            "statuses": [],
+            "tags": [],
+            "emojis": [],
+            "reactions": [
+                { "name": "bongoCat", "count": 9, "me": false, "url": "https://files.mastodon.social/custom_emojis/images/000/067/715/original/fdba57dff7576d53.png", "static_url": "https://files.mastodon.social/custom_emojis/images/000/067/715/static/fdba57dff7576d53.png" },
+                { "name": "🤔", "count": 1, "me": true }
+            ]
+        }"#)).unwrap();
+
+        match got {
+            EventType::Announcement(announcement) => {
+                assert_eq!(announcement.id(), "8");
+                assert!(announcement.read());
+                assert_eq!(announcement.reactions().len(), 2);
+                assert!(announcement.reactions()[1].me());
+                assert!(announcement.reactions()[1].url().is_none());
+            },
+            _ => panic!("Unexpected event type: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        assert_eq!(
+            get_event_type(&event("something.new", "{}")).unwrap(),
+            EventType::Unknown("something.new: {}\n".to_owned())
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use reqwest::blocking::Response;
+use chrono::{ DateTime, Duration, Utc };
+use reqwest::{
+    blocking::Response,
+    header::{ HeaderMap, RETRY_AFTER },
+};
 use crate::{
     Error,
     Result,
@@ -7,12 +11,19 @@ use crate::{
 
 const EXPECTED_CONTENT_TYPE: &str = "application/json";
 
+// Mastodon sends the end of the current rate limit period, see app/controllers/concerns/rate_limit_headers.rb.
+const RATE_LIMIT_RESET: &str = "x-ratelimit-reset";
+
+const HTTP_TOO_MANY_REQUESTS: u16 = 429;
+
 pub(crate) fn check_response(resp: Response) -> Result<Response> {
     let status = resp.status();
     let url = resp.url().clone();
 
     if status.is_success() {
         Ok(resp)
+    } else if status.as_u16() == HTTP_TOO_MANY_REQUESTS {
+        Err(Error::HttpTooManyRequestsError(url, rate_limit_reset(resp.headers(), Utc::now())))
     } else if status.is_client_error() {
         if let Some(content_type) = resp.headers().get(reqwest::header::CONTENT_TYPE) {
             if content_type.to_str().unwrap_or("").starts_with(EXPECTED_CONTENT_TYPE) {
@@ -31,6 +42,24 @@ pub(crate) fn check_response(resp: Response) -> Result<Response> {
     }
 }
 
+// Get the time the rate limit is reset from `Retry-After` in seconds or an HTTP date, or from `X-RateLimit-Reset`.
+fn rate_limit_reset(headers: &HeaderMap, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+
+    let retry_after = header(RETRY_AFTER.as_str()).and_then(|value| {
+        match value.parse::<i64>() {
+            Ok(seconds) => Some(now + Duration::seconds(seconds)),
+            Err(_) => DateTime::parse_from_rfc2822(value).ok().map(|date| date.with_timezone(&Utc)),
+        }
+    });
+
+    retry_after.or_else(|| {
+        header(RATE_LIMIT_RESET)
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|date| date.with_timezone(&Utc))
+    })
+}
+
 pub(crate) fn build_array_query<'a, T>(key: &'a str, values: &'a [T]) -> Vec<(&'a str, &'a str)>
 where
     T: AsRef<str> + 'a,
@@ -41,3 +70,34 @@ where
     }
     array_query
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(values: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in values {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_rate_limit_reset() {
+        let now = DateTime::parse_from_rfc3339("2019-08-05T15:29:30Z").unwrap().with_timezone(&Utc);
+        let reset = DateTime::parse_from_rfc3339("2019-08-05T15:30:00Z").unwrap().with_timezone(&Utc);
+
+        assert_eq!(rate_limit_reset(&headers(&[("retry-after", "30")]), now), Some(reset));
+        assert_eq!(rate_limit_reset(&headers(&[("retry-after", "Mon, 05 Aug 2019 15:30:00 GMT")]), now), Some(reset));
+        assert_eq!(rate_limit_reset(&headers(&[("x-ratelimit-reset", "2019-08-05T15:30:00.000Z")]), now), Some(reset));
+
+        // Retry-After takes precedence, and an invalid value falls back to X-RateLimit-Reset.
+        assert_eq!(rate_limit_reset(&headers(&[("retry-after", "30"), ("x-ratelimit-reset", "2019-08-05T16:00:00.000Z")]), now), Some(reset));
+        assert_eq!(rate_limit_reset(&headers(&[("retry-after", "soon"), ("x-ratelimit-reset", "2019-08-05T15:30:00.000Z")]), now), Some(reset));
+
+        assert_eq!(rate_limit_reset(&headers(&[("x-ratelimit-reset", "soon")]), now), None);
+        assert_eq!(rate_limit_reset(&HeaderMap::new(), now), None);
+    }
+}
@@ -5,5 +5,13 @@ pub(crate) use self::serde::transform_string_to_u64;
 pub(crate) use self::serde::transform_string_to_i64;
 pub(crate) use self::serde::transform_string_to_option_string;
 pub(crate) use self::serde::transform_string_to_option_url;
+pub(crate) use self::serde::transform_usize_to_string;
+pub(crate) use self::serde::transform_u64_to_string;
+pub(crate) use self::serde::transform_i64_to_string;
+pub(crate) use self::serde::transform_option_string_to_string;
+pub(crate) use self::serde::transform_option_url_to_string;
+pub(crate) use self::serde::transform_url_to_string_without_trailing_slash;
+pub(crate) use self::serde::transform_datetime_to_string;
+pub(crate) use self::serde::transform_option_datetime_to_string;
 pub(crate) use crate::current_mode::utils::reqwest::check_response;
 pub(crate) use crate::current_mode::utils::reqwest::build_array_query;
@@ -1,6 +1,8 @@
+use chrono::{ DateTime, SecondsFormat, Utc };
 use serde::{
     Deserialize,
     Deserializer,
+    Serializer,
     de::Error,
 };
 
@@ -9,7 +11,7 @@ where
     D: Deserializer<'de>
 {
 
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s = String::deserialize(deserializer)?;
     s.parse::<usize>().map_err(D::Error::custom)
 }
 
@@ -18,7 +20,7 @@ where
     D: Deserializer<'de>
 {
 
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s = String::deserialize(deserializer)?;
     s.parse::<u64>().map_err(D::Error::custom)
 }
 
@@ -27,7 +29,7 @@ where
     D: Deserializer<'de>
 {
 
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s = String::deserialize(deserializer)?;
     s.parse::<i64>().map_err(D::Error::custom)
 }
 
@@ -37,11 +39,11 @@ pub(crate) fn transform_string_to_option_string<'de, D>(deserializer: D) -> Resu
 where
     D:Deserializer<'de>
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s = String::deserialize(deserializer)?;
     if s.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(s.to_string()))
+        Ok(Some(s))
     }
 }
 pub(crate) fn transform_string_to_option_url<'de, D>(deserializer: D) -> Result<Option<Url>, D::Error>
@@ -49,10 +51,78 @@ where
     D: Deserializer<'de>,
 {
 
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    let s = String::deserialize(deserializer)?;
     if s.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(Url::parse(s).map_err(D::Error::custom)?))
+        Ok(Some(Url::parse(&s).map_err(D::Error::custom)?))
+    }
+}
+
+// The following functions are the reverse of the above, to serialize entities to the same format as Mastodon.
+
+pub(crate) fn transform_usize_to_string<S>(value: &usize, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+pub(crate) fn transform_u64_to_string<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+pub(crate) fn transform_i64_to_string<S>(value: &i64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+pub(crate) fn transform_option_string_to_string<S>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(value.as_deref().unwrap_or_default())
+}
+
+pub(crate) fn transform_option_url_to_string<S>(value: &Option<Url>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(value.as_ref().map(Url::as_str).unwrap_or_default())
+}
+
+// Mastodon sends base URLs such as `wss://mastodon.social` without a trailing slash, but `Url` always has a path.
+pub(crate) fn transform_url_to_string_without_trailing_slash<S>(value: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    let url = value.as_str();
+    if value.path() == "/" && value.query().is_none() && value.fragment().is_none() {
+        serializer.serialize_str(url.trim_end_matches('/'))
+    } else {
+        serializer.serialize_str(url)
+    }
+}
+
+// Mastodon always sends timestamps with milliseconds such as `2020-01-01T00:00:00.000Z`.
+pub(crate) fn transform_datetime_to_string<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+pub(crate) fn transform_option_datetime_to_string<S>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    match value {
+        Some(value) => transform_datetime_to_string(value, serializer),
+        None => serializer.serialize_none(),
     }
 }